@@ -0,0 +1,113 @@
+use crate::*;
+
+// older lines are dropped
+const CHAT_KEPT_LINES: usize = 100;
+
+// the chat pane of a room, kept across the states inside it
+#[derive(Clone, Debug, Default)]
+pub struct Chat {
+    // (sender, text), the oldest first, sender is empty for notices
+    pub lines: Vec<(String, String)>,
+    pub input: Input,
+    // keys go to the pane instead of the state while open
+    pub open: bool,
+    // lines scrolled back from the latest
+    pub scroll: usize,
+    // arrived while the pane was closed
+    pub unread: usize,
+}
+
+impl Chat {
+    fn push(&mut self, name: String, text: String) {
+        if self.lines.len() == CHAT_KEPT_LINES {
+            self.lines.remove(0);
+        }
+        self.lines.push((name, text));
+        if !self.open {
+            self.unread += 1;
+        } else if self.scroll != 0 {
+            // stay on the lines being read
+            self.scroll = (self.scroll + 1).min(self.lines.len() - 1);
+        }
+    }
+}
+
+impl ClientStateManager {
+    // the states with a chat pane
+    pub(crate) fn has_chat(&self) -> bool {
+        matches!(self.state,
+            ClientStateInternal::WaitPlayer {..} | ClientStateInternal::WaitReady {..}
+            | ClientStateInternal::Gaming {..} | ClientStateInternal::GameResult {..}
+        )
+    }
+
+    pub(crate) fn chat_is_open(&self) -> bool {
+        self.chat.open && self.has_chat()
+    }
+
+    pub fn handle_toggle_chat(&mut self) -> bool {
+        if !self.has_chat() {
+            return false
+        }
+        self.chat.open = !self.chat.open;
+        self.chat.unread = 0;
+        self.chat.scroll = 0;
+        true
+    }
+
+    pub fn handle_chat_msg(&mut self, cm: ChatMsg) -> bool {
+        self.chat.push(cm.name, cm.text);
+        self.has_chat()
+    }
+
+    // keys while the pane is open, return None for the ones it does not take
+    pub async fn handle_chat_key(&mut self, e: &ClientEvent) -> Option<bool> {
+        let input = &mut self.chat.input;
+        match e {
+            ClientEvent::Type(c) => input.handle(InputRequest::InsertChar(*c)),
+            ClientEvent::Backspace => input.handle(InputRequest::DeletePrevChar),
+            ClientEvent::Delete => input.handle(InputRequest::DeleteNextChar),
+            ClientEvent::LeftArrow => input.handle(InputRequest::GoToPrevChar),
+            ClientEvent::RightArrow => input.handle(InputRequest::GoToNextChar),
+            ClientEvent::ResetInput(new_input) => *input = new_input.clone(),
+            ClientEvent::UpArrow => {
+                self.chat.scroll = (self.chat.scroll + 1).min(self.chat.lines.len().saturating_sub(1));
+            }
+            ClientEvent::DownArrow => {
+                self.chat.scroll = self.chat.scroll.saturating_sub(1);
+            }
+            ClientEvent::Esc | ClientEvent::ToggleChat => return Some(self.handle_toggle_chat()),
+            ClientEvent::Enter => self.send_chat().await,
+            _ => return None,
+        }
+        Some(true)
+    }
+
+    async fn send_chat(&mut self) {
+        let text = self.chat.input.value().trim().to_string();
+        if text.is_empty() {
+            return
+        }
+        let (c, pid, roomid) = match self.state {
+            ClientStateInternal::WaitPlayer { ref mut client, ref roomid, ref players, .. }
+            | ClientStateInternal::WaitReady { ref mut client, ref roomid, ref players, .. }
+                => (client, players[0].1, roomid.clone()),
+            ClientStateInternal::Gaming { ref mut client, ref roomid, my_remote_idx, .. }
+            | ClientStateInternal::GameResult { ref mut client, ref roomid, my_remote_idx, .. }
+                => (client, my_remote_idx, roomid.clone()),
+            _ => return,
+        };
+
+        // our own msg comes back on the stream like everyone else's
+        match c.send_chat(pid, roomid, text).await {
+            Ok(()) => {
+                self.chat.input.reset();
+                self.chat.scroll = 0;
+            }
+            Err(s) => {
+                warn!("Failed to send chat: {}", s);
+                self.chat.push("".into(), format!("Not sent: {}", s.message()));
+            }
+        }
+    }
+}
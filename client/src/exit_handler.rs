@@ -22,8 +22,12 @@ impl ClientStateManager {
     pub async fn handle_exitmenu_event(&mut self, e: ExitMenuEvent) -> (bool, bool) {
         let button_num = match self.state {
             ClientStateInternal::GetServer {..} | ClientStateInternal::AskName {..}
-            | ClientStateInternal::JoinRoom {..} | ClientStateInternal::NewRoom {..} => 2,
-            ClientStateInternal::WaitPlayer {..} | ClientStateInternal::WaitReady {..} => 3,
+            | ClientStateInternal::JoinRoom {..} | ClientStateInternal::NewRoom {..}
+            | ClientStateInternal::Lobby {..} => 2,
+            ClientStateInternal::WaitPlayer {..} | ClientStateInternal::WaitReady {..}
+                if self.is_host() => 4,
+            ClientStateInternal::WaitPlayer {..} | ClientStateInternal::WaitReady {..}
+            | ClientStateInternal::Spectating {..} => 3,
             ClientStateInternal::Gaming {..} | ClientStateInternal::GameResult {..} => 4,
         };
         let mut cancel_stream = false;
@@ -46,10 +50,23 @@ impl ClientStateManager {
     // return (cancel_stream_listener, full_exit)
     pub async fn handle_exitmenu_enter(&mut self) -> (bool, bool) {
         self.exitmenu.0 = false;
+        // the host has Host Menu as the second button, the rest move down by one
+        if self.is_host() {
+            match self.exitmenu.1 {
+                1 => {
+                    self.exitmenu.1 = 0;
+                    self.handle_open_host_menu();
+                    return (false, false)
+                }
+                2 | 3 => self.exitmenu.1 -= 1,
+                _ => {}
+            }
+        }
         match self.state {
             ClientStateInternal::GetServer {..}
             | ClientStateInternal::AskName {..}
             | ClientStateInternal::NewRoom {..}
+            | ClientStateInternal::Lobby {..}
             | ClientStateInternal::JoinRoom {..} => {
                 match self.exitmenu.1 {
                     0 => {},
@@ -86,6 +103,28 @@ impl ClientStateManager {
                     _ => panic!("Invalid button num!"),
                 }
             }
+            ClientStateInternal::Spectating {
+                client: ref c, ref name, ..
+            } => {
+                match self.exitmenu.1 {
+                    0 => {}
+                    // dropping the stream is all the server needs
+                    1 => {
+                        self.state = ClientStateInternal::AskName {
+                            client: c.clone(),
+                            input: Input::new(name.clone()),
+                            msg: "Stopped watching the room.\n\
+                                    Please enter your nickname:".into(),
+                            button: 0,
+                            is_input: true,
+                        };
+                        self.exitmenu.1 = 0;
+                        return (true, false)
+                    },
+                    2 => return (true, true),
+                    _ => panic!("Invalid button num!"),
+                }
+            }
             ClientStateInternal::Gaming {
                 client: ref mut c, ref game, ref roomid, my_remote_idx, ..
             } => {
@@ -96,10 +135,12 @@ impl ClientStateManager {
                         self.state = ClientStateInternal::WaitReady {
                             client: c.clone(),
                             players: game.get_player_names().into_iter().enumerate().map(
-                                |(i, name)| (name, Self::get_remote_idx(my_remote_idx, i), false)
+                                |(i, name)| (name, Self::get_remote_idx(my_remote_idx, i, game.get_seats()), false)
                             ).collect(),
                             roomid: roomid.clone(),
                             msg: vec!["Please press the button to get ready!".into()],
+                            rules: game.get_rules(),
+                            profiles: vec![],
                         };
                         self.exitmenu.1 = 0;
                     }
@@ -138,6 +179,8 @@ impl ClientStateManager {
                             client: c.clone(),
                             roomid: roomid.clone(),
                             msg: vec!["Please press the button to get ready!".into()],
+                            rules: ri.rules.unwrap_or_default(),
+                            profiles: vec![],
                         };
                         self.exitmenu.1 = 0;
                     }
@@ -0,0 +1,250 @@
+use crate::*;
+
+// Back, Lock, Seats, Kick, Make Host
+pub const HOST_MENU_ITEMS: usize = 5;
+
+// what the host can do in a wait room, opened from the exit menu
+#[derive(Clone, Debug, Default)]
+pub struct HostMenu {
+    pub which: usize,
+    // local seat to kick or hand the host to, 0 while nobody else is in
+    pub target: usize,
+    // the error of the last action, empty if none
+    pub msg: String,
+    // labels of the items, filled in when shown
+    pub items: Vec<String>,
+}
+
+impl ClientStateManager {
+    // only in a wait room, where the host may do anything
+    pub(crate) fn is_host(&self) -> bool {
+        match self.state {
+            ClientStateInternal::WaitPlayer { ref players, ref roomid, .. }
+            | ClientStateInternal::WaitReady { ref players, ref roomid, .. }
+                => self.room.roomid == *roomid && self.room.host as usize == players[0].1,
+            _ => false,
+        }
+    }
+
+    // keep the latest RoomInfo of any msg carrying one
+    pub(crate) fn note_room_info(&mut self, msg: &GameMsg) {
+        let ri = match msg.msg {
+            Some(Msg::RoomInfo(ref ri)) | Some(Msg::ExitRoom(ref ri))
+            | Some(Msg::LoseConnection(ref ri)) => ri,
+            Some(Msg::HostNotice(HostNotice { room: Some(ref ri), .. })) => ri,
+            Some(Msg::Resume(ResumeInfo { room_info: Some(ref ri), .. })) => ri,
+            _ => return,
+        };
+        self.room = ri.clone();
+    }
+
+    // local seats of everyone but us
+    fn host_targets(&self) -> Vec<usize> {
+        match self.state {
+            ClientStateInternal::WaitPlayer { ref players, .. }
+            | ClientStateInternal::WaitReady { ref players, .. }
+                => players.iter().enumerate().skip(1).filter_map(
+                    |(i, p)| (!p.0.is_empty()).then_some(i)
+                ).collect(),
+            _ => vec![],
+        }
+    }
+
+    // the picked seat may have left since
+    fn host_target(&self, picked: usize) -> Option<usize> {
+        let targets = self.host_targets();
+        if targets.contains(&picked) {
+            Some(picked)
+        } else {
+            targets.first().copied()
+        }
+    }
+
+    pub fn handle_open_host_menu(&mut self) -> bool {
+        if !self.is_host() {
+            return false
+        }
+        self.host_menu = Some(HostMenu::default());
+        true
+    }
+
+    pub(crate) fn get_host_menu(&self) -> Option<HostMenu> {
+        let mut menu = self.host_menu.clone()?;
+        let (ClientStateInternal::WaitPlayer { ref players, .. }
+            | ClientStateInternal::WaitReady { ref players, .. }) = self.state else {
+            return None
+        };
+
+        menu.target = self.host_target(menu.target).unwrap_or_default();
+        let name = match menu.target {
+            0 => "-".into(),
+            t => players[t].0.clone(),
+        };
+        menu.items = vec![
+            "Back".into(),
+            if self.room.locked { "Unlock Room" } else { "Lock Room" }.into(),
+            format!("Seats: {}", self.room.seats),
+            format!("Kick {}", name),
+            format!("Make {} Host", name),
+        ];
+        Some(menu)
+    }
+
+    // keys while the menu is open, return None for the ones it does not take
+    pub async fn handle_host_menu_key(&mut self, e: &ClientEvent) -> Option<bool> {
+        let targets = self.host_targets();
+        let target = self.host_menu.as_ref().and_then(|m| self.host_target(m.target));
+        let menu = self.host_menu.as_mut()?;
+        match e {
+            ClientEvent::UpArrow => {
+                menu.which = (menu.which + HOST_MENU_ITEMS - 1) % HOST_MENU_ITEMS;
+            }
+            ClientEvent::DownArrow => {
+                menu.which = (menu.which + 1) % HOST_MENU_ITEMS;
+            }
+            ClientEvent::LeftArrow | ClientEvent::RightArrow => {
+                let Some(i) = target.and_then(|t| targets.iter().position(|&s| s == t)) else {
+                    return Some(false)
+                };
+                let n = targets.len();
+                let i = if let ClientEvent::LeftArrow = e { (i + n - 1) % n } else { (i + 1) % n };
+                menu.target = targets[i];
+            }
+            ClientEvent::Esc => self.host_menu = None,
+            ClientEvent::Enter => self.host_menu_enter(target).await,
+            // nothing underneath takes keys while the menu is open
+            ClientEvent::Type(_) | ClientEvent::Backspace | ClientEvent::Delete
+            | ClientEvent::SetChoose(_) | ClientEvent::ToggleChat
+            | ClientEvent::ToggleReactions | ClientEvent::React(_) => return Some(false),
+            _ => return None,
+        }
+        Some(true)
+    }
+
+    async fn host_menu_enter(&mut self, target: Option<usize>) {
+        let Some(which) = self.host_menu.as_ref().map(|m| m.which) else {
+            return
+        };
+        let room = self.room.clone();
+        let (ClientStateInternal::WaitPlayer { ref mut client, ref players, ref roomid, .. }
+            | ClientStateInternal::WaitReady { ref mut client, ref players, ref roomid, .. })
+            = self.state else {
+            return
+        };
+        let pid = players[0].1;
+        let remote = target.map(|t| players[t].1);
+
+        // what changed comes back on the stream as a HostNotice
+        let r = match (which, remote) {
+            (0, _) => {
+                self.host_menu = None;
+                return
+            }
+            (1, _) => client.lock_room(pid, roomid.clone(), !room.locked).await,
+            (2, _) => {
+                // one more seat each time, back to the fewest after the most
+                let seats = match room.seats as usize {
+                    0 => dealer::DEFAULT_SEATS,
+                    s => s,
+                };
+                let seats = if seats >= dealer::MAX_SEATS { dealer::MIN_SEATS } else { seats + 1 };
+                let settings = RoomSettings {
+                    rules: room.rules,
+                    seats: seats as u32,
+                    match_config: room.match_config,
+                    turn_timer: room.turn_timer,
+                };
+                client.change_settings(pid, roomid.clone(), settings).await
+            }
+            (3, Some(t)) => client.kick_player(pid, roomid.clone(), t).await,
+            (4, Some(t)) => client.transfer_host(pid, roomid.clone(), t).await,
+            _ => Err(Status::new(Code::NotFound, "Nobody else is in the room!")),
+        };
+
+        if let Some(menu) = self.host_menu.as_mut() {
+            menu.msg = match r {
+                Ok(()) => String::new(),
+                Err(s) => {
+                    warn!("Host action {} failed: {}", which, s);
+                    s.message().into()
+                }
+            };
+        }
+    }
+
+    pub fn handle_host_notice(&mut self, my_remote_idx: usize, hn: HostNotice) -> bool {
+        let Some(ri) = hn.room else {
+            warn!("Drop HostNotice without RoomInfo");
+            return false
+        };
+        let notice = match hn.change {
+            Some(host_notice::Change::Kicked(name)) => format!("{} was kicked out by the host.", name),
+            Some(host_notice::Change::Locked(true)) => "The host locked the room.".into(),
+            Some(host_notice::Change::Locked(false)) => "The host unlocked the room.".into(),
+            Some(host_notice::Change::NewHost(name)) => format!("{} is the host now.", name),
+            Some(host_notice::Change::Settings(s)) =>
+                format!("The host changed the room to {} seats.", s.seats),
+            None => {
+                warn!("Drop empty HostNotice");
+                return false
+            }
+        };
+        let (client, roomid, profiles) = match self.state {
+            ClientStateInternal::WaitPlayer { ref client, ref roomid, .. }
+                => (client.clone(), roomid.clone(), vec![]),
+            ClientStateInternal::WaitReady { ref client, ref roomid, ref profiles, .. }
+                => (client.clone(), roomid.clone(), profiles.clone()),
+            _ => {
+                warn!("Drop HostNotice outside a wait room");
+                return false
+            }
+        };
+
+        let players = rpc::room_info_to_players(my_remote_idx, &ri);
+        self.state = match ri.state {
+            Some(State::WaitReady(_)) => ClientStateInternal::WaitReady {
+                client,
+                msg: vec![notice, if players[0].2 {
+                    "Waiting for other players to get ready......"
+                } else {
+                    "Please press the button to get ready!"
+                }.into()],
+                players,
+                roomid,
+                rules: ri.rules.unwrap_or_default(),
+                // fetched again if the players changed
+                profiles,
+            },
+            _ => ClientStateInternal::WaitPlayer {
+                client,
+                players,
+                roomid,
+                msg: vec![notice, "Waiting for other players to join room......".into()],
+            },
+        };
+        self.exitmenu.1 = 0;
+        true
+    }
+
+    // the stream ends right after this
+    pub fn handle_kicked(&mut self, host: String) -> bool {
+        let (ClientStateInternal::WaitPlayer { ref client, ref players, ref roomid, .. }
+            | ClientStateInternal::WaitReady { ref client, ref players, ref roomid, .. })
+            = self.state else {
+            warn!("Drop Kicked msg outside a wait room");
+            return false
+        };
+
+        self.state = ClientStateInternal::AskName {
+            client: client.clone(),
+            input: Input::new(players[0].0.clone()),
+            msg: format!("Kicked out of room {} by {}.\n\
+                    Please enter your nickname:", roomid, host),
+            button: 0,
+            is_input: true,
+        };
+        self.exitmenu = (false, 0);
+        self.host_menu = None;
+        true
+    }
+}
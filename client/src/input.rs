@@ -31,6 +31,11 @@ impl Input {
         self
     }
 
+    // one '*' per char, keeping the cursor, for drawing a password
+    pub fn masked(&self) -> Self {
+        Self { value: "*".repeat(self.value.chars().count()), cursor: self.cursor }
+    }
+
     pub fn reset(&mut self) {
         self.cursor = Default::default();
         self.value = Default::default();
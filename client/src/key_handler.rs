@@ -1,16 +1,13 @@
 use crate::*;
 
 impl ClientStateManager {
-    // return (redraw, spawn_rpc_client, spawn_stream_listener)
-    pub async fn handle_enter(&mut self) -> (bool, Option<String>, Option<GameStream>) {
-        let mut spawn_rpc_client = None;
-        let mut spawn_stream_listener = None;
-        let redraw = match self.state {
+    pub async fn handle_enter(&mut self, reply: &mut ClientStateAdvanceReply) -> bool {
+        match self.state {
             ClientStateInternal::GetServer {
                 ref mut input, ref mut msg, ref mut connecting
             } if !*connecting && input.value().len() > 0 => {
                 // connect to server
-                spawn_rpc_client = Some(input.value().to_string());
+                reply.spawn_rpc_client = Some(input.value().to_string());
                 *connecting = true;
                 *msg = format!("Try connecting to {} ......", input.value());
                 true
@@ -24,43 +21,178 @@ impl ClientStateManager {
                     self.state = ClientStateInternal::NewRoom {
                         client: c.clone(),
                         input: Input::default(),
+                        password: Input::default(),
+                        on_password: false,
                         name: input.value().into(),
                         msg: format!("Hello, {}!\n\
-                                Please enter new room name:", input.value()),
+                                Please enter new room name, or nothing for an invite code:", input.value()),
                     }
-                } else {
-                    //join room
-                    info!("Player {} chooses to join room, enter JoinRoom state", input.value());
+                } else if let Some(roomid) = self.invite.take() {
+                    info!("Player {} follows the invite, enter JoinRoom state", input.value());
                     self.state = ClientStateInternal::JoinRoom {
                         name: input.value().into(),
-                        input: Input::default(),
+                        input: Input::new(roomid),
+                        password: Input::default(),
+                        on_password: false,
                         client: c.clone(),
                         msg: format!("Hello, {}!\n\
-                                Please enter room ID:", input.value()),
+                                Please press the button to join the room you are invited to:", input.value()),
                         roomid: None,
                         pid: None,
                         spawning_stream_listener: false,
+                        spectate: false,
                     };
                     self.exitmenu.1 = 0;
+                } else {
+                    //join room
+                    match c.lobby_stream().await {
+                        Ok(ls) => {
+                            info!("Player {} chooses to join room, enter Lobby state", input.value());
+                            reply.spawn_lobby_listener = Some(ls);
+                            // rooms will be filled by the first message of lobby stream
+                            self.state = ClientStateInternal::Lobby {
+                                client: c.clone(),
+                                name: input.value().into(),
+                                rooms: Vec::new(),
+                                choose: 0,
+                                msg: format!("Hello, {}!\n\
+                                        Please choose a room to join:", input.value()),
+                            };
+                        }
+                        Err(s) => {
+                            warn!("Failed to open lobby stream: {}, enter JoinRoom state", s);
+                            self.state = ClientStateInternal::JoinRoom {
+                                name: input.value().into(),
+                                input: Input::default(),
+                                password: Input::default(),
+                                on_password: false,
+                                client: c.clone(),
+                                msg: format!("Hello, {}!\n\
+                                        Please enter room ID:", input.value()),
+                                roomid: None,
+                                pid: None,
+                                spawning_stream_listener: false,
+                                spectate: false,
+                            };
+                        }
+                    }
+                    self.exitmenu.1 = 0;
                 }
                 true
             }
+            ClientStateInternal::Lobby {
+                client: ref mut c, ref name, ref rooms, choose, ref mut msg,
+            } => {
+                let roomid = match rooms.get(choose) {
+                    Some(RoomSummary { info: Some(ri), .. }) => {
+                        if !matches!(ri.state, Some(room_info::State::NotFull(_))) && ri.has_password {
+                            // no seat left, watching it takes the password
+                            info!("Player {} leaves lobby to watch a private room", name);
+                            reply.cancel_lobby();
+                            self.state = ClientStateInternal::JoinRoom {
+                                msg: format!("Hello, {}!\n\
+                                        The room is full and private, please enter its password to watch:", name),
+                                name: name.clone(),
+                                input: Input::new(ri.roomid.clone()),
+                                password: Input::default(),
+                                on_password: true,
+                                client: c.clone(),
+                                roomid: None,
+                                pid: None,
+                                spawning_stream_listener: false,
+                                spectate: true,
+                            };
+                            self.exitmenu.1 = 0;
+                            return true;
+                        }
+                        if !matches!(ri.state, Some(room_info::State::NotFull(_))) {
+                            // no seat left, watch it instead
+                            match c.spectate(ri.roomid.clone(), String::new()).await {
+                                Ok(gs) => {
+                                    info!("Player {} spectates room {}, enter Spectating state",
+                                        name, ri.roomid);
+                                    reply.cancel_lobby();
+                                    reply.spawn_stream_listener = Some(gs);
+                                    self.state = Self::spectating(c, name, ri.roomid.clone());
+                                    self.exitmenu.1 = 0;
+                                }
+                                Err(s) => {
+                                    *msg = format!("Making Spectate request to server failed:\n\
+                                            {}\n\
+                                            Please choose another room:", s.message());
+                                }
+                            }
+                            return true;
+                        }
+                        ri.roomid.clone()
+                    }
+                    // the last row: enter room ID by hand
+                    _ => String::new(),
+                };
+                let private = rooms.get(choose).and_then(|rs| rs.info.as_ref())
+                    .is_some_and(|ri| ri.has_password);
+                info!("Player {} leaves lobby, enter JoinRoom state", name);
+                reply.cancel_lobby();
+                self.state = ClientStateInternal::JoinRoom {
+                    msg: if roomid.is_empty() {
+                        format!("Hello, {}!\n\
+                                Please enter room ID:", name)
+                    } else if private {
+                        format!("Hello, {}!\n\
+                                The room is private, please enter its password:", name)
+                    } else {
+                        format!("Hello, {}!\n\
+                                Please press the button to join room:", name)
+                    },
+                    name: name.clone(),
+                    input: Input::new(roomid),
+                    password: Input::default(),
+                    on_password: private,
+                    client: c.clone(),
+                    roomid: None,
+                    pid: None,
+                    spawning_stream_listener: false,
+                    spectate: false,
+                };
+                self.exitmenu.1 = 0;
+                true
+            }
             ClientStateInternal::NewRoom {
-                client: ref mut c, ref input, ref name, ref mut msg
-            } if input.value().len() > 0 => {
-                match c.new_room(input.value().into()).await {
-                    Ok(()) => {
+                ref name, ref mut msg, ref mut on_password, ..
+            } if !*on_password => {
+                // the password comes next, nothing for a public room
+                *on_password = true;
+                *msg = format!("Hello, {}!\n\
+                        Please enter a password for a private room, or nothing for a public one:", name);
+                true
+            }
+            ClientStateInternal::NewRoom {
+                client: ref mut c, ref input, ref password, ref name, ref mut msg, ..
+            } => {
+                // an empty name gets an invite code from the server
+                let room = input.value().to_string();
+                match c.new_room(room.clone(), password.value().into()).await {
+                    Ok(roomid) => {
                         info!("Get NewRoom result from server, enter JoinRoom state");
                         self.state = ClientStateInternal::JoinRoom {
                             client: c.clone(),
-                            input: Input::new(input.value().into()),
-                            msg: format!("Hello, {}!\n\
-                                    Successfully created a room, ID is shown below.\n\
-                                    Please press the button to join room:", name),
+                            input: Input::new(roomid.clone()),
+                            password: password.clone(),
+                            on_password: false,
+                            msg: if room.is_empty() {
+                                format!("Hello, {}!\n\
+                                        Successfully created a room, invite code is {}.\n\
+                                        Please press the button to join room:", name, roomid)
+                            } else {
+                                format!("Hello, {}!\n\
+                                        Successfully created a room, ID is shown below.\n\
+                                        Please press the button to join room:", name)
+                            },
                             name: name.clone(),
                             roomid: None,
                             pid: None,
                             spawning_stream_listener: false,
+                            spectate: false,
                         };
                         self.exitmenu.1 = 0;
                     },
@@ -73,34 +205,62 @@ impl ClientStateManager {
                 true
             }
             ClientStateInternal::JoinRoom {
-                ref input, ref mut msg, client: ref mut c, ref name,
-                ref mut roomid, pid: ref mut opid, ref mut spawning_stream_listener,
+                ref input, ref password, ref mut msg, client: ref mut c, ref name, spectate: true, ..
             } if input.value().len() > 0 => {
+                let room = input.value().to_string();
+                match c.spectate(room.clone(), password.value().into()).await {
+                    Ok(gs) => {
+                        info!("Player {} spectates room {}, enter Spectating state", name, room);
+                        reply.spawn_stream_listener = Some(gs);
+                        self.state = Self::spectating(c, name, room);
+                        self.exitmenu.1 = 0;
+                    }
+                    Err(s) => {
+                        *msg = format!("Making Spectate request to server failed:\n\
+                                        {}\n\
+                                        Please retry:", s.message());
+                    }
+                }
+                true
+            }
+            ClientStateInternal::JoinRoom {
+                ref input, ref password, ref mut on_password, ref mut msg, client: ref mut c, ref name,
+                ref mut roomid, pid: ref mut opid, ref mut spawning_stream_listener, ..
+            } if input.value().len() > 0 => {
+                let room = input.value().to_string();
+                // a room ID typed by hand may need a password too
+                if opid.is_none() && !*on_password && password.value().is_empty()
+                    && c.room_status(room.clone()).await.is_ok_and(|ri| ri.has_password)
+                {
+                    *on_password = true;
+                    *msg = format!("Hello, {}!\n\
+                            The room is private, please enter its password:", name);
+                    return true;
+                }
                 let pid = if opid.is_none() {
-                    let room: String = input.value().into();
                     info!("Joining room {}", room);
-                    c.join_room(name.clone(), room.clone()).await.unwrap_or_else(
+                    c.join_room(name.clone(), room.clone(), password.value().into()).await.unwrap_or_else(
                         |e| {
                             *msg = format!("Making JoinRoom request to server failed:\n\
                                             {}\n\
                                             Please retry:", e);
-                            4
+                            dealer::MAX_SEATS
                         }
                     )
                 } else {
                     opid.unwrap()
                 };
 
-                if pid < 4 {
+                if pid < dealer::MAX_SEATS {
                     info!("Joining room succeeded");
                     // if join_room success, set opid, avoiding redundant join_room reqs
                     *opid = Some(pid);
                     // if join_room success, set roomid only once
-                    *roomid = roomid.clone().or(Some(input.value().into()));
+                    *roomid = roomid.clone().or(Some(room));
                     match c.game_stream(pid, roomid.as_ref().unwrap().clone()).await {
                         Ok(gs) => {
                             // spawn stream listener task
-                            spawn_stream_listener = Some(gs);
+                            reply.spawn_stream_listener = Some(gs);
                             *spawning_stream_listener = true;
                         }
                         Err(e) => {
@@ -112,6 +272,16 @@ impl ClientStateManager {
                 }
                 true
             }
+            ClientStateInternal::WaitPlayer {
+                ref mut client, ref roomid, ref mut msg, ..
+            } => {
+                // the new seat shows up with the next RoomInfo
+                if let Err(s) = client.add_bot(roomid.clone(), BotKind::Heuristic).await {
+                    *msg = vec![format!("Failed to add bot: {}", s.message()),
+                        "Waiting for other players to join room......".into()];
+                }
+                true
+            }
             ClientStateInternal::WaitReady {
                 ref mut client, ref mut players, ref roomid, ref mut msg, ..
             } if !players[0].2 => {
@@ -146,8 +316,20 @@ impl ClientStateManager {
                 true
             }
             ClientStateInternal::GameResult {
-                ref mut client, ref roomid, my_remote_idx, ..
+                ref mut client, ref roomid, my_remote_idx, ref mut scoreboard, ..
             } => {
+                // a match shows the running totals before the next hand
+                if scoreboard.is_none() {
+                    match client.match_status(roomid.clone()).await {
+                        Ok(mi) => {
+                            info!("Confirmed GameResult, show the scoreboard");
+                            *scoreboard = Some(rpc::match_info_to_local(my_remote_idx, mi));
+                            return true
+                        }
+                        Err(s) if s.code() == Code::NotFound => {}
+                        Err(s) => panic!("Failed to get MatchStatus in GameResult: {}", s),
+                    }
+                }
                 info!("Confirmed GameResult, enter WaitReady state");
                 let _ = client.exit_game(my_remote_idx, roomid.clone()).await
                         .unwrap_or_else(|s| panic!("Failed to ExitGame in GameResult: {}", s));
@@ -162,6 +344,8 @@ impl ClientStateManager {
                     client: client.clone(),
                     roomid: roomid.clone(),
                     msg: vec!["Please press the button to get ready!".into()],
+                    rules: ri.rules.unwrap_or_default(),
+                    profiles: vec![],
                 };
                 self.exitmenu.1 = 0;
                 true
@@ -169,8 +353,7 @@ impl ClientStateManager {
             _ => {
                 false
             }
-        };
-        (redraw, spawn_rpc_client, spawn_stream_listener)
+        }
     }
 
     pub fn handle_typing(&mut self, c: char) -> bool {
@@ -183,13 +366,15 @@ impl ClientStateManager {
                 input.handle(InputRequest::InsertChar(c));
                 true
             }
-            ClientStateInternal::NewRoom {ref mut input, ..} => {
+            ClientStateInternal::NewRoom {ref mut input, ref mut password, on_password, ..} => {
+                let input = if on_password { password } else { input };
                 input.handle(InputRequest::InsertChar(c));
                 true
             }
             ClientStateInternal::JoinRoom {
-                ref mut input, spawning_stream_listener, ..
+                ref mut input, ref mut password, on_password, spawning_stream_listener, ..
             } if !spawning_stream_listener => {
+                let input = if on_password { password } else { input };
                 input.handle(InputRequest::InsertChar(c));
                 true
             }
@@ -219,13 +404,15 @@ impl ClientStateManager {
                 }
                 true
             }
-            ClientStateInternal::NewRoom {ref mut input, ..} => {
+            ClientStateInternal::NewRoom {ref mut input, ref mut password, on_password, ..} => {
+                let input = if on_password { password } else { input };
                 input.handle(req);
                 true
             }
             ClientStateInternal::JoinRoom {
-                ref mut input, spawning_stream_listener, ..
+                ref mut input, ref mut password, on_password, spawning_stream_listener, ..
             } if !spawning_stream_listener => {
+                let input = if on_password { password } else { input };
                 input.handle(req);
                 true
             }
@@ -259,12 +446,33 @@ impl ClientStateManager {
         }
     }
 
-    pub fn handle_ud_arrow(&mut self, _is_up: bool) -> bool {
+    pub fn handle_ud_arrow(&mut self, is_up: bool) -> bool {
         match self.state {
             ClientStateInternal::AskName { ref mut is_input, ..} => {
                 *is_input = !*is_input;
                 true
             }
+            // the room ID is above the password
+            ClientStateInternal::NewRoom { ref mut on_password, ..} => {
+                *on_password = !is_up;
+                true
+            }
+            ClientStateInternal::JoinRoom {
+                ref mut on_password, spawning_stream_listener, ..
+            } if !spawning_stream_listener => {
+                *on_password = !is_up;
+                true
+            }
+            ClientStateInternal::Lobby { ref mut choose, ref rooms, ..} => {
+                let rn = rooms.len() + 1;
+                if is_up {
+                    *choose += rn - 1;
+                } else {
+                    *choose += 1;
+                }
+                *choose %= rn;
+                true
+            }
             ClientStateInternal::Gaming {
                 ref mut button, ref game, ..
             } if game.is_my_turn() => {
@@ -296,13 +504,15 @@ impl ClientStateManager {
                 input.handle(keycode);
                 true
             }
-            ClientStateInternal::NewRoom {ref mut input, ..} => {
+            ClientStateInternal::NewRoom {ref mut input, ref mut password, on_password, ..} => {
+                let input = if on_password { password } else { input };
                 input.handle(keycode);
                 true
             }
             ClientStateInternal::JoinRoom {
-                ref mut input, spawning_stream_listener, ..
+                ref mut input, ref mut password, on_password, spawning_stream_listener, ..
             } if !spawning_stream_listener => {
+                let input = if on_password { password } else { input };
                 input.handle(keycode);
                 true
             }
@@ -313,14 +523,19 @@ impl ClientStateManager {
     pub fn handle_reset_input(&mut self, new_input: Input) -> bool {
         match self.state {
             ClientStateInternal::GetServer { ref mut input, .. }
-            | ClientStateInternal::AskName { ref mut input, .. }
-            | ClientStateInternal::NewRoom { ref mut input, .. } => {
+            | ClientStateInternal::AskName { ref mut input, .. } => {
+                *input = new_input;
+                true
+            }
+            ClientStateInternal::NewRoom { ref mut input, ref mut password, on_password, .. } => {
+                let input = if on_password { password } else { input };
                 *input = new_input;
                 true
             }
             ClientStateInternal::JoinRoom {
-                ref mut input, spawning_stream_listener, ..
+                ref mut input, ref mut password, on_password, spawning_stream_listener, ..
             } if !spawning_stream_listener => {
+                let input = if on_password { password } else { input };
                 *input = new_input;
                 true
             }
@@ -341,4 +556,16 @@ impl ClientStateManager {
             _ => false,
         }
     }
+
+    // the stream listener is spawned by the caller, the game comes with the first Resume
+    fn spectating(client: &RpcClient, name: &str, roomid: String) -> ClientStateInternal {
+        ClientStateInternal::Spectating {
+            client: client.clone(),
+            name: name.into(),
+            game: None,
+            roomid,
+            msg: Some("Connecting......".into()),
+            clock: None,
+        }
+    }
 }
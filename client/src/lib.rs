@@ -4,12 +4,20 @@ mod key_handler;
 mod exit_handler;
 mod input;
 mod logging;
+mod seat;
+mod chat;
+mod reaction;
+mod host;
 
-pub use rpc::{RpcClient, GameStream};
+pub use rpc::{RpcClient, GameStream, LobbyStream, lobby_row, snapshot_to_game};
 use std::panic;
+use std::collections::BTreeMap;
 pub use tonic::{Code, Request, Response, Status};
 pub use heart7_rule::*;
 pub use input::*;
+pub use seat::*;
+pub use chat::Chat;
+pub use host::{HostMenu, HOST_MENU_ITEMS};
 #[cfg(not(target_arch = "wasm32"))]
 pub use logging::*;
 
@@ -18,11 +26,25 @@ pub type RPCResult<T> = Result<T, tonic::Status>;
 pub struct ClientState {
     pub exitmenu: (bool, u32),
     pub fsm: ClientStateMachine,
+    // None in states without a chat pane
+    pub chat: Option<Chat>,
+    // by local seat, empty in states without players on screen
+    pub bubbles: Vec<Option<Reaction>>,
+    // the reaction picker of clients without a key for each
+    pub picking_reaction: bool,
+    // only in a wait room, the exit menu then has a Host Menu button
+    pub is_host: bool,
+    pub host_menu: Option<HostMenu>,
 }
 
 pub struct ClientStateBrief {
     pub exitmenu: (bool, u32),
     pub fsm: ClientStateMachineBrief,
+    pub chat_open: bool,
+    pub picking_reaction: bool,
+    pub is_host: bool,
+    // the item chosen, Some while the host menu is open
+    pub host_menu: Option<usize>,
 }
 
 pub enum ClientStateMachineBrief {
@@ -36,9 +58,15 @@ pub enum ClientStateMachineBrief {
     },
     NewRoom {
         input: Input,
+        masked: bool,
+    },
+    Lobby {
+        choose: usize,
+        row_num: usize,
     },
     JoinRoom {
         input: Input,
+        masked: bool,
     },
     WaitPlayer,
     WaitReady,
@@ -49,6 +77,23 @@ pub enum ClientStateMachineBrief {
         my_turn: bool,
     },
     GameResult,
+    Spectating,
+}
+
+// what is left of the current turn, counted down by ClockTick
+#[derive(Clone, Copy, Debug)]
+pub struct TurnClock {
+    // local seat, the same as Game::get_next()
+    pub player: usize,
+    pub secs: u32,
+    // the last part of secs, taken from the time bank
+    pub bank_secs: u32,
+}
+
+impl TurnClock {
+    pub fn in_bank(&self) -> bool {
+        self.secs <= self.bank_secs
+    }
 }
 
 pub enum ClientStateMachine {
@@ -64,12 +109,21 @@ pub enum ClientStateMachine {
         is_input: bool,
     },
     NewRoom {
+        // the field being edited, a password is drawn masked
         input: Input,
+        masked: bool,
         msg: String,
         name: String,
     },
+    Lobby {
+        name: String,
+        rooms: Vec<RoomSummary>,
+        choose: usize, // rooms.len() for joining by room ID
+        msg: String,
+    },
     JoinRoom {
         input: Input,
+        masked: bool,
         msg: String,
         name: String,
     },
@@ -82,6 +136,9 @@ pub enum ClientStateMachine {
         players: Vec<(String, usize, bool)>,
         msg: Vec<String>,
         roomid: String,
+        rules: RuleSet,
+        // by local seat, empty until fetched
+        profiles: Vec<Profile>,
     },
     Gaming {
         choose: usize, // 0 for none
@@ -90,6 +147,8 @@ pub enum ClientStateMachine {
         roomid: String,
         button: u32,
         msg: Option<String>,
+        // None when the room has no clock
+        clock: Option<TurnClock>,
     },
     GameResult {
         ds: Vec<Vec<(Card, usize)>>,
@@ -98,6 +157,15 @@ pub enum ClientStateMachine {
         roomid: String,
         winner: usize,
         winner_state: GameWinnerState,
+        // Some after confirming a hand of a match, local player 0 comes first
+        scoreboard: Option<MatchInfo>,
+    },
+    Spectating {
+        // None between games
+        game: Option<Game>,
+        roomid: String,
+        msg: Option<String>,
+        clock: Option<TurnClock>,
     },
 }
 
@@ -118,12 +186,25 @@ enum ClientStateInternal {
     NewRoom {
         client: RpcClient,
         input: Input,
+        // empty for a public room
+        password: Input,
+        // typing goes to the password instead of the room ID
+        on_password: bool,
         msg: String,
         name: String,
     },
+    Lobby {
+        client: RpcClient,
+        name: String,
+        rooms: Vec<RoomSummary>,
+        choose: usize, // rooms.len() for joining by room ID
+        msg: String,
+    },
     JoinRoom {
         client: RpcClient,
         input: Input,
+        password: Input,
+        on_password: bool,
         msg: String,
         name: String,
         // be Some(pid_remote) only after join_room succes
@@ -132,6 +213,8 @@ enum ClientStateInternal {
         // be Some(roomid) only after join_room succes
         roomid: Option<String>,
         spawning_stream_listener: bool,
+        // watch the room instead, for a full private one picked in the lobby
+        spectate: bool,
     },
     WaitPlayer {
         client: RpcClient,
@@ -144,6 +227,9 @@ enum ClientStateInternal {
         players: Vec<(String, usize, bool)>,
         msg: Vec<String>,
         roomid: String,
+        rules: RuleSet,
+        // by local seat, empty until fetched
+        profiles: Vec<Profile>,
     },
     Gaming {
         client: RpcClient,
@@ -153,6 +239,8 @@ enum ClientStateInternal {
         roomid: String,
         button: u32,
         msg: Option<String>,
+        // None when the room has no clock
+        clock: Option<TurnClock>,
     },
     GameResult {
         ds: Vec<Vec<(Card, usize)>>,
@@ -162,6 +250,18 @@ enum ClientStateInternal {
         roomid: String,
         winner: usize,
         winner_state: GameWinnerState,
+        // Some after confirming a hand of a match, local player 0 comes first
+        scoreboard: Option<MatchInfo>,
+    },
+    Spectating {
+        client: RpcClient,
+        // kept for going back to AskName
+        name: String,
+        // None between games, seat 0 is at the bottom
+        game: Option<Game>,
+        roomid: String,
+        msg: Option<String>,
+        clock: Option<TurnClock>,
     },
 }
 
@@ -179,14 +279,21 @@ impl Into<ClientStateMachine> for ClientStateInternal {
                 input, msg, button, is_input
             },
             ClientStateInternal::NewRoom {
-                input, msg, name, ..
+                input, password, on_password, msg, name, ..
             } => ClientStateMachine::NewRoom {
-                input, msg, name
+                input: if on_password { password } else { input },
+                masked: on_password, msg, name
+            },
+            ClientStateInternal::Lobby {
+                name, rooms, choose, msg, ..
+            } => ClientStateMachine::Lobby {
+                name, rooms, choose, msg,
             },
             ClientStateInternal::JoinRoom {
-                input, msg, name, ..
+                input, password, on_password, msg, name, ..
             } => ClientStateMachine::JoinRoom {
-                input, msg, name,
+                input: if on_password { password } else { input },
+                masked: on_password, msg, name,
             },
             ClientStateInternal::WaitPlayer {
                 players, msg, roomid, ..
@@ -194,19 +301,24 @@ impl Into<ClientStateMachine> for ClientStateInternal {
                 players, msg, roomid
             },
             ClientStateInternal::WaitReady {
-                players, msg, roomid, ..
+                players, msg, roomid, rules, profiles, ..
             } => ClientStateMachine::WaitReady {
-                players, msg, roomid
+                players, msg, roomid, rules, profiles
             },
             ClientStateInternal::Gaming {
-                choose, game, my_remote_idx, roomid, button, msg, ..
+                choose, game, my_remote_idx, roomid, button, msg, clock, ..
             } => ClientStateMachine::Gaming{
-                choose, game, my_remote_idx, roomid, button, msg
+                choose, game, my_remote_idx, roomid, button, msg, clock
             },
             ClientStateInternal::GameResult {
-                ds, my_remote_idx, players, roomid, winner, winner_state, ..
+                ds, my_remote_idx, players, roomid, winner, winner_state, scoreboard, ..
             } => ClientStateMachine::GameResult {
-                ds, my_remote_idx, players, roomid, winner, winner_state,
+                ds, my_remote_idx, players, roomid, winner, winner_state, scoreboard,
+            },
+            ClientStateInternal::Spectating {
+                game, roomid, msg, clock, ..
+            } => ClientStateMachine::Spectating {
+                game, roomid, msg, clock,
             },
         }
     }
@@ -229,8 +341,16 @@ pub enum ClientEvent {
     ServerConnectResult(Result<RpcClient, String>),
     StreamListenerSpawned,
     StreamMsg(GameMsg),
+    StreamLost(String),
+    LobbyMsg(RoomList),
+    ResumeSession,
     ResetInput(Input),
     SetChoose(usize),
+    // sent every second by the frontend
+    ClockTick,
+    ToggleChat,
+    React(Reaction),
+    ToggleReactions,
 }
 
 #[derive(Default)]
@@ -240,6 +360,10 @@ pub struct ClientStateAdvanceReply {
     pub full_exit: bool,
     pub spawn_rpc_client: Option<String>,
     pub spawn_stream_listener: Option<GameStream>,
+    pub spawn_lobby_listener: Option<LobbyStream>,
+    pub cancel_lobby_listener: bool,
+    // send a ResumeSession event after this many millis
+    pub resume_after: Option<u32>,
 }
 
 impl ClientStateAdvanceReply {
@@ -251,6 +375,10 @@ impl ClientStateAdvanceReply {
         self.cancel_stream_listener = true;
     }
 
+    pub fn cancel_lobby(&mut self) {
+        self.cancel_lobby_listener = true;
+    }
+
     pub fn full_exit(&mut self) {
         self.full_exit = true;
     }
@@ -265,20 +393,46 @@ impl ClientStateAdvanceReply {
 //  6. get a whoready: someone get ready
 //  7. get a start: server start game, and client should rpc GameStatus to get cards
 //  8. continue listen stream
-//  9. rpc ExitGame after user confirm the gameresult
+//  9. rpc ExitGame after user confirm the gameresult,
+//     or the scoreboard following it in a match
 // 10. return to WaitReady
 // 11. handle when someone exits
 // 12. handle Esc of all states
+// 13. resume session with backoff when GameStream is lost
+// 14. apply stream msgs in seq order, fetch the missing ones on a gap
+// 15. count down the turn clock from TurnDeadline on every ClockTick
+// 16. keys go to the chat pane while it is open, in any state inside a room
+// 17. the host of a wait room gets a host menu from the exit menu
+// 18. a password goes in its own masked field after the room ID, up and down switch them,
+//     an invite goes to JoinRoom right after AskName
 
 pub struct ClientStateManager {
     state: ClientStateInternal,
     exitmenu: (bool, u32), // (inside exitmenu or not, which button is choosed)
+    resuming: Option<u32>, // Some(attempt) while resuming a lost GameStream
+    last_seq: Option<u64>, // seq of the last applied stream msg, None for a new stream
+    pending: BTreeMap<u64, GameMsg>, // stream msgs arrived ahead of their turn
+    chat: Chat, // of the current room, cleared when joining another
+    bubbles: BTreeMap<usize, (Reaction, u32)>, // by remote seat, with clock ticks left
+    picking_reaction: bool,
+    room: RoomInfo, // the latest one seen on the stream, for the host menu
+    host_menu: Option<HostMenu>,
+    invite: Option<String>, // roomid of an invite link, offered once the name is in
 }
 
 impl ClientStateManager {
     pub fn new(default_addr: String) -> Self {
         Self {
             exitmenu: (false, 0),
+            resuming: None,
+            last_seq: None,
+            pending: BTreeMap::new(),
+            chat: Chat::default(),
+            bubbles: BTreeMap::new(),
+            picking_reaction: false,
+            room: RoomInfo::default(),
+            host_menu: None,
+            invite: None,
             state: ClientStateInternal::GetServer {
                 input: Input::new(default_addr.clone()),
                 msg: "Welcome to Seven-of-Heart !!!\n\
@@ -288,10 +442,27 @@ impl ClientStateManager {
         }
     }
 
+    // go straight to the room after asking the name
+    pub fn with_invite(mut self, roomid: String) -> Self {
+        self.invite = Some(roomid);
+        self
+    }
+
     // returns: (redraw, need_cancel)
     pub async fn advance(&mut self, e: ClientEvent, blocked: bool) -> ClientStateAdvanceReply {
         let mut reply = ClientStateAdvanceReply::default();
-        let redraw = if self.exitmenu.0 {
+        // no user input until we are back in the room
+        let blocked = blocked || self.resuming.is_some();
+        let menu_key = if self.host_menu.is_some() && !self.exitmenu.0 && !blocked {
+            self.handle_host_menu_key(&e).await
+        } else if self.chat_is_open() && !self.exitmenu.0 && !blocked {
+            self.handle_chat_key(&e).await
+        } else {
+            None
+        };
+        let redraw = if let Some(redraw) = menu_key {
+            redraw
+        } else if self.exitmenu.0 {
             match e {
                 ClientEvent::Esc if !blocked
                     => self.handle_esc(),
@@ -300,9 +471,11 @@ impl ClientStateManager {
                         self.handle_exitmenu_event(e.into()).await;
                     if cancel_stream {
                         reply.cancel_stream();
+                        reply.cancel_lobby();
                     }
                     if full_exit {
                         reply.full_exit();
+                        reply.cancel_lobby();
                     }
                     true
                 }
@@ -312,23 +485,35 @@ impl ClientStateManager {
                 ClientEvent::ServerConnectResult(r)
                     => self.handle_server_connect_result(r),
                 ClientEvent::StreamMsg(msg)
-                    => self.handle_stream_msg(msg).await,
+                    => self.handle_stream_msg(msg, &mut reply).await,
                 ClientEvent::StreamListenerSpawned
                     => self.handle_stream_listener_spawned().await,
+                ClientEvent::LobbyMsg(rl)
+                    => self.handle_lobby_msg(rl),
+                ClientEvent::StreamLost(e) => {
+                    let (redraw, resume_after) = self.handle_stream_lost(e);
+                    reply.resume_after = resume_after;
+                    redraw
+                }
+                ClientEvent::ResumeSession => {
+                    let (redraw, spawn_stream_listener, resume_after) =
+                        self.handle_resume_session().await;
+                    reply.spawn_stream_listener = spawn_stream_listener;
+                    reply.resume_after = resume_after;
+                    redraw
+                }
                 ClientEvent::ResetInput(new_input)
                     => self.handle_reset_input(new_input),
+                ClientEvent::ClockTick
+                    => self.handle_clock_tick(),
                 _ => false,
             }
         } else {
             match e {
                 ClientEvent::Esc if !blocked
                     => self.handle_esc(),
-                ClientEvent::Enter if !blocked => {
-                    let (redraw, spawn_rpc_client, spawn_stream_listener) = self.handle_enter().await;
-                    reply.spawn_rpc_client = spawn_rpc_client;
-                    reply.spawn_stream_listener = spawn_stream_listener;
-                    redraw
-                },
+                ClientEvent::Enter if !blocked
+                    => self.handle_enter(&mut reply).await,
                 ClientEvent::LeftArrow if !blocked
                     => self.handle_lr_arrow(true),
                 ClientEvent::RightArrow if !blocked
@@ -337,6 +522,12 @@ impl ClientStateManager {
                     => self.handle_ud_arrow(true),
                 ClientEvent::DownArrow if !blocked
                     => self.handle_ud_arrow(false),
+                // number keys send the reactions in a game
+                ClientEvent::Type(c @ '1'..='9') if !blocked && self.can_react()
+                    => match Reaction::ALL.get(c as usize - '1' as usize) {
+                        Some(&r) => self.handle_send_reaction(r).await,
+                        None => false,
+                    },
                 ClientEvent::Type(c) if !blocked
                     => self.handle_typing(c),
                 ClientEvent::CtrlC
@@ -350,16 +541,43 @@ impl ClientStateManager {
                 ClientEvent::ServerConnectResult(r)
                     => self.handle_server_connect_result(r),
                 ClientEvent::StreamMsg(msg)
-                    => self.handle_stream_msg(msg).await,
+                    => self.handle_stream_msg(msg, &mut reply).await,
                 ClientEvent::StreamListenerSpawned
                     => self.handle_stream_listener_spawned().await,
+                ClientEvent::LobbyMsg(rl)
+                    => self.handle_lobby_msg(rl),
+                ClientEvent::StreamLost(e) => {
+                    let (redraw, resume_after) = self.handle_stream_lost(e);
+                    reply.resume_after = resume_after;
+                    redraw
+                }
+                ClientEvent::ResumeSession => {
+                    let (redraw, spawn_stream_listener, resume_after) =
+                        self.handle_resume_session().await;
+                    reply.spawn_stream_listener = spawn_stream_listener;
+                    reply.resume_after = resume_after;
+                    redraw
+                }
                 ClientEvent::ResetInput(new_input)
                     => self.handle_reset_input(new_input),
                 ClientEvent::SetChoose(choose)
                     => self.handle_set_choose(choose),
+                ClientEvent::ToggleChat if !blocked
+                    => self.handle_toggle_chat(),
+                ClientEvent::React(r) if !blocked && self.can_react()
+                    => self.handle_send_reaction(r).await,
+                ClientEvent::ToggleReactions if !blocked
+                    => self.handle_toggle_reactions(),
+                ClientEvent::ClockTick
+                    => self.handle_clock_tick(),
                 _ => false,
             }
         };
+        let redraw = self.refresh_profiles().await || redraw;
+        // gone with the wait room, or with the host
+        if !self.is_host() {
+            self.host_menu = None;
+        }
         if redraw {
             reply.redraw();
         }
@@ -370,6 +588,11 @@ impl ClientStateManager {
         ClientState {
             exitmenu: self.exitmenu.clone(),
             fsm: self.state.clone().into(),
+            chat: self.has_chat().then(|| self.chat.clone()),
+            bubbles: self.local_bubbles(),
+            picking_reaction: self.picking_reaction && self.can_react(),
+            is_host: self.is_host(),
+            host_menu: self.get_host_menu(),
         }
     }
 
@@ -379,10 +602,18 @@ impl ClientStateManager {
                 => ClientStateMachineBrief::GetServer{input: input.clone()},
             ClientStateInternal::AskName{button, is_input, ref input, ..}
                 => ClientStateMachineBrief::AskName{button, is_input, input: input.clone()},
-            ClientStateInternal::NewRoom{ref input, ..}
-                => ClientStateMachineBrief::NewRoom{input: input.clone()},
-            ClientStateInternal::JoinRoom{ref input, ..}
-                => ClientStateMachineBrief::JoinRoom{input: input.clone()},
+            ClientStateInternal::NewRoom{ref input, ref password, on_password, ..}
+                => ClientStateMachineBrief::NewRoom{
+                    input: if on_password { password.clone() } else { input.clone() },
+                    masked: on_password,
+                },
+            ClientStateInternal::Lobby{choose, ref rooms, ..}
+                => ClientStateMachineBrief::Lobby{choose, row_num: rooms.len() + 1},
+            ClientStateInternal::JoinRoom{ref input, ref password, on_password, ..}
+                => ClientStateMachineBrief::JoinRoom{
+                    input: if on_password { password.clone() } else { input.clone() },
+                    masked: on_password,
+                },
             ClientStateInternal::WaitPlayer{..} => ClientStateMachineBrief::WaitPlayer,
             ClientStateInternal::WaitReady{..} => ClientStateMachineBrief::WaitReady,
             ClientStateInternal::Gaming { choose, ref game, button, .. }
@@ -393,10 +624,15 @@ impl ClientStateManager {
                 my_turn: game.is_my_turn(),
             },
             ClientStateInternal::GameResult{..} => ClientStateMachineBrief::GameResult,
+            ClientStateInternal::Spectating{..} => ClientStateMachineBrief::Spectating,
         };
         ClientStateBrief {
             exitmenu: self.exitmenu.clone(),
             fsm,
+            chat_open: self.chat_is_open(),
+            picking_reaction: self.picking_reaction && self.can_react(),
+            is_host: self.is_host(),
+            host_menu: self.host_menu.as_ref().map(|m| m.which),
         }
     }
 }
@@ -404,8 +640,12 @@ impl ClientStateManager {
 pub fn get_button_num(cs: &ClientState) -> u32 {
     match cs.fsm {
         ClientStateMachine::GetServer {..} | ClientStateMachine::AskName {..}
-        | ClientStateMachine::JoinRoom {..} | ClientStateMachine::NewRoom { .. } => 2,
-        ClientStateMachine::WaitPlayer {..} | ClientStateMachine::WaitReady {..} => 3,
+        | ClientStateMachine::JoinRoom {..} | ClientStateMachine::NewRoom { .. }
+        | ClientStateMachine::Lobby {..} => 2,
+        ClientStateMachine::WaitPlayer {..} | ClientStateMachine::WaitReady {..}
+            if cs.is_host => 4,
+        ClientStateMachine::WaitPlayer {..} | ClientStateMachine::WaitReady {..}
+        | ClientStateMachine::Spectating {..} => 3,
         ClientStateMachine::Gaming {..} | ClientStateMachine::GameResult {..} => 4,
     }
 }
@@ -413,8 +653,12 @@ pub fn get_button_num(cs: &ClientState) -> u32 {
 pub fn get_button_num_from_brief(cs: &ClientStateBrief) -> u32 {
     match cs.fsm {
         ClientStateMachineBrief::GetServer {..} | ClientStateMachineBrief::AskName {..}
-        | ClientStateMachineBrief::JoinRoom {..} | ClientStateMachineBrief::NewRoom { .. } => 2,
-        ClientStateMachineBrief::WaitPlayer {..} | ClientStateMachineBrief::WaitReady {..} => 3,
+        | ClientStateMachineBrief::JoinRoom {..} | ClientStateMachineBrief::NewRoom { .. }
+        | ClientStateMachineBrief::Lobby {..} => 2,
+        ClientStateMachineBrief::WaitPlayer {..} | ClientStateMachineBrief::WaitReady {..}
+            if cs.is_host => 4,
+        ClientStateMachineBrief::WaitPlayer {..} | ClientStateMachineBrief::WaitReady {..}
+        | ClientStateMachineBrief::Spectating {..} => 3,
         ClientStateMachineBrief::Gaming {..} | ClientStateMachineBrief::GameResult {..} => 4,
     }
 }
@@ -1,5 +1,7 @@
 use crate::*;
-use rpc::{self, RpcClient};
+use rpc::{self, RpcClient, GameStream};
+
+const RESUME_MAX_ATTEMPTS: u32 = 8;
 
 impl ClientStateManager {
     pub fn handle_server_connect_result(&mut self, r: Result<RpcClient, String>) -> bool {
@@ -15,9 +17,15 @@ impl ClientStateManager {
                         self.state = ClientStateInternal::AskName {
                             client: c,
                             input: Input::default(),
-                            msg: "Game server connected.\n\
-                                    Please enter your nickname:".into(),
-                            button: 0,
+                            msg: match self.invite {
+                                Some(ref roomid) => format!("Game server connected, \
+                                        you are invited to room {}.\n\
+                                        Please enter your nickname:", roomid),
+                                None => "Game server connected.\n\
+                                        Please enter your nickname:".into(),
+                            },
+                            // join room
+                            button: if self.invite.is_some() { 1 } else { 0 },
                             is_input: true,
                         };
                         self.exitmenu.1 = 0;
@@ -35,8 +43,58 @@ impl ClientStateManager {
         }
     }
 
+    pub fn handle_lobby_msg(&mut self, rl: RoomList) -> bool {
+        match self.state {
+            ClientStateInternal::Lobby {ref mut rooms, ref mut choose, ..} => {
+                *rooms = rl.rooms;
+                // keep the cursor on a valid row, the last one is for entering room ID
+                *choose = (*choose).min(rooms.len());
+                true
+            }
+            _ => {
+                debug!("Not in Lobby, drop lobby message");
+                false
+            }
+        }
+    }
+
+    pub fn handle_clock_tick(&mut self) -> bool {
+        let bubbles = self.tick_bubbles();
+        let clock = match self.state {
+            ClientStateInternal::Gaming { clock: Some(ref mut tc), .. }
+            | ClientStateInternal::Spectating { clock: Some(ref mut tc), .. } if tc.secs > 0 => {
+                tc.secs -= 1;
+                true
+            }
+            _ => false,
+        };
+        bubbles || clock
+    }
+
+    // whole seconds, rounded up so 0 only shows once time is up
+    fn turn_clock(player: usize, td: &TurnDeadline) -> TurnClock {
+        TurnClock {
+            player,
+            secs: td.millis.div_ceil(1000),
+            bank_secs: td.bank_millis.div_ceil(1000),
+        }
+    }
+
     pub async fn handle_stream_listener_spawned(&mut self) -> bool {
+        // a new stream starts its own sequence
+        self.last_seq = None;
+        self.pending.clear();
+        if self.resuming.is_some() {
+            // the resumed stream, wait for its Resume msg
+            return false
+        }
+        // a room of its own
+        self.chat = Chat::default();
+        self.bubbles.clear();
+        self.room = RoomInfo::default();
         match self.state {
+            // nothing to do until the Resume msg arrives
+            ClientStateInternal::Spectating {..} => false,
             ClientStateInternal::JoinRoom {
                 ref input, client: ref mut c, spawning_stream_listener,
                 ref pid, ref roomid, ..
@@ -44,7 +102,7 @@ impl ClientStateManager {
                 let roomid = roomid.clone().unwrap();
                 c.stream_ready(pid.unwrap(), roomid).await.expect("Requesting stream_ready");
                 self.state = ClientStateInternal::WaitPlayer {
-                    players: vec![("".into(), 4, false); 4],
+                    players: vec![("".into(), 0, false); dealer::DEFAULT_SEATS],
                     client: c.clone(),
                     roomid: input.value().into(),
                     msg: vec!["Waiting for other players to join room......".into()],
@@ -56,8 +114,409 @@ impl ClientStateManager {
         }
     }
 
-    pub async fn handle_stream_msg(&mut self, msg: GameMsg) -> bool {
+    // return (redraw, resume_after)
+    pub fn handle_stream_lost(&mut self, e: String) -> (bool, Option<u32>) {
+        warn!("GameStream lost: {}", e);
+        let lost = "Connection lost, reconnecting......";
+        match self.state {
+            ClientStateInternal::WaitPlayer { ref mut msg, .. }
+            | ClientStateInternal::WaitReady { ref mut msg, .. } => {
+                *msg = vec![lost.into()];
+            }
+            ClientStateInternal::Gaming { ref mut msg, .. } => {
+                *msg = Some(lost.into());
+            }
+            ClientStateInternal::GameResult { .. } => {}
+            // spectators have no session to resume
+            ClientStateInternal::Spectating { ref mut msg, .. } => {
+                *msg = Some("Lost connection to room.".into());
+                return (true, None)
+            }
+            _ => return (false, None),
+        }
+        self.resuming = Some(0);
+        (true, Some(Self::resume_backoff(0)))
+    }
+
+    // profiles of the wait room, fetched again whenever the players change
+    pub async fn refresh_profiles(&mut self) -> bool {
+        let ClientStateInternal::WaitReady {
+            ref mut client, ref players, ref mut profiles, ..
+        } = self.state else {
+            return false
+        };
+        if profiles.len() == players.len()
+            && profiles.iter().zip(players.iter()).all(|(pf, p)| pf.name == p.0)
+        {
+            return false
+        }
+
+        let mut fetched = Vec::with_capacity(players.len());
+        for p in players.iter() {
+            fetched.push(client.get_profile(p.0.clone()).await.unwrap_or_else(|s| {
+                warn!("Failed to get profile of {}: {}", p.0, s);
+                Profile::new(p.0.clone())
+            }));
+        }
+        *profiles = fetched;
+        true
+    }
+
+    fn resume_backoff(attempt: u32) -> u32 {
+        500 * (1 << attempt.min(4))
+    }
+
+    // return (redraw, spawn_stream_listener, resume_after)
+    pub async fn handle_resume_session(&mut self) -> (bool, Option<GameStream>, Option<u32>) {
+        let Some(attempt) = self.resuming else {
+            return (false, None, None)
+        };
+        let (c, roomid, name) = match self.state {
+            ClientStateInternal::WaitPlayer { ref mut client, ref roomid, ref players, .. }
+            | ClientStateInternal::WaitReady { ref mut client, ref roomid, ref players, .. }
+                => (client, roomid.clone(), players[0].0.clone()),
+            ClientStateInternal::GameResult { ref mut client, ref roomid, ref players, .. }
+                => (client, roomid.clone(), players[0].0.clone()),
+            ClientStateInternal::Gaming { ref mut client, ref roomid, ref game, .. }
+                => (client, roomid.clone(), game.get_my_name()),
+            // no session, but spectating again brings a new Resume msg
+            ClientStateInternal::Spectating { ref mut client, ref roomid, ref mut msg, .. } => {
+                info!("Spectating room {} again", roomid);
+                let password = client.password.clone().unwrap_or_default();
+                return match client.spectate(roomid.clone(), password).await {
+                    Ok(gs) => (false, Some(gs), None),
+                    Err(s) => {
+                        warn!("Failed to spectate again: {}", s);
+                        self.resuming = None;
+                        *msg = Some("Lost connection to room.".into());
+                        (true, None, None)
+                    }
+                }
+            }
+            _ => {
+                self.resuming = None;
+                return (false, None, None)
+            }
+        };
+
+        info!("Resuming session of room {}, attempt {}", roomid, attempt);
+        match c.resume_session(roomid).await {
+            // resuming is done after the Resume msg arrives
+            Ok(gs) => (false, Some(gs), None),
+            Err(s) if attempt + 1 < RESUME_MAX_ATTEMPTS
+                && s.code() != Code::NotFound && s.code() != Code::FailedPrecondition => {
+                warn!("Failed to resume session: {}", s);
+                self.resuming = Some(attempt + 1);
+                (false, None, Some(Self::resume_backoff(attempt + 1)))
+            }
+            Err(s) => {
+                error!("Give up resuming session: {}", s);
+                c.session = None;
+                self.resuming = None;
+                self.state = ClientStateInternal::AskName {
+                    client: c.clone(),
+                    input: Input::new(name),
+                    msg: format!("Lost connection to room:\n\
+                            {}\n\
+                            Please enter your nickname:", s.message()),
+                    button: 0,
+                    is_input: true,
+                };
+                self.exitmenu = (false, 0);
+                (true, None, None)
+            }
+        }
+    }
+
+    // rebuild the whole state from the first msg of a resumed stream
+    fn handle_resume_info(&mut self, my_remote_idx: usize, ri: ResumeInfo) -> bool {
+        let (client, roomid) = match self.state {
+            ClientStateInternal::WaitPlayer { ref client, ref roomid, .. }
+            | ClientStateInternal::WaitReady { ref client, ref roomid, .. }
+            | ClientStateInternal::Gaming { ref client, ref roomid, .. }
+            | ClientStateInternal::GameResult { ref client, ref roomid, .. }
+                => (client.clone(), roomid.clone()),
+            _ => {
+                warn!("Drop Resume msg outside a room");
+                return false
+            }
+        };
+        let room_info = ri.room_info.expect("Empty RoomInfo in Resume msg!");
+        let players = rpc::room_info_to_players(my_remote_idx, &room_info);
+
+        info!("Session resumed, room state: {:?}", room_info.state);
+        self.state = match room_info.state {
+            Some(State::NotFull(_)) => ClientStateInternal::WaitPlayer {
+                client,
+                players,
+                roomid,
+                msg: vec!["Reconnected.".into(),
+                    "Waiting for other players to join room......".into()],
+            },
+            Some(State::WaitReady(_)) => ClientStateInternal::WaitReady {
+                client,
+                msg: vec![if players[0].2 {
+                    "Waiting for other players to get ready......"
+                } else {
+                    "Please press the button to get ready!"
+                }.into()],
+                players,
+                roomid,
+                rules: room_info.rules.unwrap_or_default(),
+                profiles: vec![],
+            },
+            Some(State::Gaming(_)) => {
+                let seats = players.len();
+                let mut game = Game::new();
+                game.set_rules(room_info.rules.unwrap_or_default()).unwrap();
+                game.set_seats(seats).unwrap();
+                players.iter().for_each(|p| {
+                    game.add_player(p.0.clone());
+                });
+                game.set_start(Self::get_local_idx(my_remote_idx, ri.start as usize, seats));
+                game.init_my_cards(ri.cards.iter().map(|c| c.into()).collect(), my_remote_idx);
+                for mut pi in ri.plays {
+                    pi.player = Self::get_local_idx(my_remote_idx, pi.player as usize, seats) as u32;
+                    game.play_card_no_check(pi.into()).unwrap();
+                }
+                ClientStateInternal::Gaming {
+                    client,
+                    roomid,
+                    game,
+                    my_remote_idx,
+                    choose: 0,
+                    button: 0,
+                    msg: Some("Reconnected.".into()),
+                    clock: None,
+                }
+            }
+            Some(State::EndGame(_)) => {
+                let GameEnding { desk, hold, winner, winner_state } =
+                    ri.endgame.expect("Empty GameEnding in Resume msg!");
+                let ds = desk.expect("Empty DeskResult in GameResult from server!");
+                let seats = players.len();
+                ClientStateInternal::GameResult {
+                    ds: Self::parse_desk_result(&ds, my_remote_idx, seats),
+                    players: Self::parse_hold_result(
+                        &hold, players.into_iter().map(|p| p.0).collect(), my_remote_idx
+                    ),
+                    winner: Self::get_local_idx(my_remote_idx, winner as usize, seats),
+                    winner_state: winner_state.try_into().unwrap(),
+                    my_remote_idx,
+                    client,
+                    roomid,
+                    scoreboard: None,
+                }
+            }
+            None => panic!("Empty room state in Resume msg!"),
+        };
+        self.resuming = None;
+        self.exitmenu.1 = 0;
+        true
+    }
+
+    // every player holds dummy cards, and the view is from seat 0
+    fn spectator_game(
+        names: Vec<String>, rules: RuleSet, start: usize, plays: Vec<PlayInfo>
+    ) -> Game {
+        let seats = names.len();
+        let mut game = Game::new();
+        game.set_rules(rules).unwrap();
+        game.set_seats(seats).unwrap();
+        names.into_iter().for_each(|n| {
+            game.add_player(n);
+        });
+        game.set_start(start);
+        game.init_my_cards(vec![DUMMY_CARD.clone(); dealer::hand_sizes(seats)[0]], 0);
+        for pi in plays {
+            game.play_card_no_check(pi.into()).unwrap();
+        }
+        game
+    }
+
+    fn winner_msg(name: &str, winner_state: GameWinnerState) -> String {
+        match winner_state {
+            GameWinnerState::Normal => format!("Player {} wins.", name),
+            GameWinnerState::Clear => format!("Player {} clears.", name),
+            GameWinnerState::Seven => format!("Player {} clears with SEVEN.", name),
+        }
+    }
+
+    async fn handle_spectate_msg(&mut self, msg: GameMsg) -> bool {
+        let ClientStateInternal::Spectating {
+            ref mut client, ref roomid, ref mut game, msg: ref mut smsg, ref mut clock, ..
+        } = self.state else {
+            unreachable!()
+        };
+        let waiting = "Waiting for the next game......";
+        match msg.msg {
+            Some(Msg::Resume(ri)) => {
+                self.resuming = None;
+                *clock = None;
+                let room_info = ri.room_info.expect("Empty RoomInfo in Resume msg!");
+                let names: Vec<String> = room_info.players.into_iter().map(|p| p.name).collect();
+                info!("Start spectating room {}, room state: {:?}", roomid, room_info.state);
+                match room_info.state {
+                    Some(State::Gaming(_)) => {
+                        *game = Some(Self::spectator_game(
+                            names, room_info.rules.unwrap_or_default(), ri.start as usize, ri.plays
+                        ));
+                        *smsg = None;
+                    }
+                    Some(State::EndGame(_)) => {
+                        let ge = ri.endgame.expect("Empty GameEnding in Resume msg!");
+                        *game = None;
+                        *smsg = Some(Self::winner_msg(
+                            &names[ge.winner as usize], ge.winner_state.try_into().unwrap()
+                        ));
+                    }
+                    _ => {
+                        *game = None;
+                        *smsg = Some(waiting.into());
+                    }
+                }
+            }
+            Some(Msg::Start(next)) => {
+                let ri = client.room_status(roomid.clone()).await.unwrap_or_else(
+                    |s| panic!("Failed to get RoomStatus on start: {}", s)
+                );
+                let names = ri.players.into_iter().map(|p| p.name).collect();
+                *game = Some(Self::spectator_game(
+                    names, ri.rules.unwrap_or_default(), next as usize, vec![]
+                ));
+                *smsg = None;
+            }
+            Some(Msg::Play(pi)) => {
+                if let Some(ref mut g) = game {
+                    g.play_card_no_check(pi.into()).unwrap();
+                }
+            }
+            Some(Msg::Endgame(GameEnding { winner, winner_state, .. })) => {
+                *clock = None;
+                // keep the final desk on screen
+                if let Some(ref g) = game {
+                    *smsg = Some(Self::winner_msg(
+                        &g.get_player_name(winner as usize), winner_state.try_into().unwrap()
+                    ));
+                }
+            }
+            Some(Msg::BotTakeover(who)) => {
+                if let Some(ref g) = game {
+                    *smsg = Some(format!("{} lost connection, a bot plays for now.",
+                        g.get_player_name(who as usize)));
+                }
+            }
+            Some(Msg::ExitGame(_)) | Some(Msg::ExitRoom(_)) | Some(Msg::LoseConnection(_)) => {
+                *game = None;
+                *clock = None;
+                *smsg = Some(waiting.into());
+            }
+            // spectators see from seat 0, so no need to turn it around
+            Some(Msg::TurnDeadline(td)) => {
+                *clock = Some(Self::turn_clock(td.player as usize, &td));
+            }
+            Some(Msg::RoomInfo(_)) | Some(Msg::WhoReady(_)) | Some(Msg::Chat(_))
+            | Some(Msg::Reaction(_)) | Some(Msg::HostNotice(_))
+            | Some(Msg::Kicked(_)) | Some(Msg::DealFailed(_)) => return false,
+            None => {
+                warn!("Drop empty GameMsg");
+                return false
+            }
+        }
+        true
+    }
+
+    // broadcasts may be sent out of order, apply them by seq
+    pub async fn handle_stream_msg(&mut self, msg: GameMsg, reply: &mut ClientStateAdvanceReply) -> bool {
         debug!("Got GameMsg: {:?}", msg);
+        match self.last_seq {
+            // the old stream is being replaced, what it still has is across the gap
+            _ if self.resuming.is_some() && !matches!(msg.msg, Some(Msg::Resume(_))) => {
+                debug!("Drop GameMsg {} while resuming", msg.seq);
+                return false
+            }
+            // Resume carries the latest seq, what's before is in it
+            _ if matches!(msg.msg, Some(Msg::Resume(_))) => {
+                self.pending.clear();
+                self.last_seq = Some(msg.seq);
+                return self.apply_stream_msg(msg).await;
+            }
+            Some(last) if msg.seq <= last => {
+                debug!("Drop GameMsg {} already applied", msg.seq);
+                return false
+            }
+            Some(last) if msg.seq > last + 1 => {
+                warn!("GameMsg {} arrives after {}, fetching the missing ones", msg.seq, last);
+                self.pending.insert(msg.seq, msg);
+                if !self.fetch_missing(last).await {
+                    // never apply msgs across the gap, start over from a Resume msg
+                    self.pending.clear();
+                    self.resuming = Some(0);
+                    reply.cancel_stream();
+                    reply.resume_after = Some(Self::resume_backoff(0));
+                    return false
+                }
+            }
+            _ => {
+                self.pending.insert(msg.seq, msg);
+            }
+        }
+
+        // whatever is still missing after fetching was sent to others
+        let mut redraw = false;
+        while let Some((seq, msg)) = self.pending.pop_first() {
+            self.last_seq = Some(seq);
+            redraw |= self.apply_stream_msg(msg).await;
+        }
+        redraw
+    }
+
+    // return false if the backlog can't fill the gap
+    async fn fetch_missing(&mut self, last: u64) -> bool {
+        let (mut c, pid, roomid) = match self.state {
+            ClientStateInternal::WaitPlayer { ref client, ref roomid, ref players, .. }
+            | ClientStateInternal::WaitReady { ref client, ref roomid, ref players, .. }
+                => (client.clone(), Some(players[0].1), roomid.clone()),
+            ClientStateInternal::Gaming { ref client, ref roomid, my_remote_idx, .. }
+            | ClientStateInternal::GameResult { ref client, ref roomid, my_remote_idx, .. }
+                => (client.clone(), Some(my_remote_idx), roomid.clone()),
+            ClientStateInternal::Spectating { ref client, ref roomid, .. }
+                => (client.clone(), None, roomid.clone()),
+            _ => return false,
+        };
+
+        match c.fetch_since(pid, roomid, last).await {
+            Ok(msgs) => for m in msgs {
+                self.pending.entry(m.seq).or_insert(m);
+            }
+            Err(s) => {
+                warn!("Failed to fetch GameMsg since {}: {}", last, s);
+                return false
+            }
+        }
+        true
+    }
+
+    async fn apply_stream_msg(&mut self, msg: GameMsg) -> bool {
+        if let Some(Msg::Reaction(rm)) = msg.msg {
+            return self.handle_reaction_msg(rm);
+        }
+        self.note_room_info(&msg);
+        if let ClientStateInternal::Spectating {..} = self.state {
+            return self.handle_spectate_msg(msg).await;
+        }
+        if let Some(Msg::HostNotice(hn)) = msg.msg {
+            return self.handle_host_notice(msg.your_id as usize, hn);
+        }
+        if let Some(Msg::Kicked(host)) = msg.msg {
+            return self.handle_kicked(host);
+        }
+        if let Some(Msg::Resume(ri)) = msg.msg {
+            return self.handle_resume_info(msg.your_id as usize, ri);
+        }
+        if let Some(Msg::Chat(cm)) = msg.msg {
+            return self.handle_chat_msg(cm);
+        }
         match self.state {
             ClientStateInternal::WaitPlayer {
                 ref mut client, ref mut players, ref roomid, ..
@@ -72,6 +531,8 @@ impl ClientStateManager {
                                 players: players.clone(),
                                 msg: vec!["Please press the button to get ready!".into()],
                                 roomid: roomid.clone(),
+                                rules: ri.rules.unwrap_or_default(),
+                                profiles: vec![],
                             };
                             self.exitmenu.1 = 0;
                         }
@@ -79,21 +540,30 @@ impl ClientStateManager {
                     Some(Msg::ExitRoom(ri)) => {
                         *players = rpc::room_info_to_players(msg.your_id as usize, &ri);
                     }
-                    None => panic!("Got empty GameMsg!"),
-                    _ => panic!("Got GameMsg not possible in state WaitPlayer!"),
+                    m => {
+                        warn!("Drop GameMsg not possible in state WaitPlayer: {:?}", m);
+                        return false
+                    }
                 }
                 true
             }
             ClientStateInternal::WaitReady {
-                ref mut client, ref mut players, ref roomid, ..
+                ref mut client, ref mut players, ref roomid, ref mut rules, msg: ref mut rmsg, ..
             } => {
                 match msg.msg {
                     Some(Msg::RoomInfo(ri)) => {
                         *players = rpc::room_info_to_players(msg.your_id as usize, &ri);
+                        *rules = ri.rules.unwrap_or_default();
                     }
                     Some(Msg::WhoReady(who)) => {
                         Self::someone_get_ready(players, who as usize);
                     }
+                    Some(Msg::DealFailed(e)) => {
+                        // the bots get ready again right after this
+                        players.iter_mut().for_each(|p| p.2 = false);
+                        *rmsg = vec![format!("Cannot deal: {}", e),
+                            "Please press the button to get ready!".into()];
+                    }
                     Some(Msg::Start(next)) => {
                         let gi = client.game_status(players[0].1, roomid.clone())
                                     .await.unwrap_or_else(
@@ -104,11 +574,13 @@ impl ClientStateManager {
                         ).collect();
 
                         let mut game = Game::new();
+                        game.set_rules(*rules).unwrap();
+                        game.set_seats(players.len()).unwrap();
                         players.iter().for_each(|p| {
                             game.add_player(p.0.clone());
                         });
-                        game.set_next(players.iter().position(|p| p.1 == next as usize).unwrap());
-                        game.init_my_cards(cards);
+                        game.set_start(players.iter().position(|p| p.1 == next as usize).unwrap());
+                        game.init_my_cards(cards, players[0].1);
 
                         self.state = ClientStateInternal::Gaming{
                             client: client.clone(),
@@ -118,6 +590,7 @@ impl ClientStateManager {
                             choose: 0,
                             button: 0,
                             msg: None,
+                            clock: None,
                         };
                         self.exitmenu.1 = 0;
                     }
@@ -146,48 +619,65 @@ impl ClientStateManager {
                         };
                         self.exitmenu.1 = 0;
                     }
-                    None => panic!("Got empty GameMsg!"),
-                    _ => panic!("Got GameMsg not possible in state WaitReady!"),
+                    m => {
+                        warn!("Drop GameMsg not possible in state WaitReady: {:?}", m);
+                        return false
+                    }
                 }
                 true
             }
             ClientStateInternal::Gaming {
-                ref mut client, ref roomid, ref mut game, my_remote_idx, ..
+                ref mut client, ref roomid, ref mut game, my_remote_idx, msg: ref mut gmsg,
+                ref mut clock, ..
             } => {
+                let seats = game.get_seats();
                 match msg.msg {
                     Some(Msg::Play(mut pi)) => {
-                        pi.player = Self::get_local_idx(my_remote_idx, pi.player as usize) as u32;
+                        pi.player = Self::get_local_idx(my_remote_idx, pi.player as usize, seats) as u32;
                         game.play_card_no_check(pi.into()).unwrap();
                     }
+                    Some(Msg::BotTakeover(who)) => {
+                        let name = game.get_player_name(
+                            Self::get_local_idx(my_remote_idx, who as usize, seats)
+                        );
+                        *gmsg = Some(format!("{} lost connection, a bot plays for now.", name));
+                    }
+                    Some(Msg::TurnDeadline(td)) => {
+                        let player = Self::get_local_idx(my_remote_idx, td.player as usize, seats);
+                        *clock = Some(Self::turn_clock(player, &td));
+                    }
                     Some(Msg::Endgame(GameEnding { desk, hold, winner, winner_state })) => {
                         let ds = desk.expect("Empty DeskResult in GameResult from server!");
                         // actually it should be already sorted
                         // holds.sort();
                         self.state = ClientStateInternal::GameResult{
-                            ds: Self::parse_desk_result(&ds, my_remote_idx),
+                            ds: Self::parse_desk_result(&ds, my_remote_idx, seats),
                             players: Self::parse_hold_result(
                                 &hold, game.get_player_names(), my_remote_idx
                             ),
-                            winner: Self::get_local_idx(my_remote_idx, winner as usize),
+                            winner: Self::get_local_idx(my_remote_idx, winner as usize, seats),
                             winner_state: winner_state.try_into().unwrap(),
                             my_remote_idx,
                             client: client.clone(),
                             roomid: roomid.clone(),
+                            scoreboard: None,
                         };
                         self.exitmenu.1 = 0;
                     }
                     Some(Msg::ExitGame(who)) => {
                         let exit_name = game.get_player_name(
-                            Self::get_local_idx(my_remote_idx, who as usize)
+                            Self::get_local_idx(my_remote_idx, who as usize, seats)
                         );
                         self.state = ClientStateInternal::WaitReady {
                             client: client.clone(),
                             players: game.get_player_names().into_iter().enumerate().map(
-                                |(i, name)| (name, Self::get_remote_idx(my_remote_idx, i), false)
+                                |(i, name)| (name, Self::get_remote_idx(my_remote_idx, i, seats), false)
                             ).collect(),
                             roomid: roomid.clone(),
                             msg: vec![format!("Player {} exits game.", exit_name),
                                 "Please press the button to get ready!".into()],
+                            rules: game.get_rules(),
+                            profiles: vec![],
                         };
                         self.exitmenu.1 = 0;
                     }
@@ -211,7 +701,10 @@ impl ClientStateManager {
                         };
                         self.exitmenu.1 = 0;
                     }
-                    _ => panic!("Got GameMsg other than Msg::Play in state Gaming!"),
+                    m => {
+                        warn!("Drop GameMsg not possible in state Gaming: {:?}", m);
+                        return false
+                    }
                 }
                 true
             }
@@ -246,10 +739,16 @@ impl ClientStateManager {
                         info!("Stream got WhoReady in GameResult, drop");
                         true
                     }
-                    _ => panic!("Got impossible GameMsg in state GameResult!"),
+                    m => {
+                        warn!("Drop GameMsg not possible in state GameResult: {:?}", m);
+                        false
+                    }
                 }
             }
-            _ => panic!("Shouldn't receive any stream msg in this state!"),
+            _ => {
+                warn!("Drop GameMsg outside a room: {:?}", msg);
+                false
+            }
         }
     }
 
@@ -257,40 +756,41 @@ impl ClientStateManager {
         if let Some(p) = players.iter_mut().find(|p| p.1 == who) {
             p.2 = true;
         } else {
-            panic!("Player ID {} doesn't exists!", who);
+            warn!("Player ID {} doesn't exists!", who);
         }
     }
 
 
-    fn parse_hold_result(
+    pub fn parse_hold_result(
         hs: &Vec<HoldList>, names: Vec<String>, my_remote_idx: usize
     ) -> Vec<(String, Vec<Card>)> {
-        let mut ret: Vec<(String, Vec<Card>)> = vec![Default::default(); 4];
+        let seats = names.len();
+        let mut ret: Vec<(String, Vec<Card>)> = vec![Default::default(); seats];
         for (local_idx, name) in names.into_iter().enumerate() {
             ret[local_idx].0 = name;
-            ret[local_idx].1 = hs[Self::get_remote_idx(my_remote_idx, local_idx)]
+            ret[local_idx].1 = hs[Self::get_remote_idx(my_remote_idx, local_idx, seats)]
                                 .holds.iter().map(|c| c.into()).collect();
         }
         ret
     }
 
-    fn get_local_idx(my_remote_idx: usize, remote_idx: usize) -> usize {
-        (remote_idx + 4 - my_remote_idx) % 4
+    fn get_local_idx(my_remote_idx: usize, remote_idx: usize, seats: usize) -> usize {
+        (remote_idx + seats - my_remote_idx) % seats
     }
 
-    pub fn get_remote_idx(my_remote_idx: usize, local_idx: usize) -> usize {
-        (local_idx + my_remote_idx) % 4
+    pub fn get_remote_idx(my_remote_idx: usize, local_idx: usize, seats: usize) -> usize {
+        (local_idx + my_remote_idx) % seats
     }
 
-    fn parse_desk_result(
-        ds: &DeskResult, my_remote_idx: usize
+    pub fn parse_desk_result(
+        ds: &DeskResult, my_remote_idx: usize, seats: usize
     ) -> Vec<Vec<(Card, usize)>> {
         let mut ret = Vec::new();
         for each in [&ds.spade, &ds.heart, &ds.club, &ds.diamond] {
             let mut chain: Vec<(Card, usize)> = each.iter().map(
                 |cs| {
                     (cs.card.as_ref().unwrap().into(),
-                        Self::get_local_idx(my_remote_idx, cs.whose as usize))
+                        Self::get_local_idx(my_remote_idx, cs.whose as usize, seats))
                 }
             ).collect();
             chain.sort_by(|a, b| b.cmp(a));
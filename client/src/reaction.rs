@@ -0,0 +1,72 @@
+use crate::*;
+
+// how long a bubble stays, in clock ticks
+const BUBBLE_TICKS: u32 = 3;
+
+impl ClientStateManager {
+    // only players in a game send reactions
+    pub(crate) fn can_react(&self) -> bool {
+        matches!(self.state, ClientStateInternal::Gaming {..})
+    }
+
+    pub fn handle_toggle_reactions(&mut self) -> bool {
+        if !self.can_react() {
+            return false
+        }
+        self.picking_reaction = !self.picking_reaction;
+        true
+    }
+
+    pub async fn handle_send_reaction(&mut self, reaction: Reaction) -> bool {
+        self.picking_reaction = false;
+        let ClientStateInternal::Gaming {
+            ref mut client, ref roomid, my_remote_idx, ref mut msg, ..
+        } = self.state else {
+            return false
+        };
+
+        // our own bubble comes back on the stream like everyone else's
+        if let Err(s) = client.send_reaction(my_remote_idx, roomid.clone(), reaction).await {
+            warn!("Failed to send reaction: {}", s);
+            *msg = Some(s.message().into());
+        }
+        true
+    }
+
+    pub fn handle_reaction_msg(&mut self, rm: ReactionMsg) -> bool {
+        let Ok(reaction) = Reaction::try_from(rm.reaction) else {
+            warn!("Drop unknown reaction {}", rm.reaction);
+            return false
+        };
+        self.bubbles.insert(rm.player as usize, (reaction, BUBBLE_TICKS));
+        !self.local_bubbles().is_empty()
+    }
+
+    pub(crate) fn tick_bubbles(&mut self) -> bool {
+        if self.bubbles.is_empty() {
+            return false
+        }
+        self.bubbles.retain(|_, (_, ticks)| {
+            *ticks -= 1;
+            *ticks > 0
+        });
+        true
+    }
+
+    // by local seat, empty in states without players on screen
+    pub(crate) fn local_bubbles(&self) -> Vec<Option<Reaction>> {
+        let bubble = |remote: usize| self.bubbles.get(&remote).map(|b| b.0);
+        match self.state {
+            ClientStateInternal::Gaming { ref game, my_remote_idx, .. } => {
+                let seats = game.get_seats();
+                (0..seats).map(
+                    |i| bubble(Self::get_remote_idx(my_remote_idx, i, seats))
+                ).collect()
+            }
+            // spectators see from seat 0
+            ClientStateInternal::Spectating { game: Some(ref game), .. }
+                => (0..game.get_seats()).map(bubble).collect(),
+            _ => vec![],
+        }
+    }
+}
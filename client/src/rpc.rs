@@ -1,5 +1,6 @@
 use crate::*;
 use tonic::codec::Streaming;
+use tonic::metadata::MetadataValue;
 
 #[cfg(not(target_arch = "wasm32"))]
 use tonic::transport::Channel;
@@ -13,21 +14,26 @@ pub struct RpcClient {
     #[cfg(target_arch = "wasm32")]
     pub c: Heart7Client<Client>,
     pub addr: String,
+    // given by JoinRoom, for resuming a lost GameStream
+    pub session: Option<String>,
+    // given to Spectate, for watching a private room
+    pub password: Option<String>,
 }
 
 pub type GameStream = Streaming<GameMsg>;
+pub type LobbyStream = Streaming<RoomList>;
 
 impl RpcClient {
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn new(c: Heart7Client<Channel>, addr: String) -> RPCResult<Self> {
-        let mut rpcclient = Self { c, addr };
+        let mut rpcclient = Self { c, addr, session: None, password: None };
         rpcclient.hello().await?;
         Ok(rpcclient)
     }
 
     #[cfg(target_arch = "wasm32")]
     pub async fn new(c: Heart7Client<Client>, addr: String) -> RPCResult<Self> {
-        let mut rpcclient = Self { c, addr };
+        let mut rpcclient = Self { c, addr, session: None, password: None };
         rpcclient.hello().await?;
         Ok(rpcclient)
     }
@@ -36,6 +42,26 @@ impl RpcClient {
         self.addr.clone()
     }
 
+    // for per-player calls, the server checks our session in metadata
+    fn request_with_session<T>(&self, msg: T) -> Request<T> {
+        let mut request = Request::new(msg);
+        if let Some(ref s) = self.session {
+            match s.parse() {
+                Ok(v) => { request.metadata_mut().insert(SESSION_KEY, v); }
+                Err(e) => { error!("Invalid session {}: {}", s, e); }
+            }
+        }
+        request
+    }
+
+    // for calls without a seat, a private room checks its password in metadata
+    fn with_password<T>(&self, mut request: Request<T>) -> Request<T> {
+        if let Some(ref p) = self.password {
+            request.metadata_mut().insert_bin(PASSWORD_KEY, MetadataValue::from_bytes(p.as_bytes()));
+        }
+        request
+    }
+
     pub async fn hello(&mut self) -> RPCResult<()> {
         let r = self.c.hello(EmptyRequest{}).await?.into_inner();
         if r.success {
@@ -49,15 +75,22 @@ impl RpcClient {
         }
     }
 
-    pub async fn new_room(&mut self, name: String) -> RPCResult<()> {
+    // an empty name for an invite code, return the roomid
+    pub async fn new_room(&mut self, name: String, password: String) -> RPCResult<String> {
         let request = Request::new(NewRoomReq {
-            roomid: name
+            roomid: name,
+            deal: None,
+            rules: None,
+            seats: 0,
+            match_config: None,
+            turn_timer: None,
+            password,
         });
 
         let r = self.c.new_room(request).await?.into_inner();
         if r.success {
             assert!(r.msg == "Ok");
-            Ok(())
+            Ok(r.roomid)
         } else {
             Err(Status::new(
                 Code::Internal,
@@ -66,18 +99,28 @@ impl RpcClient {
         }
     }
 
-    pub async fn join_room(&mut self, name: String, roomid: String) -> RPCResult<usize> {
+    pub async fn list_rooms(&mut self) -> RPCResult<RoomList> {
+        Ok(self.c.list_rooms(EmptyRequest{}).await?.into_inner())
+    }
+
+    pub async fn lobby_stream(&mut self) -> RPCResult<LobbyStream> {
+        Ok(self.c.lobby_stream(EmptyRequest{}).await?.into_inner())
+    }
+
+    pub async fn join_room(&mut self, name: String, roomid: String, password: String) -> RPCResult<usize> {
         let request = Request::new(JoinRoomReq{
             player: Some(PlayerInfo { name }),
-            roomid
+            roomid,
+            password,
         });
 
-        let PlayerId { your_id } = self.c.join_room(request).await?.into_inner();
+        let PlayerId { your_id, session } = self.c.join_room(request).await?.into_inner();
+        self.session = Some(session);
         Ok(your_id as usize)
     }
 
     pub async fn game_stream(&mut self, pid: usize, roomid: String) -> RPCResult<GameStream> {
-        let request = Request::new(RoomReq{
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
@@ -85,8 +128,67 @@ impl RpcClient {
         Ok(self.c.game_stream(request).await?.into_inner())
     }
 
+    // needs no seat, a private room takes its password or the session of a member
+    pub async fn spectate(&mut self, roomid: String, password: String) -> RPCResult<GameStream> {
+        self.password = (!password.is_empty()).then_some(password);
+        let request = self.with_password(self.request_with_session(RoomReq{
+            playerid: 0,
+            roomid
+        }));
+
+        Ok(self.c.spectate(request).await?.into_inner())
+    }
+
+    // None for the public snapshot, which needs no session
+    pub async fn game_snapshot(&mut self, pid: Option<usize>, roomid: String) -> RPCResult<GameSnapshot> {
+        let roomreq = RoomReq{
+            playerid: pid.unwrap_or(0) as u32,
+            roomid
+        };
+        let request = if pid.is_some() {
+            self.request_with_session(roomreq)
+        } else {
+            self.with_password(Request::new(roomreq))
+        };
+
+        Ok(self.c.snapshot(request).await?.into_inner())
+    }
+
+    // None for what spectators got, which needs no session
+    pub async fn fetch_since(&mut self, pid: Option<usize>, roomid: String, seq: u64) -> RPCResult<Vec<GameMsg>> {
+        let fetchreq = FetchReq{
+            roomreq: Some(RoomReq{
+                playerid: pid.unwrap_or(0) as u32,
+                roomid
+            }),
+            seq,
+        };
+        let request = if pid.is_some() {
+            self.request_with_session(fetchreq)
+        } else {
+            self.with_password(Request::new(fetchreq))
+        };
+
+        Ok(self.c.fetch_since(request).await?.into_inner().msgs)
+    }
+
+    pub async fn resume_session(&mut self, roomid: String) -> RPCResult<GameStream> {
+        let session = self.session.clone().ok_or(
+            Status::new(
+                Code::FailedPrecondition,
+                "No session to resume"
+            )
+        )?;
+        let request = Request::new(ResumeReq{
+            roomid,
+            session,
+        });
+
+        Ok(self.c.resume_session(request).await?.into_inner())
+    }
+
     pub async fn stream_ready(&mut self, pid: usize, roomid: String) -> RPCResult<()> {
-        let request = Request::new(RoomReq{
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
@@ -112,8 +214,21 @@ impl RpcClient {
         Ok(self.c.room_status(request).await?.into_inner())
     }
 
-    pub async fn game_ready(&mut self, pid: usize, roomid: String) -> RPCResult<GameReadyReply> {
+    pub async fn match_status(&mut self, roomid: String) -> RPCResult<MatchInfo> {
         let request = Request::new(RoomReq{
+            playerid: 0,
+            roomid
+        });
+
+        Ok(self.c.match_status(request).await?.into_inner())
+    }
+
+    pub async fn get_profile(&mut self, name: String) -> RPCResult<Profile> {
+        Ok(self.c.get_profile(PlayerInfo { name }).await?.into_inner())
+    }
+
+    pub async fn game_ready(&mut self, pid: usize, roomid: String) -> RPCResult<GameReadyReply> {
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
@@ -122,7 +237,7 @@ impl RpcClient {
     }
 
     pub async fn game_status(&mut self, pid: usize, roomid: String) -> RPCResult<GameInfo> {
-        let request = Request::new(RoomReq{
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
@@ -135,7 +250,7 @@ impl RpcClient {
             playerid: pid as u32,
             roomid
         };
-        let request = Request::new(PlayReq{
+        let request = self.request_with_session(PlayReq{
             roomreq: Some(roomreq),
             playone: Some(playone),
         });
@@ -152,8 +267,140 @@ impl RpcClient {
         }
     }
 
+    pub async fn send_chat(&mut self, pid: usize, roomid: String, text: String) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(ChatReq{
+            roomreq: Some(roomreq),
+            text,
+        });
+
+        let r = self.c.send_chat(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when sending chat, {}", r.msg)
+            ).into())
+        }
+    }
+
+    pub async fn send_reaction(&mut self, pid: usize, roomid: String, reaction: Reaction) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(ReactionReq{
+            roomreq: Some(roomreq),
+            reaction: reaction.into(),
+        });
+
+        let r = self.c.send_reaction(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when sending reaction, {}", r.msg)
+            ).into())
+        }
+    }
+
+    pub async fn kick_player(&mut self, pid: usize, roomid: String, target: usize) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(SeatReq{
+            roomreq: Some(roomreq),
+            target: target as u32,
+        });
+
+        let r = self.c.kick_player(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when kicking a player, {}", r.msg)
+            ).into())
+        }
+    }
+
+    pub async fn lock_room(&mut self, pid: usize, roomid: String, locked: bool) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(LockReq{
+            roomreq: Some(roomreq),
+            locked,
+        });
+
+        let r = self.c.lock_room(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when locking room, {}", r.msg)
+            ).into())
+        }
+    }
+
+    pub async fn transfer_host(&mut self, pid: usize, roomid: String, target: usize) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(SeatReq{
+            roomreq: Some(roomreq),
+            target: target as u32,
+        });
+
+        let r = self.c.transfer_host(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when transferring host, {}", r.msg)
+            ).into())
+        }
+    }
+
+    pub async fn change_settings(&mut self, pid: usize, roomid: String, settings: RoomSettings) -> RPCResult<()> {
+        let roomreq = RoomReq{
+            playerid: pid as u32,
+            roomid
+        };
+        let request = self.request_with_session(SettingsReq{
+            roomreq: Some(roomreq),
+            settings: Some(settings),
+        });
+
+        let r = self.c.change_settings(request).await?.into_inner();
+        if r.success {
+            assert!(r.msg == "Ok");
+            Ok(())
+        } else {
+            Err(Status::new(
+                Code::Internal,
+                format!("Server response false when changing settings, {}", r.msg)
+            ).into())
+        }
+    }
+
     pub async fn exit_game(&mut self, pid: usize, roomid: String) -> RPCResult<()> {
-        let request = Request::new(RoomReq{
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
@@ -171,12 +418,15 @@ impl RpcClient {
     }
 
     pub async fn exit_room(&mut self, pid: usize, roomid: String) -> RPCResult<()> {
-        let request = Request::new(RoomReq{
+        let request = self.request_with_session(RoomReq{
             playerid: pid as u32,
             roomid
         });
 
-        let r = self.c.exit_room(request).await?.into_inner();
+        let r = self.c.exit_room(request).await;
+        // session is useless once we try to leave
+        self.session = None;
+        let r = r?.into_inner();
         if r.success {
             assert!(r.msg == "Ok");
             Ok(())
@@ -187,10 +437,22 @@ impl RpcClient {
             ).into())
         }
     }
+
+    pub async fn add_bot(&mut self, roomid: String, kind: BotKind) -> RPCResult<usize> {
+        let request = self.request_with_session(AddBotReq{
+            roomid,
+            kind: kind.into(),
+        });
+
+        let PlayerId { your_id, .. } = self.c.add_bot(request).await?.into_inner();
+        Ok(your_id as usize)
+    }
 }
 
 pub fn room_info_to_players(my_remote_idx: usize, ri: &RoomInfo) -> Vec<(String, usize, bool)> {
-    let mut players = vec![("".into(), 0, false); 4];
+    // 0 from an older server, which always seats four
+    let seats = if ri.seats == 0 { dealer::DEFAULT_SEATS } else { ri.seats as usize };
+    let mut players = vec![("".into(), 0, false); seats];
     for i in 0..ri.players.len() {
         players[i].0 = ri.players[i].name.clone();
         players[i].1 = i;
@@ -203,3 +465,65 @@ pub fn room_info_to_players(my_remote_idx: usize, ri: &RoomInfo) -> Vec<(String,
     players.rotate_left(my_remote_idx);
     players
 }
+
+// rotate everything by seat so that local player 0 comes first
+pub fn match_info_to_local(my_remote_idx: usize, mut mi: MatchInfo) -> MatchInfo {
+    let seats = mi.totals.len();
+    if my_remote_idx >= seats {
+        return mi;
+    }
+    mi.totals.rotate_left(my_remote_idx);
+    for hs in mi.history.iter_mut() {
+        hs.points.rotate_left(my_remote_idx);
+    }
+    for l in mi.leaders.iter_mut() {
+        *l = ((*l as usize + seats - my_remote_idx) % seats) as u32;
+    }
+    mi.leaders.sort();
+    mi
+}
+
+// build a Game with the snapshot owner as local player 0,
+// the same way the client keeps it while gaming
+pub fn snapshot_to_game(mut gs: GameSnapshot) -> GameResult<Game> {
+    let me = gs.your_id;
+    let seats = gs.players.len() as u32;
+    if me >= seats {
+        return Err(GameError::NotFound(format!("Player {} not exist!", me)));
+    }
+    let local = |remote: u32| (remote + seats - me) % seats;
+
+    gs.players.rotate_left(me as usize);
+    gs.card_nums.rotate_left(me as usize);
+    gs.hold_nums.rotate_left(me as usize);
+    gs.start = local(gs.start);
+    gs.next = local(gs.next);
+    if let Some(ref mut ds) = gs.desk {
+        for chain in [&mut ds.spade, &mut ds.heart, &mut ds.club, &mut ds.diamond] {
+            chain.iter_mut().for_each(|cr| cr.whose = local(cr.whose));
+        }
+    }
+    gs.thisround.iter_mut().for_each(|cr| cr.whose = local(cr.whose));
+    gs.last.iter_mut().chain(gs.history.iter_mut()).for_each(
+        |pi| pi.player = local(pi.player)
+    );
+    gs.your_id = 0;
+
+    Game::from_snapshot(&gs)
+}
+
+// one line of the lobby list, shared by all frontends
+pub fn lobby_row(rs: &RoomSummary) -> String {
+    let Some(ref ri) = rs.info else {
+        return "".into();
+    };
+    let state = match ri.state {
+        Some(State::NotFull(_)) => "Waiting",
+        Some(State::WaitReady(_)) => "Full",
+        Some(State::Gaming(_)) => "Gaming",
+        Some(State::EndGame(_)) => "Game Over",
+        None => "Unknown",
+    };
+    let private = if ri.has_password { "  Private" } else { "" };
+    format!("{}  [{}/{}]  {}{}", ri.roomid, ri.players.len(), rs.seats, state, private)
+}
@@ -0,0 +1,29 @@
+// where each local seat sits on screen, shared by all frontends
+//
+// local seat 0 is always at the bottom, the others go round counterclockwise
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatPos {
+    Bottom,
+    Right,
+    // the roomy one of a four seat table
+    Top,
+    Left,
+    // compact ones when the top row seats more than one
+    TopLeft,
+    TopMid,
+    TopRight,
+}
+
+pub fn seat_positions(seats: usize) -> &'static [SeatPos] {
+    use SeatPos::*;
+    match seats {
+        3 => &[Bottom, Right, Left],
+        5 => &[Bottom, Right, TopRight, TopLeft, Left],
+        6 => &[Bottom, Right, TopRight, TopMid, TopLeft, Left],
+        _ => &[Bottom, Right, Top, Left],
+    }
+}
+
+pub fn seat_pos(seats: usize, local_idx: usize) -> SeatPos {
+    seat_positions(seats)[local_idx]
+}
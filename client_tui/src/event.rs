@@ -146,6 +146,9 @@ impl TermEventHandler {
             KeyCode::Delete => {
                 tx.send(ClientEvent::Delete).await.expect("Send Action::Delete to client");
             }
+            KeyCode::Tab => {
+                tx.send(ClientEvent::ToggleChat).await.expect("Send Action::ToggleChat to client");
+            }
             _ => {}
         }
     }
@@ -1,4 +1,5 @@
 mod event;
+mod replay;
 mod tui;
 mod ui;
 
@@ -13,7 +14,9 @@ use clap::Parser;
 use anyhow::Result;
 pub use ::heart7_client::*;
 use std::net::Ipv4Addr;
+use std::path::PathBuf;
 use tui::*;
+use replay::ReplayTui;
 
 pub const DEFAULT_PORT: u16 = 20007;
 pub const DEFAULT_CHANNEL_SIZE: usize = 64;
@@ -24,6 +27,10 @@ struct Args {
     #[arg(short, long)]
     #[clap(default_value_t=format!("127.0.0.1:{}", DEFAULT_PORT))]
     addr: String,
+
+    /// View a hand saved by the server's --replay-dir, no server needed
+    #[arg(long)]
+    replay: Option<PathBuf>,
 }
 
 pub(crate) fn add_cancel_to_panic(cancel: CancellationToken) {
@@ -44,6 +51,7 @@ struct ClientTui {
     te_cancel: CancellationToken,
     // a panic from stream listener should NOT cancel client.
     stream_cancel: CancellationToken,
+    lobby_cancel: CancellationToken,
 }
 
 impl ClientTui {
@@ -51,6 +59,7 @@ impl ClientTui {
         let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
 
         let stream_cancel = CancellationToken::new();
+        let lobby_cancel = CancellationToken::new();
         let te_cancel = CancellationToken::new();
         add_cancel_to_panic(te_cancel.clone());
         add_cancel_to_panic(stream_cancel.clone());
@@ -61,6 +70,7 @@ impl ClientTui {
             tx,
             rx,
             stream_cancel,
+            lobby_cancel,
             te_cancel,
         })
     }
@@ -85,7 +95,12 @@ impl ClientTui {
                     }
                     maybe_msg = gs.message() => {
                         match maybe_msg {
-                            Err(s) => panic!("GameStream error: {}", s),
+                            Err(s) => {
+                                warn!("GameStream error: {}", s);
+                                txc.send(ClientEvent::StreamLost(s.to_string())).await
+                                    .expect("Send Action::StreamLost to client");
+                                break;
+                            }
                             Ok(None) => {
                                 info!("GameStream closed! Stream listener exits!");
                                 break;
@@ -99,6 +114,70 @@ impl ClientTui {
         });
     }
 
+    fn spawn_lobby_listener(&mut self, mut ls: LobbyStream) {
+        let txc = self.tx.clone();
+
+        if self.lobby_cancel.is_cancelled() {
+            self.lobby_cancel = CancellationToken::new();
+        }
+        let lcancel = self.lobby_cancel.clone();
+
+        info!("Spawning LobbyStream listener...");
+        tokio::spawn(async move {
+            loop {
+                tokio::select!{
+                    _ = lcancel.cancelled() => {
+                        info!("lobby listener is cancelled");
+                        break;
+                    }
+                    maybe_msg = ls.message() => {
+                        match maybe_msg {
+                            Err(s) => {
+                                // the lobby is only a convenience, rooms can still be joined by ID
+                                warn!("LobbyStream error: {}", s);
+                                break;
+                            }
+                            Ok(None) => {
+                                info!("LobbyStream closed! Lobby listener exits!");
+                                break;
+                            }
+                            Ok(Some(rl)) => txc.send(ClientEvent::LobbyMsg(rl)).await
+                                .expect("Send Action::LobbyMsg to client"),
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn spawn_resume_timer(&mut self, millis: u32) {
+        let txc = self.tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(millis as u64)).await;
+            txc.send(ClientEvent::ResumeSession).await
+                .expect("Send Action::ResumeSession to client");
+        });
+    }
+
+    // the turn clock counts down on these, stops with the client
+    fn spawn_clock_ticker(&mut self) {
+        let txc = self.tx.clone();
+        let cancel = self.te_cancel.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+            loop {
+                tokio::select!{
+                    _ = cancel.cancelled() => break,
+                    _ = interval.tick() => {
+                        if txc.send(ClientEvent::ClockTick).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
     fn spawn_rpc_client(&mut self, addr: String) {
         let txc = self.tx.clone();
         tokio::spawn(async move {
@@ -125,6 +204,7 @@ impl ClientTui {
     pub async fn run(&mut self) -> Result<()> {
         info!("Starting terminal event handler...");
         TermEventHandler::spawn(DEFAULT_CHANNEL_SIZE, self.te_cancel.clone(), self.tx.clone())?;
+        self.spawn_clock_ticker();
 
         // draw first anyway
         self.draw()?;
@@ -147,12 +227,21 @@ impl ClientTui {
                             if reply.cancel_stream_listener {
                                 self.stream_cancel.cancel();
                             }
+                            if reply.cancel_lobby_listener {
+                                self.lobby_cancel.cancel();
+                            }
                             if let Some(addr) = reply.spawn_rpc_client {
                                 self.spawn_rpc_client(addr);
                             }
                             if let Some(gs) = reply.spawn_stream_listener {
                                 self.spawn_stream_listener(gs);
                             }
+                            if let Some(ls) = reply.spawn_lobby_listener {
+                                self.spawn_lobby_listener(ls);
+                            }
+                            if let Some(millis) = reply.resume_after {
+                                self.spawn_resume_timer(millis);
+                            }
                             if reply.need_redraw {
                                 self.draw()?;
                             }
@@ -177,6 +266,7 @@ impl ClientTui {
     pub fn exit(mut self) -> Result<()> {
         self.te_cancel.cancel();
         self.stream_cancel.cancel();
+        self.lobby_cancel.cancel();
         self.tui.exit()?;
         Ok(())
     }
@@ -199,6 +289,14 @@ async fn main() -> Result<()> {
 
     info!("Heart7 Client Starts!");
 
+    if let Some(path) = args.replay {
+        let mut viewer = ReplayTui::new(&path)?;
+        viewer.run().await?;
+        info!("Exiting...");
+        viewer.exit()?;
+        return Ok(());
+    }
+
     let mut client = ClientTui::new(args.addr)?;
     client.run().await?;
 
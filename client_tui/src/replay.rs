@@ -0,0 +1,221 @@
+use crate::*;
+use crate::event::TermEventHandler;
+use std::fs;
+use std::path::Path;
+use tokio::sync::mpsc;
+use tokio::time::{self, Duration, Instant, Interval};
+use tokio_util::sync::CancellationToken;
+use anyhow::Result;
+
+// millis between two moves when autoplaying, from slow to fast
+const AUTOPLAY_SPEEDS: [u64; 5] = [2000, 1000, 500, 250, 100];
+const DEFAULT_SPEED: usize = 1;
+
+// a saved hand, stepped through without any server
+pub struct Replay {
+    pub title: String,
+    pub names: Vec<String>,
+    // game before any play, then after each play
+    pub steps: Vec<Game>,
+    pub plays: Vec<Play>,
+    pub ending: GameEnding,
+    // how many plays are shown
+    pub at: usize,
+    pub autoplay: bool,
+    pub speed: usize,
+    // move number being typed
+    pub jump: String,
+}
+
+impl Replay {
+    pub fn load(path: &Path) -> Result<Self> {
+        let rec = GameRecord::from_json(&fs::read_to_string(path)?)?;
+        let steps = rec.replay_steps()?;
+
+        Ok(Self {
+            title: path.file_name().unwrap_or_default().to_string_lossy().into(),
+            names: rec.names,
+            steps,
+            plays: rec.plays.into_iter().map(|tp| tp.play).collect(),
+            ending: rec.ending,
+            at: 0,
+            autoplay: false,
+            speed: DEFAULT_SPEED,
+            jump: String::new(),
+        })
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(AUTOPLAY_SPEEDS[self.speed])
+    }
+
+    // (ds, players, winner, winner_state) for ui_game_result
+    pub fn result(&self) -> (Vec<Vec<(Card, usize)>>, Vec<(String, Vec<Card>)>, usize, GameWinnerState) {
+        let GameEnding { desk, hold, winner, winner_state } = &self.ending;
+        (
+            ClientStateManager::parse_desk_result(&desk.clone().unwrap_or_default(), 0, self.names.len()),
+            ClientStateManager::parse_hold_result(hold, self.names.clone(), 0),
+            *winner as usize,
+            (*winner_state).try_into().unwrap_or(GameWinnerState::Normal),
+        )
+    }
+
+    fn is_end(&self) -> bool {
+        self.at == self.plays.len()
+    }
+
+    // returns: (redraw, restart autoplay timer, exit)
+    fn handle_event(&mut self, e: ClientEvent) -> (bool, bool, bool) {
+        match e {
+            ClientEvent::RightArrow if !self.is_end() => {
+                self.at += 1;
+                (true, false, false)
+            }
+            ClientEvent::LeftArrow if self.at > 0 => {
+                self.at -= 1;
+                (true, false, false)
+            }
+            ClientEvent::UpArrow if self.speed + 1 < AUTOPLAY_SPEEDS.len() => {
+                self.speed += 1;
+                (true, true, false)
+            }
+            ClientEvent::DownArrow if self.speed > 0 => {
+                self.speed -= 1;
+                (true, true, false)
+            }
+            ClientEvent::Type(' ') => {
+                self.autoplay = !self.autoplay;
+                if self.autoplay && self.is_end() {
+                    self.at = 0;
+                }
+                (true, true, false)
+            }
+            ClientEvent::Type(c) if c.is_ascii_digit() && self.jump.len() < 2 => {
+                self.jump.push(c);
+                (true, false, false)
+            }
+            ClientEvent::Backspace => {
+                self.jump.pop();
+                (true, false, false)
+            }
+            ClientEvent::Enter => {
+                if let Ok(n) = self.jump.parse::<usize>() {
+                    self.at = n.min(self.plays.len());
+                } else if self.is_end() {
+                    // "continue" on the result screen
+                    self.at = 0;
+                }
+                self.jump.clear();
+                (true, false, false)
+            }
+            ClientEvent::Esc if !self.jump.is_empty() => {
+                self.jump.clear();
+                (true, false, false)
+            }
+            ClientEvent::Esc | ClientEvent::CtrlC | ClientEvent::Type('q')
+                => (false, false, true),
+            ClientEvent::Resize(_, _) | ClientEvent::Refresh
+                => (true, false, false),
+            _ => (false, false, false),
+        }
+    }
+
+    // returns: redraw
+    fn tick(&mut self) -> bool {
+        if !self.autoplay || self.is_end() {
+            return false;
+        }
+
+        self.at += 1;
+        if self.is_end() {
+            self.autoplay = false;
+        }
+        true
+    }
+}
+
+pub struct ReplayTui {
+    r: Replay,
+    tui: Tui,
+    tx: mpsc::Sender<ClientEvent>,
+    rx: mpsc::Receiver<ClientEvent>,
+    te_cancel: CancellationToken,
+}
+
+impl ReplayTui {
+    pub fn new(path: &Path) -> Result<Self> {
+        // fail before taking over the terminal
+        let r = Replay::load(path)?;
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        let te_cancel = CancellationToken::new();
+        add_cancel_to_panic(te_cancel.clone());
+
+        Ok(Self {
+            r,
+            tui: Tui::new()?,
+            tx,
+            rx,
+            te_cancel,
+        })
+    }
+
+    // first tick after a full period, not right away
+    fn autoplay_timer(&self) -> Interval {
+        let period = self.r.interval();
+        time::interval_at(Instant::now() + period, period)
+    }
+
+    pub async fn run(&mut self) -> Result<()> {
+        info!("Starting terminal event handler...");
+        TermEventHandler::spawn(DEFAULT_CHANNEL_SIZE, self.te_cancel.clone(), self.tx.clone())?;
+
+        let mut timer = self.autoplay_timer();
+        self.draw()?;
+        loop {
+            tokio::select! {
+                _ = self.te_cancel.cancelled() => {
+                    break;
+                }
+                _ = timer.tick() => {
+                    if self.r.tick() {
+                        self.draw()?;
+                    }
+                }
+                event = self.rx.recv() => {
+                    match event {
+                        None => panic!("Channel to replay viewer closed!"),
+                        Some(e) => {
+                            let (redraw, restart_timer, exit) = self.r.handle_event(e);
+                            if exit {
+                                break;
+                            }
+                            if restart_timer {
+                                timer = self.autoplay_timer();
+                            }
+                            if redraw {
+                                self.draw()?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self) -> Result<()> {
+        if self.tui.should_block()? {
+            self.tui.draw_blocked()?;
+        } else {
+            self.tui.draw_replay(&self.r)?;
+        }
+        Ok(())
+    }
+
+    pub fn exit(mut self) -> Result<()> {
+        self.te_cancel.cancel();
+        self.tui.exit()?;
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use anyhow::Result;
 use crate::*;
+use crate::replay::Replay;
 
 type TuiBackend = CrosstermBackend<std::io::Stdout>;
 
@@ -61,6 +62,11 @@ impl Tui {
         Ok(())
     }
 
+    pub fn draw_replay(&mut self, r: &Replay) -> Result<()> {
+        self.terminal.draw(|frame| ui::render_replay(frame, r))?;
+        Ok(())
+    }
+
     pub fn draw_blocked(&mut self) -> Result<()> {
         let sz = self.get_size()?;
         self.terminal.draw(|frame| ui::blocked(frame, sz))?;
@@ -0,0 +1,135 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    text::*,
+    widgets::*,
+    Frame
+};
+use super::*;
+use super::game_result::name_shorten;
+
+const CHAT_WIDTH: u16 = 48;
+const CHAT_HEIGHT: u16 = 16;
+
+// a msg cut into rows of the pane, the sender's name keeps its own color
+fn chat_rows(name: &String, text: &str, width: usize) -> Vec<Line<'static>> {
+    if name.is_empty() {
+        let chars: Vec<char> = text.chars().collect();
+        return chars.chunks(width).map(
+            |c| Line::styled(c.iter().collect::<String>(), Style::default().fg(GAME_MSG))
+        ).collect();
+    }
+
+    let prefix = format!("{}: ", name_shorten(name));
+    let plen = prefix.chars().count();
+    let chars: Vec<char> = prefix.chars().chain(text.chars()).collect();
+    chars.chunks(width).enumerate().map(|(i, c)| {
+        let row: String = c.iter().collect();
+        if i == 0 {
+            let (n, t) = row.split_at(row.char_indices().nth(plen).map_or(row.len(), |(i, _)| i));
+            Line::from(vec![
+                Span::styled(n.to_string(), Style::default().fg(NAME).bold()),
+                Span::styled(t.to_string(), Style::default().fg(TEXT_NORMAL)),
+            ])
+        } else {
+            Line::styled(row, Style::default().fg(TEXT_NORMAL))
+        }
+    }).collect()
+}
+
+fn render_chat_pane<B: Backend>(frame: &mut Frame<B>, chat: &Chat) {
+    let size = frame.size();
+    let a = Rect {
+        x: size.width.saturating_sub(CHAT_WIDTH + 1),
+        y: size.height.saturating_sub(CHAT_HEIGHT + 1),
+        width: CHAT_WIDTH.min(size.width),
+        height: CHAT_HEIGHT.min(size.height),
+    };
+    frame.render_widget(Clear, a);
+    let title = if chat.scroll == 0 {
+        "Chat".to_string()
+    } else {
+        format!("Chat (-{})", chat.scroll)
+    };
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title(title)
+            .style(Style::default().fg(BORDER_LIGHT)),
+        a
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(3),
+            ].as_ref()
+        )
+        .split(a);
+
+    // newest at the bottom, fill upwards from the scrolled to one
+    let width = chunks[0].width.max(1) as usize;
+    let shown = chat.lines.len().saturating_sub(chat.scroll);
+    let mut rows = vec![];
+    for (name, text) in chat.lines[..shown].iter().rev() {
+        if rows.len() >= chunks[0].height as usize {
+            break;
+        }
+        let mut r = chat_rows(name, text, width);
+        r.extend(rows);
+        rows = r;
+    }
+    let skip = rows.len().saturating_sub(chunks[0].height as usize);
+    frame.render_widget(
+        Paragraph::new(Text::from(rows.split_off(skip))),
+        chunks[0]
+    );
+
+    let input = &chat.input;
+    let input_width = chunks[1].width.max(3) - 3;
+    let scroll = input.visual_scroll(input_width as usize);
+    frame.render_widget(
+        Paragraph::new(input.value())
+            .style(Style::default().fg(INPUT_BORDER))
+            .scroll((0, scroll as u16))
+            .block(Block::default().borders(Borders::ALL).title("ENTER to send, ESC to close")),
+        chunks[1]
+    );
+    frame.set_cursor(
+        chunks[1].x + ((input.visual_cursor()).max(scroll) - scroll) as u16 + 1,
+        chunks[1].y + 1,
+    );
+}
+
+// on the bottom border, out of the way of everything else
+fn render_chat_hint<B: Backend>(frame: &mut Frame<B>, unread: usize) {
+    let (text, color) = if unread == 0 {
+        (" TAB: Chat ".to_string(), NORMAL_DIM)
+    } else {
+        (format!(" TAB: Chat ({}) ", unread), NEXT_TURN)
+    };
+    let size = frame.size();
+    let width = text.len() as u16;
+    frame.render_widget(
+        Paragraph::new(text).style(Style::default().fg(color)),
+        Rect {
+            x: size.width.saturating_sub(width + 2),
+            y: size.height.saturating_sub(1),
+            width: width.min(size.width),
+            height: 1,
+        }
+    );
+}
+
+pub fn render_chat<B: Backend>(frame: &mut Frame<B>, chat: Chat) {
+    if chat.open {
+        render_chat_pane(frame, &chat);
+    } else {
+        render_chat_hint(frame, chat.unread);
+    }
+}
@@ -10,6 +10,7 @@ pub const INPUT_BORDER_BLOCK:    Color = Color::White;
 
 pub const BUTTON:                Color = Color::Rgb(0xE6, 0xB3, 0x25);
 pub const BUTTON_DIM:            Color = Color::Rgb(0xC8, 0xC2, 0xBC);
+pub const BUTTON_DISABLED:       Color = Color::Rgb(0x7E, 0x7E, 0x7E);
 
 pub const CENTER_MSG:            Color = Color::Rgb(0xFF, 0x5B, 0x00);
 
@@ -42,13 +43,23 @@ pub const DIAMOND:               Color = Color::Rgb(0xFF, 0x7B, 0x54);
 pub const HOLD_BORDER:           Color = Color::Rgb(0xFF, 0x77, 0x77);
 
 pub const NEXT_TURN:             Color = Color::Rgb(0xFF, 0x67, 0x01);
+pub const NEXT_TURN_BANK:        Color = Color::Rgb(0xF9, 0x07, 0x16);
 
 pub const GAME_MSG:              Color = Color::Rgb(0xF9, 0x07, 0x16);
 
+pub const BUBBLE:                Color = Color::Rgb(0x2C, 0xD3, 0xE1);
+
 pub const DESK_RESULT_0:         Color = Color::Rgb(0x95, 0xCD, 0x41);
 pub const DESK_RESULT_1:         Color = Color::Rgb(0xF9, 0x07, 0x16);
 pub const DESK_RESULT_2:         Color = Color::Rgb(0xFF, 0xCE, 0x45);
 pub const DESK_RESULT_3:         Color = Color::Rgb(0x35, 0xD0, 0xBA);
+pub const DESK_RESULT_4:         Color = Color::Rgb(0xB3, 0x7F, 0xEB);
+pub const DESK_RESULT_5:         Color = Color::Rgb(0x4D, 0x96, 0xFF);
+
+// one for each seat
+pub const DESK_RESULT: [Color; 6] = [
+    DESK_RESULT_0, DESK_RESULT_1, DESK_RESULT_2, DESK_RESULT_3, DESK_RESULT_4, DESK_RESULT_5,
+];
 
 pub const RESULT_MSG_LOSE:       Color = Color::Rgb(0x00, 0xDF, 0xA2);
 pub const RESULT_MSG_WIN:        Color = Color::Rgb(0xFF, 0x00, 0x60);
@@ -7,7 +7,8 @@ use ratatui::{
 };
 use super::*;
 
-pub fn render_exit_menu<B: Backend>(frame: &mut Frame<B>, button_num: u32, which: u32) {
+// the host of a wait room has four buttons too, with Host Menu for Exit Game
+pub fn render_exit_menu<B: Backend>(frame: &mut Frame<B>, button_num: u32, which: u32, host: bool) {
     assert!(which < button_num);
 
     let menu = rect_cut_center(frame.size(), 50, 30);
@@ -44,7 +45,8 @@ pub fn render_exit_menu<B: Backend>(frame: &mut Frame<B>, button_num: u32, which
         }
         4 => {
             frame.render_widget(get_button("Back", which == 0), buttons[0]);
-            frame.render_widget(get_button("Exit Game", which == 1), buttons[2]);
+            let second = if host { "Host Menu" } else { "Exit Game" };
+            frame.render_widget(get_button(second, which == 1), buttons[2]);
             frame.render_widget(get_button("Exit Room", which == 2), buttons[4]);
             frame.render_widget(get_button("Exit Program", which == 3), buttons[6]);
         }
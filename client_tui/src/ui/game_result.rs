@@ -38,7 +38,6 @@ fn render_desk_result<B: Backend>(
     ];
 
 
-    let colors = vec![DESK_RESULT_0, DESK_RESULT_1, DESK_RESULT_2, DESK_RESULT_3];
     ds.iter().zip(rects).for_each(
         |(chain, ref mut a)| {
             a.height = 8;
@@ -50,42 +49,81 @@ fn render_desk_result<B: Backend>(
                     } else {
                         CardStyle::Horizontal
                     },
-                    false, Some(colors[*who]));
+                    false, Some(DESK_RESULT[*who]));
                 a.y += 2;
             }
         }
     );
 }
 
-fn render_hold_result<B: Backend>(
-    frame: &mut Frame<B>, players: &Vec<(String, Vec<Card>)>
-) {
-    // myself
-    let mut a = Layout::default()
-        .direction(Direction::Vertical)
-        .vertical_margin(1)
-        .constraints(
-            [
-                Constraint::Min(1),
-                Constraint::Length(11),
-            ].as_ref()
-        )
-        .split(frame.size())[1];
-    a = Layout::default()
-        .direction(Direction::Horizontal)
-        .horizontal_margin(1)
-        .constraints(
-            [
-                Constraint::Percentage(10),
-                Constraint::Length(14),
-                Constraint::Min(1),
-            ].as_ref()
+fn render_name<B: Backend>(frame: &mut Frame<B>, name: &String, color: Color, a: Rect) {
+    let name = Text::from(
+        Span::styled(name.clone(),
+        Style::default().bold().fg(NAME))
+    );
+    frame.render_widget(
+        Paragraph::new(name)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .style(Style::default().fg(color))
+            ),
+        a
+    );
+}
+
+fn sum_text(holds: &Vec<Card>) -> Text {
+    Text::from(
+        Span::styled(
+            format!("HOLD: {}    POINTS: {}", holds.len(), hold_sum(holds)),
+            Style::default().fg(HOLD_BORDER)
         )
-        .split(a)[1];
-    render_one_player(frame, players[0].0.clone(), a, Some(DESK_RESULT_0));
-    render_my_holds(frame, &players[0].1, players[0].1.len() == 0);
+    )
+}
+
+// 7 a row, lined up to the outer side of the right or left one
+fn render_side_holds<B: Backend>(
+    frame: &mut Frame<B>, holds: &Vec<Card>, mut a: Rect, right: bool
+) {
+    let shift = |n: usize| if right { 3 * (7 - n as u16) } else { 0 };
+    if holds.len() <= 7 {
+        a = rect_cut_center(a, -8, 100);
+    }
+    a.width = 11;
+    a.height = 8;
+
+    if holds.len() == 0 {
+        a.x += shift(1);
+        render_card(frame, &NULL_CARD, a.clone(),
+            CardStyle::Clear,
+            false, Some(CARD_CLEAR_BOREDER)
+        );
+        return;
+    }
+
+    let org_x = a.x;
+    for row in holds.chunks(7) {
+        a.x = org_x + shift(row.len());
+        for (i, c) in row.iter().enumerate() {
+            render_card(frame, c, a.clone(),
+                if i == row.len() - 1 {
+                    CardStyle::All
+                } else {
+                    CardStyle::Vertical
+                },
+                false, Some(MYCARD_BORDER)
+            );
+            a.x += 3;
+        }
+        a.y += 8;
+    }
+}
 
-    //right
+fn render_side_hold<B: Backend>(
+    frame: &mut Frame<B>, player: &(String, Vec<Card>), color: Color, right: bool
+) {
     let a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -101,6 +139,7 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(frame.size());
+    let side = if right { 3 } else { 1 };
     let name_a = Layout::default()
         .direction(Direction::Horizontal)
         .horizontal_margin(1)
@@ -114,21 +153,8 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(a[1]);
-    let name = Text::from(
-        Span::styled(players[1].0.clone(),
-        Style::default().bold().fg(NAME))
-    );
-    frame.render_widget(
-        Paragraph::new(name)
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(DESK_RESULT_1))
-            ),
-        name_a[3]
-    );
+    render_name(frame, &player.0, color, name_a[side]);
+
     let sum_a = Layout::default()
         .direction(Direction::Horizontal)
         .horizontal_margin(1)
@@ -142,17 +168,12 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(a[3]);
-    let sum_str = Text::from(
-        Span::styled(
-            format!("HOLD: {}    POINTS: {}", players[1].1.len(), hold_sum(&players[1].1)),
-            Style::default().fg(HOLD_BORDER)
-        )
-    );
     frame.render_widget(
-        Paragraph::new(sum_str)
-            .alignment(Alignment::Right),
-        sum_a[3]
+        Paragraph::new(sum_text(&player.1))
+            .alignment(if right { Alignment::Right } else { Alignment::Left }),
+        sum_a[side]
     );
+
     let holds_rect = Layout::default()
         .direction(Direction::Horizontal)
         .horizontal_margin(1)
@@ -166,145 +187,37 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(a[5]);
-    let mut holds = holds_rect[3];
-    if players[1].1.len() == 0 {
-        holds = rect_cut_center(holds, -8, 100);
-        holds.x += 3 * (7 - 1);
-        holds.width = 11;
-        holds.height = 8;
-        render_card(frame, &NULL_CARD, holds.clone(),
-            CardStyle::Clear,
-            false, Some(CARD_CLEAR_BOREDER)
-        );
-    } else if players[1].1.len() <= 7 {
-        holds = rect_cut_center(holds, -8, 100);
-        holds.x += 3 * (7 - players[1].1.len() as u16);
-        holds.width = 11;
-        holds.height = 8;
-        for (i, c) in players[1].1.iter().enumerate() {
-            render_card(frame, c, holds.clone(),
-                if i == players[1].1.len() - 1 {
-                    CardStyle::All
-                } else {
-                    CardStyle::Vertical
-                },
-                false, Some(MYCARD_BORDER)
-            );
-            holds.x += 3;
-        }
-    } else {
-        let org_x = holds.x;
-        holds.width = 11;
-        holds.height = 8;
-        for i in 0..=6 {
-            render_card(frame, &players[1].1[i], holds.clone(),
-                if i == 6 {
-                    CardStyle::All
-                } else {
-                    CardStyle::Vertical
-                },
-                false, Some(MYCARD_BORDER)
-            );
-            holds.x += 3;
-        }
-        holds.y += 8;
-        holds.x = org_x;
-        holds.x += 3 * (14 - players[1].1.len() as u16);
-        for i in 7..players[1].1.len() {
-            render_card(frame, &players[1].1[i], holds.clone(),
-                if i == players[1].1.len() - 1 {
-                    CardStyle::All
-                } else {
-                    CardStyle::Vertical
-                },
-                false, Some(MYCARD_BORDER)
-            );
-            holds.x += 3;
-        }
-    }
+    render_side_holds(frame, &player.1, holds_rect[side], right);
+}
 
-    // left
-    let name = Text::from(
-        Span::styled(players[3].0.clone(),
-        Style::default().bold().fg(NAME))
-    );
-    frame.render_widget(
-        Paragraph::new(name)
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(DESK_RESULT_3))
-            ),
-        name_a[1]
-    );
-    let sum_str = Text::from(
-        Span::styled(
-            format!("HOLD: {}    POINTS: {}", players[3].1.len(), hold_sum(&players[3].1)),
-            Style::default().fg(HOLD_BORDER)
-        )
-    );
-    frame.render_widget(
-        Paragraph::new(sum_str)
-            .alignment(Alignment::Left),
-        sum_a[1]
-    );
-    let mut holds = holds_rect[1];
-    if players[3].1.len() == 0 {
-        holds = rect_cut_center(holds, -8, 100);
-        holds.width = 11;
-        holds.height = 8;
-        render_card(frame, &NULL_CARD, holds.clone(),
-            CardStyle::Clear,
+fn render_half_holds<B: Backend>(frame: &mut Frame<B>, holds: &Vec<Card>, mut a: Rect) {
+    a.width = 11;
+    a.height = 5;
+
+    if holds.len() == 0 {
+        render_card(frame, &NULL_CARD, a.clone(),
+            CardStyle::ClearHalf,
             false, Some(CARD_CLEAR_BOREDER)
         );
-    } else if players[3].1.len() <= 7 {
-        holds = rect_cut_center(holds, -8, 100);
-        holds.width = 11;
-        holds.height = 8;
-        for (i, c) in players[3].1.iter().enumerate() {
-            render_card(frame, c, holds.clone(),
-                if i == players[3].1.len() - 1 {
-                    CardStyle::All
-                } else {
-                    CardStyle::Vertical
-                },
-                false, Some(MYCARD_BORDER)
-            );
-            holds.x += 3;
-        }
     } else {
-        let org_x = holds.x;
-        holds.width = 11;
-        holds.height = 8;
-        for i in 0..=6 {
-            render_card(frame, &players[3].1[i], holds.clone(),
-                if i == 6 {
-                    CardStyle::All
-                } else {
-                    CardStyle::Vertical
-                },
-                false, Some(MYCARD_BORDER)
-            );
-            holds.x += 3;
-        }
-        holds.y += 8;
-        holds.x = org_x;
-        for i in 7..players[3].1.len() {
-            render_card(frame, &players[3].1[i], holds.clone(),
-                if i == players[3].1.len() - 1 {
-                    CardStyle::All
+        for (i, c) in holds.iter().enumerate() {
+            render_card(frame, c, a.clone(),
+                if i == holds.len() - 1 {
+                    CardStyle::Half
                 } else {
                     CardStyle::Vertical
                 },
-                false, Some(MYCARD_BORDER)
+                false,
+                Some(MYCARD_BORDER)
             );
-            holds.x += 3;
+            a.x += 3;
         }
     }
+}
 
-    // top
+fn render_top_hold<B: Backend>(
+    frame: &mut Frame<B>, player: &(String, Vec<Card>), color: Color
+) {
     let mut a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -328,7 +241,7 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(a);
-    let mut holds_rect = rects[2];
+    let holds_rect = rects[2];
     let rects = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -348,57 +261,93 @@ fn render_hold_result<B: Backend>(
             ].as_ref()
         )
         .split(rects[0])[1];
-    let name = Text::from(
-        Span::styled(players[2].0.clone(),
-        Style::default().bold().fg(NAME))
-    );
+    render_name(frame, &player.0, color, name_a);
     frame.render_widget(
-        Paragraph::new(name)
-            .alignment(Alignment::Center)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Rounded)
-                    .style(Style::default().fg(DESK_RESULT_2))
-            ),
-        name_a
+        Paragraph::new(sum_text(&player.1))
+            .alignment(Alignment::Right),
+        rects[2]
     );
-    let sum_str = Text::from(
-        Span::styled(
-            format!("HOLD: {}    POINTS: {}", players[2].1.len(), hold_sum(&players[2].1)),
-            Style::default().fg(HOLD_BORDER)
+    render_half_holds(frame, &player.1, holds_rect);
+}
+
+// name and sum in a row, holds under them
+fn render_compact_top_hold<B: Backend>(
+    frame: &mut Frame<B>, player: &(String, Vec<Card>), color: Color, pos: SeatPos
+) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .vertical_margin(1)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Min(1),
+            ].as_ref()
         )
-    );
+        .split(frame.size());
+    let cols = |row: Rect| Layout::default()
+        .direction(Direction::Horizontal)
+        .horizontal_margin(1)
+        .constraints(
+            [
+                Constraint::Percentage(top_slot_x(pos)),
+                Constraint::Length(17),
+                Constraint::Length(2),
+                Constraint::Length(21),
+                Constraint::Min(1),
+            ].as_ref()
+        )
+        .split(row);
+    let name_row = cols(rows[0]);
+    render_name(frame, &player.0, color, name_row[1]);
     frame.render_widget(
-        Paragraph::new(sum_str)
-            .alignment(Alignment::Right),
-        rects[2]
+        Paragraph::new(sum_text(&player.1))
+            .alignment(Alignment::Left),
+        rect_cut_center(name_row[3], -1, 100)
     );
-    holds_rect.width = 11;
-    holds_rect.height = 5;
+    render_half_holds(frame, &player.1, cols(rows[1])[1]);
+}
 
-    if players[2].1.len() == 0 {
-        render_card(frame, &NULL_CARD, holds_rect.clone(),
-            CardStyle::ClearHalf,
-            false, Some(CARD_CLEAR_BOREDER)
-        );
-    } else {
-        for (i, c) in players[2].1.iter().enumerate() {
-            render_card(frame, c, holds_rect.clone(),
-                if i == players[2].1.len() - 1 {
-                    CardStyle::Half
-                } else {
-                    CardStyle::Vertical
-                },
-                false,
-                Some(MYCARD_BORDER)
-            );
-            holds_rect.x += 3;
+fn render_hold_result<B: Backend>(
+    frame: &mut Frame<B>, players: &Vec<(String, Vec<Card>)>
+) {
+    let positions = seat_positions(players.len());
+    for (i, (player, pos)) in players.iter().zip(positions).enumerate() {
+        match pos {
+            SeatPos::Bottom => {
+                let mut a = Layout::default()
+                    .direction(Direction::Vertical)
+                    .vertical_margin(1)
+                    .constraints(
+                        [
+                            Constraint::Min(1),
+                            Constraint::Length(11),
+                        ].as_ref()
+                    )
+                    .split(frame.size())[1];
+                a = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .horizontal_margin(1)
+                    .constraints(
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Length(14),
+                            Constraint::Min(1),
+                        ].as_ref()
+                    )
+                    .split(a)[1];
+                render_one_player(frame, player.0.clone(), a, Some(DESK_RESULT[i]));
+                render_my_holds(frame, &player.1, player.1.len() == 0);
+            }
+            SeatPos::Right => render_side_hold(frame, player, DESK_RESULT[i], true),
+            SeatPos::Left => render_side_hold(frame, player, DESK_RESULT[i], false),
+            SeatPos::Top => render_top_hold(frame, player, DESK_RESULT[i]),
+            _ => render_compact_top_hold(frame, player, DESK_RESULT[i], *pos),
         }
     }
 }
 
-fn render_result_msg<B: Backend>(frame: &mut Frame<B>, msg: String, msg_color: Color){
+pub(super) fn render_result_msg<B: Backend>(frame: &mut Frame<B>, msg: String, msg_color: Color){
     let mut a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -424,7 +373,7 @@ fn render_result_msg<B: Backend>(frame: &mut Frame<B>, msg: String, msg_color: C
     );
 }
 
-fn render_result_button<B: Backend>(frame: &mut Frame<B>){
+pub(super) fn render_result_button<B: Backend>(frame: &mut Frame<B>){
     let mut a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -466,7 +415,7 @@ fn hold_sum(holds: &Vec<Card>) -> u32 {
     ).sum()
 }
 
-fn name_shorten(name: &String) -> String {
+pub(super) fn name_shorten(name: &String) -> String {
     if name.len() > 8 {
         let mut ret = String::from(&name[0..6]);
         ret.push_str("..");
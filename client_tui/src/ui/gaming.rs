@@ -62,10 +62,10 @@ fn render_my_cards<B: Backend>(frame: &mut Frame<B>, cards: &Vec<Card>,
     }
 }
 
-fn render_next<B: Backend>(frame: &mut Frame<B>, next: usize) {
-    let a = match next {
+fn render_next<B: Backend>(frame: &mut Frame<B>, pos: SeatPos, clock: Option<TurnClock>) {
+    let a = match pos {
         // myself
-        0 => {
+        SeatPos::Bottom => {
             let a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -94,7 +94,7 @@ fn render_next<B: Backend>(frame: &mut Frame<B>, next: usize) {
                 .split(a)[3]
         }
         // right
-        1 => {
+        SeatPos::Right => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -121,7 +121,7 @@ fn render_next<B: Backend>(frame: &mut Frame<B>, next: usize) {
                 .split(a)[1]
                 }
         // top
-        2 => {
+        SeatPos::Top => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -148,7 +148,7 @@ fn render_next<B: Backend>(frame: &mut Frame<B>, next: usize) {
                 .split(a)[3]
         }
         // left
-        3 => {
+        SeatPos::Left => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -174,28 +174,37 @@ fn render_next<B: Backend>(frame: &mut Frame<B>, next: usize) {
                 )
                 .split(a)[2]
         }
-        _ => panic!("next is not in 0..=3 !"),
+        _ => rect_cut_center(top_slot(frame.size(), pos)[2], -1, 100),
+    };
+
+    // shorter words to leave room for the seconds
+    let (text, color) = match clock {
+        None => (if pos == SeatPos::Bottom {
+            "Your Turn!"
+        } else {
+            "Waiting..."
+        }.to_string(), NEXT_TURN),
+        Some(tc) => (format!("{} {}s", if pos == SeatPos::Bottom {
+            "Your Turn"
+        } else {
+            "Waiting"
+        }, tc.secs), if tc.in_bank() { NEXT_TURN_BANK } else { NEXT_TURN }),
     };
 
     frame.render_widget(
-        Paragraph::new(
-            if next == 0 {
-                "Your Turn!"
-            } else {
-                "Waiting..."
-            }
-        ).alignment(Alignment::Center)
-        .style(Style::default().fg(NEXT_TURN).add_modifier(Modifier::BOLD)),
+        Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(color).add_modifier(Modifier::BOLD)),
         a
     );
 }
 
-fn render_last<B: Backend>(frame: &mut Frame<B>, last: Option<Card>, who: usize) {
-    let a = match who {
+pub(super) fn render_last<B: Backend>(frame: &mut Frame<B>, last: Option<Card>, pos: SeatPos) {
+    let a = match pos {
         // myself
-        0 => {return}
+        SeatPos::Bottom => {return}
         // right
-        1 => {
+        SeatPos::Right => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -222,7 +231,7 @@ fn render_last<B: Backend>(frame: &mut Frame<B>, last: Option<Card>, who: usize)
                 .split(a)[1]
                 }
         // top
-        2 => {
+        SeatPos::Top => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -249,7 +258,7 @@ fn render_last<B: Backend>(frame: &mut Frame<B>, last: Option<Card>, who: usize)
                 .split(a)[3]
         }
         // left
-        3 => {
+        SeatPos::Left => {
             let mut a = Layout::default()
                 .direction(Direction::Vertical)
                 .vertical_margin(1)
@@ -275,7 +284,11 @@ fn render_last<B: Backend>(frame: &mut Frame<B>, last: Option<Card>, who: usize)
                 )
                 .split(a)[2]
         }
-        _ => panic!("next is not in 0..=3 !"),
+        _ => {
+            let mut a = rect_cut_center(top_slot(frame.size(), pos)[2], -8, 100);
+            a.width = 11;
+            a
+        }
     };
 
     if let Some(c) = last {
@@ -375,7 +388,7 @@ pub fn render_my_holds<B: Backend>(frame: &mut Frame<B>, holds: &Vec<Card>, clea
     }
 }
 
-fn render_game_button<B: Backend>(frame: &mut Frame<B>, button: u32) {
+fn render_game_button<B: Backend>(frame: &mut Frame<B>, button: u32, can_hold: bool) {
     let mut a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -412,10 +425,14 @@ fn render_game_button<B: Backend>(frame: &mut Frame<B>, button: u32) {
         .split(a);
 
     frame.render_widget(get_button("Play", button == 0), buttons[0]);
-    frame.render_widget(get_button("Hold", button == 1), buttons[2]);
+    if can_hold {
+        frame.render_widget(get_button("Hold", button == 1), buttons[2]);
+    } else {
+        frame.render_widget(get_disabled_button("Hold"), buttons[2]);
+    }
 }
 
-fn render_msg<B: Backend>(frame: &mut Frame<B>, msg: String) {
+pub(super) fn render_msg<B: Backend>(frame: &mut Frame<B>, msg: String) {
     let mut a = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -456,9 +473,11 @@ pub fn ui_gaming<B: Backend>(
     my_cards: Vec<Card>, my_holds: Vec<Card>, hints: Vec<bool>,
     chains_small: Vec<Vec<(Card, CardStyleOnDesk)>>,
     chains_big: Vec<Vec<(Card, CardStyleOnDesk)>>,
-    button: u32, has_done: bool, msg: Option<String>
+    button: u32, can_hold: bool, has_done: bool, msg: Option<String>,
+    clock: Option<TurnClock>
 ) {
-    render_players(frame, &names, vec![false; 4], Some(hold_nums));
+    let seats = names.len();
+    render_players(frame, &names, vec![false; seats], Some(hold_nums));
 
     render_game_info(frame, roomid.clone());
 
@@ -474,12 +493,12 @@ pub fn ui_gaming<B: Backend>(
     }
 
     if !has_done {
-        render_next(frame, next);
+        render_next(frame, seat_pos(seats, next), clock.filter(|tc| tc.player == next));
     }
 
+    // not always the one before next, who has played all cards is skipped
     if let Some((who, opc)) = last {
-        assert_eq!((next+3)%4, who);
-        render_last(frame, opc, who);
+        render_last(frame, opc, seat_pos(seats, who));
     }
 
     render_my_holds(frame, &my_holds, false);
@@ -489,6 +508,8 @@ pub fn ui_gaming<B: Backend>(
             button
         } else {
             10 // anything not 0 or 1
-        }
+        },
+        // not greyed out while waiting, all of it is dimmed then
+        next != 0 || can_hold
     );
 }
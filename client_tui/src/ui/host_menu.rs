@@ -0,0 +1,46 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    text::*,
+    widgets::*,
+    Frame
+};
+use super::*;
+
+pub fn render_host_menu<B: Backend>(frame: &mut Frame<B>, menu: HostMenu) {
+    let height = HOST_MENU_ITEMS as i16 * 3 + 4;
+    let a = rect_cut_center(frame.size(), -height, -36);
+    frame.render_widget(Clear, a);
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Host Menu")
+            .style(Style::default().fg(BORDER_LIGHT)),
+        a
+    );
+
+    let mut constraints = vec![Constraint::Length(3); HOST_MENU_ITEMS];
+    constraints.push(Constraint::Length(1));
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(constraints)
+        .split(a);
+
+    for (i, item) in menu.items.iter().enumerate() {
+        // the player is picked by the arrows on the last two
+        let item = match i {
+            3 | 4 => format!("← {} →", item),
+            _ => item.clone(),
+        };
+        frame.render_widget(get_button(&item, menu.which == i), rows[i]);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(menu.msg, Style::default().fg(GAME_MSG)))
+            .alignment(Alignment::Center),
+        rows[HOST_MENU_ITEMS]
+    );
+}
@@ -7,7 +7,7 @@ use ratatui::{
 };
 use super::*;
 
-pub fn ui_join_room<B: Backend>(frame: &mut Frame<B>, input: Input, msg: String) {
+pub fn ui_join_room<B: Backend>(frame: &mut Frame<B>, input: Input, masked: bool, msg: String) {
     let prompt = render_prompt_window(frame);
 
     let chunks = Layout::default()
@@ -30,6 +30,7 @@ pub fn ui_join_room<B: Backend>(frame: &mut Frame<B>, input: Input, msg: String)
         chunks[0],
     );
 
+    let (input, title) = if masked { (input.masked(), "Password") } else { (input, "Room ID") };
     let input_rect = rect_cut_center(chunks[1], -3, 60);
     let input_width = input_rect.width.max(3) - 3;
     let scroll = input.visual_scroll(input_width as usize);
@@ -37,7 +38,7 @@ pub fn ui_join_room<B: Backend>(frame: &mut Frame<B>, input: Input, msg: String)
         Paragraph::new(input.value())
             .style(Style::default().fg(INPUT_BORDER))
             .scroll((0, scroll as u16))
-            .block(Block::default().borders(Borders::ALL).title("Room ID")),
+            .block(Block::default().borders(Borders::ALL).title(title)),
         input_rect,
     );
     frame.set_cursor(
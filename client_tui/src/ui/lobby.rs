@@ -0,0 +1,65 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    widgets::*,
+    text::*,
+    Frame
+};
+use super::*;
+
+pub fn ui_lobby<B: Backend>(frame: &mut Frame<B>, rooms: Vec<RoomSummary>,
+                        choose: usize, msg: String
+) {
+    let prompt = render_prompt_window(frame);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(3)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(3),
+            ]
+            .as_ref(),
+        )
+        .split(prompt);
+
+    frame.render_widget(
+        Paragraph::new(msg.as_str())
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(TEXT_NORMAL).bold()),
+        chunks[0],
+    );
+
+    // the last row is for entering room ID by hand
+    let rows: Vec<Line> = rooms.iter().map(lobby_row)
+        .chain(std::iter::once("Enter Room ID......".to_string()))
+        .enumerate()
+        .map(|(i, r)| Line::styled(r,
+            if i == choose {
+                Style::default().add_modifier(Modifier::BOLD).fg(BUTTON)
+            } else {
+                Style::default().add_modifier(Modifier::DIM).fg(BUTTON_DIM)
+            }
+        ))
+        .collect();
+
+    let list_rect = rect_cut_center(chunks[1], 100, 80);
+    // keep the chosen row visible
+    let height = list_rect.height.max(2) - 2;
+    let scroll = (choose as u16 + 1).max(height) - height;
+    frame.render_widget(
+        Paragraph::new(Text::from(rows))
+            .alignment(Alignment::Center)
+            .scroll((scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Rooms")
+                    .style(Style::default().fg(INPUT_BORDER_BLOCK))
+            ),
+        list_rect,
+    );
+}
@@ -1,6 +1,7 @@
 pub mod home_page;
 pub mod ask_name;
 pub mod join_room;
+pub mod lobby;
 pub mod card;
 pub mod desk;
 pub mod gaming;
@@ -10,11 +11,17 @@ pub mod game_result;
 pub mod blocked;
 pub mod exit_menu;
 pub mod new_room;
+pub mod spectate;
+pub mod replay;
+pub mod scoreboard;
+pub mod chat;
+pub mod host_menu;
 pub mod color;
 
 pub use home_page::*;
 pub use ask_name::*;
 pub use join_room::*;
+pub use lobby::*;
 pub use desk::*;
 pub use gaming::*;
 pub use wait::*;
@@ -22,6 +29,12 @@ pub use game_result::*;
 pub use blocked::*;
 pub use exit_menu::*;
 pub use new_room::*;
+pub use spectate::*;
+pub use replay::*;
+pub use scoreboard::*;
+pub use chat::*;
+pub use host_menu::*;
+use players::{render_bubbles, render_reaction_hint};
 use crate::*;
 pub use color::*;
 use ratatui::{
@@ -32,8 +45,7 @@ use ratatui::{
     Frame
 };
 
-pub fn render<B: Backend>(frame: &mut Frame<B>, cs: ClientState) {
-    // outer border
+fn render_border<B: Backend>(frame: &mut Frame<B>) {
     frame.render_widget(
         Block::default()
             .borders(Borders::ALL)
@@ -41,25 +53,32 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, cs: ClientState) {
             .style(Style::default().fg(BORDER_DARK)),
         frame.size(),
     );
+}
+
+pub fn render<B: Backend>(frame: &mut Frame<B>, cs: ClientState) {
+    render_border(frame);
 
     if cs.exitmenu.0 {
-        render_exit_menu(frame, get_button_num(&cs), cs.exitmenu.1);
+        render_exit_menu(frame, get_button_num(&cs), cs.exitmenu.1, cs.is_host);
     } else {
+        let gaming = matches!(cs.fsm, ClientStateMachine::Gaming {..});
         match cs.fsm {
             ClientStateMachine::GetServer {connecting, input, msg}
                 => ui_home_page(frame, input, msg, connecting),
             ClientStateMachine::AskName {input, msg, button, is_input, ..}
                 => ui_ask_name(frame, input, msg, button, is_input),
-            ClientStateMachine::NewRoom { input, msg, ..}
-                => ui_new_room(frame, input, msg),
-            ClientStateMachine::JoinRoom {input, msg, ..}
-                => ui_join_room(frame, input, msg),
+            ClientStateMachine::NewRoom { input, masked, msg, ..}
+                => ui_new_room(frame, input, masked, msg),
+            ClientStateMachine::Lobby {rooms, choose, msg, ..}
+                => ui_lobby(frame, rooms, choose, msg),
+            ClientStateMachine::JoinRoom {input, masked, msg, ..}
+                => ui_join_room(frame, input, masked, msg),
             ClientStateMachine::WaitPlayer {players, msg, roomid, ..}
                 => ui_wait_player(frame, players, msg, roomid),
-            ClientStateMachine::WaitReady {players, msg, roomid, ..}
-                => ui_wait_ready(frame, players, msg, roomid),
+            ClientStateMachine::WaitReady {players, msg, roomid, rules, profiles}
+                => ui_wait_ready(frame, players, msg, roomid, rules, profiles),
             ClientStateMachine::Gaming {
-                choose, mut game, roomid, button, msg, ..
+                choose, mut game, roomid, button, msg, clock, ..
             } => {
                 let names = game.get_player_names();
                 let hold_nums = game.get_hold_nums();
@@ -68,63 +87,108 @@ pub fn render<B: Backend>(frame: &mut Frame<B>, cs: ClientState) {
                 let my_cards = game.get_my_cards();
                 let my_holds = game.get_my_holds();
                 let hints = game.get_my_hint();
+                let can_hold = game.can_hold(0);
                 let has_done = game.has_done();
                 let thisround = game.get_thisround();
                 let thisround_my = game.get_thisround_my();
-                let mut chains_small = vec![];
-                let mut chains_big = vec![];
-                game.export_desk().into_iter().for_each(
-                    |l| {
-                        let mut small = vec![];
-                        let mut big = vec![];
-                        for c in l {
-                            if c.num <= 7 {
-                                small.push(c);
-                            } else {
-                                big.push(c);
-                            }
-                        }
-                        big.reverse();
-                        for (v, chain) in [(small, &mut chains_small), (big, &mut chains_big)] {
-                            chain.push(if v.len() == 0 {
-                                Vec::new()
-                            } else if !thisround.contains(&v[0]) {
-                                vec![(v[0].clone(), CardStyleOnDesk::Normal)]
-                            } else {
-                                let mut viter = v.into_iter();
-                                let mut ret = vec![];
-                                while let Some(c) = viter.next() {
-                                    if !thisround.contains(&c) {
-                                        break;
-                                    }
-                                    ret.push(
-                                        (c.clone(),
-                                         if thisround_my.is_some()
-                                            && thisround_my.as_ref().unwrap().clone() == c {
-                                            CardStyleOnDesk::ThisRoundMy
-                                         } else {
-                                            CardStyleOnDesk::ThisRound
-                                        })
-                                    );
-                                }
-                                ret
-                            });
-                        }
-
-                    }
-                );
+                let (chains_small, chains_big) =
+                    split_desk_chains(game.export_desk(), &thisround, &thisround_my);
 
                 ui_gaming(frame, names, hold_nums, next, roomid, choose, last,
                     my_cards, my_holds, hints, chains_small,
-                    chains_big, button, has_done, msg
+                    chains_big, button, can_hold, has_done, msg, clock
                 );
             }
+            ClientStateMachine::GameResult {players, roomid, scoreboard: Some(mi), ..}
+                => ui_scoreboard(frame, players.into_iter().map(|p| p.0).collect(), roomid, mi),
             ClientStateMachine::GameResult {ds, players, roomid, winner, winner_state, ..}
                 => ui_game_result(frame, ds, players, roomid, winner, winner_state),
+            ClientStateMachine::Spectating {game: Some(game), roomid, msg, clock} => {
+                let names = game.get_player_names();
+                let hold_nums = game.get_hold_nums();
+                let next = game.get_next();
+                let last = game.get_last();
+                let has_done = game.has_done();
+                let thisround = game.get_thisround();
+                let (chains_small, chains_big) =
+                    split_desk_chains(game.export_desk(), &thisround, &None);
+
+                ui_spectate(frame, names, hold_nums, next, roomid, last,
+                    chains_small, chains_big, has_done, msg, clock
+                );
+            }
+            ClientStateMachine::Spectating {game: None, roomid, msg, ..}
+                => ui_spectate_waiting(frame, roomid, msg),
+        }
+        render_bubbles(frame, &cs.bubbles);
+        if gaming {
+            render_reaction_hint(frame);
+        }
+        // over the state, the pane may cover part of it
+        if let Some(chat) = cs.chat {
+            render_chat(frame, chat);
+        }
+        if let Some(menu) = cs.host_menu {
+            render_host_menu(frame, menu);
         }
     }
 }
 
+pub fn render_replay<B: Backend>(frame: &mut Frame<B>, r: &crate::replay::Replay) {
+    render_border(frame);
+    ui_replay(frame, r);
+}
+
+// split every chain at 7 for rendering, highlighting cards played this round
+fn split_desk_chains(
+    desk: Vec<Vec<Card>>, thisround: &Vec<Card>, thisround_my: &Option<Card>
+) -> (Vec<Vec<(Card, CardStyleOnDesk)>>, Vec<Vec<(Card, CardStyleOnDesk)>>) {
+    let mut chains_small = vec![];
+    let mut chains_big = vec![];
+    desk.into_iter().for_each(
+        |l| {
+            let mut small = vec![];
+            let mut big = vec![];
+            for c in l {
+                if c.num <= 7 {
+                    small.push(c);
+                } else {
+                    big.push(c);
+                }
+            }
+            big.reverse();
+            for (v, chain) in [(small, &mut chains_small), (big, &mut chains_big)] {
+                chain.push(if v.len() == 0 {
+                    Vec::new()
+                } else if !thisround.contains(&v[0]) {
+                    vec![(v[0].clone(), CardStyleOnDesk::Normal)]
+                } else {
+                    let mut viter = v.into_iter();
+                    let mut ret = vec![];
+                    while let Some(c) = viter.next() {
+                        if !thisround.contains(&c) {
+                            break;
+                        }
+                        ret.push(
+                            (c.clone(),
+                             if thisround_my.is_some()
+                                && thisround_my.as_ref().unwrap().clone() == c {
+                                CardStyleOnDesk::ThisRoundMy
+                             } else {
+                                CardStyleOnDesk::ThisRound
+                            })
+                        );
+                    }
+                    ret
+                });
+            }
+
+        }
+    );
+
+    (chains_small, chains_big)
+}
+
 // cut out the center of `org` with v and h
 // v and h: negative for fixed value, positive for percentage
 fn rect_cut_center(mut org: Rect, v: i16, h: i16) -> Rect {
@@ -226,6 +290,16 @@ fn render_prompt_window<B: Backend>(frame: &mut Frame<B>) -> Rect {
     prompt
 }
 
+// greyed out whether selected or not
+fn get_disabled_button(cmd: &str) -> Paragraph {
+    get_button(cmd, false)
+        .style(
+            Style::default()
+                .add_modifier(Modifier::DIM)
+                .fg(BUTTON_DISABLED)
+        )
+}
+
 fn get_button(cmd: &str, selected: bool) -> Paragraph {
     Paragraph::new(cmd)
         .alignment(Alignment::Center)
@@ -20,259 +20,291 @@ fn render_ready<B: Backend>(frame: &mut Frame<B>, a: Rect) {
         a
     )
 }
-pub fn render_players<B: Backend>(frame: &mut Frame<B>, names: &Vec<String>,
-    ready: Vec<bool>, holds: Option<Vec<u32>>
-) {
-    // myself
-    let mut a = Layout::default()
-        .direction(Direction::Vertical)
-        .vertical_margin(1)
-        .constraints(
-            [
-                Constraint::Min(1),
-                Constraint::Length(11),
-            ].as_ref()
-        )
-        .split(frame.size())[1];
-    a = Layout::default()
-        .direction(Direction::Horizontal)
-        .horizontal_margin(1)
-        .constraints(
-            [
-                Constraint::Percentage(10),
-                Constraint::Length(14),
-                Constraint::Min(1),
-            ].as_ref()
-        )
-        .split(a)[1];
-    render_one_player(frame, names[0].clone(), a, None);
-
-    // right one
-    let mut a = Layout::default()
-        .direction(Direction::Vertical)
-        .vertical_margin(1)
-        .constraints(
-            [
-                Constraint::Percentage(30),
-                Constraint::Length(11),
-                Constraint::Min(1)
-            ].as_ref()
-        )
-        .split(frame.size())[1];
-    a = Layout::default()
-        .direction(Direction::Horizontal)
-        .horizontal_margin(1)
-        .constraints(
-            [
-                Constraint::Min(1),
-                Constraint::Length(14),
-            ].as_ref()
-        )
-        .split(a)[1];
-    render_one_player(frame, names[1].clone(), a, None);
+// percentage from the left where a compact top seat starts
+pub(super) fn top_slot_x(pos: SeatPos) -> u16 {
+    match pos {
+        SeatPos::TopLeft => 15,
+        SeatPos::TopMid => 42,
+        SeatPos::TopRight => 69,
+        _ => panic!("{:?} is not a compact top seat!", pos),
+    }
+}
 
-    // top one
-    let mut a = Layout::default()
+// a compact seat of the top row: [name, right under the name, next to the name]
+pub(super) fn top_slot(area: Rect, pos: SeatPos) -> [Rect; 3] {
+    let x = top_slot_x(pos);
+    let rows = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
         .constraints(
             [
                 Constraint::Length(11),
+                Constraint::Length(1),
                 Constraint::Min(1),
             ].as_ref()
         )
-        .split(frame.size())[0];
-    a = Layout::default()
+        .split(area);
+    let cols = |row: Rect| Layout::default()
         .direction(Direction::Horizontal)
         .horizontal_margin(1)
         .constraints(
             [
-                Constraint::Percentage(30),
+                Constraint::Percentage(x),
                 Constraint::Length(14),
+                Constraint::Length(2),
+                Constraint::Length(23),
                 Constraint::Min(1),
             ].as_ref()
         )
-        .split(a)[1];
-    render_one_player(frame, names[2].clone(), a, None);
+        .split(row);
 
-    // left one
-    let mut a = Layout::default()
-        .direction(Direction::Vertical)
-        .vertical_margin(1)
-        .constraints(
-            [
-                Constraint::Percentage(30),
-                Constraint::Length(11),
-                Constraint::Min(1)
-            ].as_ref()
-        )
-        .split(frame.size())[1];
-    a = Layout::default()
-        .direction(Direction::Horizontal)
-        .horizontal_margin(1)
-        .constraints(
-            [
-                Constraint::Length(14),
-                Constraint::Min(1),
-            ].as_ref()
-        )
-        .split(a)[0];
-    render_one_player(frame, names[3].clone(), a, None);
+    [cols(rows[0])[1], cols(rows[1])[1], cols(rows[0])[3]]
+}
 
-    // ready
-    // myself
-    if ready[0] {
-        let mut a = Layout::default()
-            .direction(Direction::Vertical)
-            .vertical_margin(1)
-            .constraints(
-                [
-                    Constraint::Min(1),
-                    Constraint::Length(11),
-                ].as_ref()
-            )
-            .split(frame.size())[1];
-        a = rect_cut_center(a, -3, 20);
-        render_ready(frame, a);
+fn player_rect(area: Rect, pos: SeatPos) -> Rect {
+    match pos {
+        SeatPos::Bottom => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(11),
+                    ].as_ref()
+                )
+                .split(area)[1];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(10),
+                        Constraint::Length(14),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        SeatPos::Right => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(11),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(area)[1];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(14),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        SeatPos::Top => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(11),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(area)[0];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(14),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        SeatPos::Left => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(11),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(area)[1];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(14),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[0]
+        }
+        _ => top_slot(area, pos)[0],
     }
+}
 
-    // right one
-    if ready[1] {
-        let mut a = rect_cut_center(frame.size(), -3, 100);
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Min(1),
-                    Constraint::Percentage(10),
-                    Constraint::Length(20),
-                ].as_ref()
-            )
-            .split(a)[1];
-        render_ready(frame, a);
+fn ready_rect(area: Rect, pos: SeatPos) -> Rect {
+    match pos {
+        SeatPos::Bottom => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(11),
+                    ].as_ref()
+                )
+                .split(area)[1];
+            rect_cut_center(a, -3, 20)
+        }
+        SeatPos::Right => {
+            let a = rect_cut_center(area, -3, 100);
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Percentage(10),
+                        Constraint::Length(20),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        SeatPos::Left => {
+            let a = rect_cut_center(area, -3, 100);
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(20),
+                        Constraint::Percentage(10),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        _ => hold_rect(area, pos),
     }
+}
 
-    // top one
-    if ready[2] {
-        let mut a = Layout::default()
-            .direction(Direction::Vertical)
-            .vertical_margin(1)
-            .constraints(
-                [
-                    Constraint::Length(13),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(frame.size())[0];
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Percentage(40),
-                    Constraint::Percentage(10),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(a)[1];
-        a = rect_cut_center(a, -3, 100);
-        render_ready(frame, a);
+// where the hold num goes, none for the bottom one
+fn hold_rect(area: Rect, pos: SeatPos) -> Rect {
+    match pos {
+        SeatPos::Right => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(13),
+                        Constraint::Length(3),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(area)[2];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(16),
+                    ].as_ref()
+                )
+                .split(a)[1]
+        }
+        SeatPos::Top => {
+            let mut a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(13),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(area)[0];
+            a = Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(40),
+                        Constraint::Percentage(10),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[1];
+            rect_cut_center(a, -3, 100)
+        }
+        SeatPos::Left => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(13),
+                        Constraint::Length(3),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(area)[2];
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(12),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[0]
+        }
+        SeatPos::Bottom => panic!("No hold num for the bottom one!"),
+        _ => top_slot(area, pos)[1],
     }
+}
 
-    // left one
-    if ready[3] {
-        let mut a = rect_cut_center(frame.size(), -3, 100);
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Length(20),
-                    Constraint::Percentage(10),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(a)[1];
-        render_ready(frame, a);
-    }
+pub fn render_players<B: Backend>(frame: &mut Frame<B>, names: &Vec<String>,
+    ready: Vec<bool>, holds: Option<Vec<u32>>
+) {
+    let positions = seat_positions(names.len());
 
-    // hold num
-    if let Some(holds) = holds {
-        // right
-        let mut a = Layout::default()
-            .direction(Direction::Vertical)
-            .vertical_margin(1)
-            .constraints(
-                [
-                    Constraint::Percentage(30),
-                    Constraint::Length(13),
-                    Constraint::Length(3),
-                    Constraint::Min(1)
-                ].as_ref()
-            )
-            .split(frame.size())[2];
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Min(1),
-                    Constraint::Length(16),
-                ].as_ref()
-            )
-            .split(a)[1];
-        render_hold_num(frame, a, holds[1]);
+    for (name, pos) in names.iter().zip(positions) {
+        render_one_player(frame, name.clone(), player_rect(frame.size(), *pos), None);
+    }
 
-        // top
-        let mut a = Layout::default()
-            .direction(Direction::Vertical)
-            .vertical_margin(1)
-            .constraints(
-                [
-                    Constraint::Length(13),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(frame.size())[0];
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Percentage(40),
-                    Constraint::Percentage(10),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(a)[1];
-        a = rect_cut_center(a, -3, 100);
-        render_hold_num(frame, a, holds[2]);
+    for (r, pos) in ready.iter().zip(positions) {
+        if *r {
+            render_ready(frame, ready_rect(frame.size(), *pos));
+        }
+    }
 
-        // left
-        let mut a = Layout::default()
-            .direction(Direction::Vertical)
-            .vertical_margin(1)
-            .constraints(
-                [
-                    Constraint::Percentage(30),
-                    Constraint::Length(13),
-                    Constraint::Length(3),
-                    Constraint::Min(1)
-                ].as_ref()
-            )
-            .split(frame.size())[2];
-        a = Layout::default()
-            .direction(Direction::Horizontal)
-            .horizontal_margin(1)
-            .constraints(
-                [
-                    Constraint::Length(12),
-                    Constraint::Min(1),
-                ].as_ref()
-            )
-            .split(a)[0];
-        render_hold_num(frame, a, holds[3]);
+    if let Some(holds) = holds {
+        for (num, pos) in holds.iter().zip(positions).skip(1) {
+            render_hold_num(frame, hold_rect(frame.size(), *pos), *num);
+        }
     }
 }
 
@@ -337,3 +369,60 @@ fn render_hold_num<B: Backend>(frame: &mut Frame<B>, a: Rect, num: u32) {
     )
 }
 
+
+// right by the card, toward the middle of the table
+fn bubble_rect(area: Rect, pos: SeatPos, width: u16) -> Rect {
+    let p = player_rect(area, pos);
+    let (x, y) = match pos {
+        SeatPos::Bottom => (p.x, p.y.saturating_sub(3)),
+        SeatPos::Right => (p.x.saturating_sub(width), p.y),
+        SeatPos::Left => (p.x + p.width, p.y),
+        _ => (p.x, p.y + p.height),
+    };
+    Rect { x, y, width, height: 3 }.intersection(area)
+}
+
+pub fn render_bubbles<B: Backend>(frame: &mut Frame<B>, bubbles: &[Option<Reaction>]) {
+    if bubbles.is_empty() {
+        return;
+    }
+    let positions = seat_positions(bubbles.len());
+
+    for (b, pos) in bubbles.iter().zip(positions) {
+        let Some(r) = b else {
+            continue;
+        };
+        let a = bubble_rect(frame.size(), *pos, r.text().len() as u16 + 4);
+        frame.render_widget(Clear, a);
+        frame.render_widget(
+            Paragraph::new(r.text())
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(BUBBLE).add_modifier(Modifier::BOLD))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .style(Style::default().fg(BORDER_LIGHT))
+                ),
+            a
+        );
+    }
+}
+
+// on the bottom border, across from the chat hint
+pub fn render_reaction_hint<B: Backend>(frame: &mut Frame<B>) {
+    let keys: Vec<String> = Reaction::ALL.iter().enumerate().map(
+        |(i, r)| format!("{}: {}", i + 1, r.text())
+    ).collect();
+    let text = format!(" {} ", keys.join("  "));
+    let size = frame.size();
+    frame.render_widget(
+        Paragraph::new(text.clone()).style(Style::default().fg(NORMAL_DIM)),
+        Rect {
+            x: 2.min(size.width),
+            y: size.height.saturating_sub(1),
+            width: (text.len() as u16).min(size.width.saturating_sub(2)),
+            height: 1,
+        }
+    );
+}
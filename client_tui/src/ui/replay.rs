@@ -0,0 +1,279 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    text::*,
+    widgets::*,
+    Frame
+};
+use super::*;
+use super::card::*;
+use super::players::*;
+use super::desk::*;
+use super::gaming::*;
+use crate::replay::Replay;
+
+// cards in hand, then held ones dimmed, `per_row` cards a row from `a`
+fn render_hand<B: Backend>(
+    frame: &mut Frame<B>, cards: &[Card], holds: &[Card],
+    mut a: Rect, per_row: usize, is_next: bool
+) {
+    a.width = 11;
+    a.height = 8;
+
+    if cards.is_empty() && holds.is_empty() {
+        render_card(frame, &NULL_CARD, a, CardStyle::Clear, false, Some(CARD_CLEAR_BOREDER));
+        return;
+    }
+
+    let border = if is_next { NEXT_TURN } else { MYCARD_BORDER };
+    let all: Vec<(&Card, bool)> = cards.iter().map(|c| (c, false)).chain(
+        holds.iter().map(|c| (c, true))
+    ).collect();
+    let org_x = a.x;
+    for (i, (c, held)) in all.iter().enumerate() {
+        if i > 0 && i % per_row == 0 {
+            a.x = org_x;
+            a.y += 8;
+        }
+        let row_end = i % per_row == per_row - 1 || i == all.len() - 1;
+        render_card(frame, c, a,
+            if row_end {
+                CardStyle::All
+            } else {
+                CardStyle::Vertical
+            },
+            *held,
+            if *held { Some(MYCARD_BORDER_DIM) } else { Some(border) }
+        );
+        a.x += 3;
+    }
+}
+
+fn render_hand_at<B: Backend>(
+    frame: &mut Frame<B>, cards: &[Card], holds: &[Card], pos: SeatPos, is_next: bool
+) {
+    match pos {
+        // bottom, where my cards are when gaming
+        SeatPos::Bottom => {
+            let mut a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(9),
+                        Constraint::Length(1),
+                    ].as_ref()
+                )
+                .split(frame.size())[1];
+            a = Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(20),
+                        Constraint::Length(14),
+                        Constraint::Percentage(5),
+                        Constraint::Length(47),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[3];
+            a = rect_cut_center(a, 100, -((cards.len() + holds.len()) as i16 * 3 + 8));
+            a.y += 1;
+            render_hand(frame, cards, holds, a, 13, is_next);
+        }
+        // right, left of the name
+        SeatPos::Right => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(16),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(frame.size())[1];
+            let a = Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Min(1),
+                        Constraint::Length(29),
+                        Constraint::Length(2),
+                        Constraint::Length(14),
+                    ].as_ref()
+                )
+                .split(a)[1];
+            render_hand(frame, cards, holds, a, 7, is_next);
+        }
+        // top, right of the hold num
+        SeatPos::Top => {
+            let mut a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(11),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(frame.size())[0];
+            a = Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(52),
+                        Constraint::Length(47),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[1];
+            a = rect_cut_center(a, -8, 100);
+            render_hand(frame, cards, holds, a, 13, is_next);
+        }
+        // left, right of the name
+        SeatPos::Left => {
+            let a = Layout::default()
+                .direction(Direction::Vertical)
+                .vertical_margin(1)
+                .constraints(
+                    [
+                        Constraint::Percentage(30),
+                        Constraint::Length(16),
+                        Constraint::Min(1)
+                    ].as_ref()
+                )
+                .split(frame.size())[1];
+            let a = Layout::default()
+                .direction(Direction::Horizontal)
+                .horizontal_margin(1)
+                .constraints(
+                    [
+                        Constraint::Length(14),
+                        Constraint::Length(1),
+                        Constraint::Length(29),
+                        Constraint::Min(1),
+                    ].as_ref()
+                )
+                .split(a)[2];
+            render_hand(frame, cards, holds, a, 7, is_next);
+        }
+        // compact top ones, right of the name
+        _ => {
+            let mut a = top_slot(frame.size(), pos)[2];
+            a.y += 1;
+            render_hand(frame, cards, holds, a, 5, is_next);
+        }
+    }
+}
+
+fn render_hands<B: Backend>(frame: &mut Frame<B>, game: &Game, next: usize) {
+    for (pid, pos) in seat_positions(game.get_seats()).iter().enumerate() {
+        render_hand_at(frame,
+            &game.get_someone_cards(pid).unwrap_or_default(),
+            &game.get_someone_holds(pid).unwrap_or_default(),
+            *pos, next == pid
+        );
+    }
+}
+
+fn render_replay_info<B: Backend>(frame: &mut Frame<B>, r: &Replay) {
+    let mut a = Layout::default()
+        .direction(Direction::Vertical)
+        .vertical_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(11),
+            ].as_ref()
+        )
+        .split(frame.size())[1];
+    a = Layout::default()
+        .direction(Direction::Horizontal)
+        .horizontal_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(45),
+            ].as_ref()
+        )
+        .split(a)[1];
+
+    let dim = Style::default().fg(NORMAL_DIM);
+    let text = Text::from([
+        Line::styled(
+            format!("MOVE: {}/{}", r.at, r.plays.len()),
+            Style::default().fg(TEXT_NORMAL).add_modifier(Modifier::BOLD)
+        ),
+        Line::styled(
+            if r.autoplay {
+                format!("AUTOPLAY: {}ms a move", r.interval().as_millis())
+            } else {
+                format!("PAUSED, autoplay at {}ms a move", r.interval().as_millis())
+            },
+            Style::default().fg(TEXT_NORMAL)
+        ),
+        Line::styled(format!("JUMP TO: {}", r.jump), Style::default().fg(INPUT_BORDER)),
+        Line::default(),
+        Line::styled("LEFT/RIGHT  step back/forward", dim),
+        Line::styled("SPACE       autoplay/pause", dim),
+        Line::styled("UP/DOWN     faster/slower", dim),
+        Line::styled("0-9 ENTER   jump to a move", dim),
+        Line::styled("ESC         quit", dim),
+    ].to_vec());
+
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .style(Style::default().fg(BORDER_NORMAL))
+            ),
+        a
+    );
+}
+
+fn play_text(names: &[String], play: &Play) -> String {
+    let (is_discard, c, pid) = play.clone().split();
+    let (suit, num) = get_card_text(&c);
+    format!("{} {} {}{}", names[pid], if is_discard { "plays" } else { "holds" }, suit, num)
+}
+
+// every hand face up, the final step is shown as the game result
+pub fn ui_replay<B: Backend>(frame: &mut Frame<B>, r: &Replay) {
+    if r.at == r.plays.len() {
+        let (ds, players, winner, winner_state) = r.result();
+        ui_game_result(frame, ds, players, r.title.clone(), winner, winner_state);
+        return;
+    }
+
+    let game = &r.steps[r.at];
+    let thisround = game.get_thisround();
+    let (chains_small, chains_big) =
+        split_desk_chains(game.export_desk(), &thisround, &None);
+
+    render_players(frame, &r.names, vec![false; r.names.len()], Some(game.get_hold_nums()));
+
+    render_game_info(frame, r.title.clone());
+
+    render_desk(frame, chains_small, chains_big);
+
+    render_hands(frame, game, game.get_next());
+
+    render_msg(frame,
+        if r.at == 0 {
+            format!("{} goes first", r.names[game.get_next()])
+        } else {
+            play_text(&r.names, &r.plays[r.at - 1])
+        }
+    );
+
+    render_replay_info(frame, r);
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    text::*,
+    widgets::*,
+    Frame
+};
+use super::*;
+use super::game_result::*;
+
+// hands shown in the table, older ones only count in TOTAL
+const SHOWN_HANDS: usize = 8;
+
+fn render_score_table<B: Backend>(frame: &mut Frame<B>, names: &Vec<String>, mi: &MatchInfo) {
+    let first = mi.history.len().saturating_sub(SHOWN_HANDS);
+    let shown = &mi.history[first..];
+
+    let mut head = format!("{:<10}", "PLAYER");
+    for i in first..mi.history.len() {
+        head.push_str(&format!("{:>6}", format!("H{}", i + 1)));
+    }
+    head.push_str(&format!("{:>8}", "TOTAL"));
+
+    let mut text = vec![
+        Line::styled(head, Style::default().fg(RESULT_MSG_GREY).bold()),
+        Line::default(),
+    ];
+    for (pid, name) in names.iter().enumerate() {
+        let mut row = format!("{:<10}", name_shorten(name));
+        for hs in shown {
+            row.push_str(&format!("{:>6}", hs.points.get(pid).copied().unwrap_or_default()));
+        }
+        row.push_str(&format!("{:>8}", mi.totals.get(pid).copied().unwrap_or_default()));
+        let color = if mi.leaders.contains(&(pid as u32)) { READY } else { TEXT_NORMAL };
+        text.push(Line::styled(row, Style::default().fg(color)));
+    }
+    text.push(Line::default());
+    for l in mi.get_config().describe() {
+        text.push(Line::styled(l, Style::default().fg(NORMAL_DIM)));
+    }
+
+    let width = 10 + 6 * shown.len() as i16 + 8 + 4;
+    let height = text.len() as i16 + 2;
+    frame.render_widget(
+        Paragraph::new(Text::from(text))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(format!("Match: {} hands played", mi.hands))
+                    .style(Style::default().fg(BORDER_NORMAL))
+            ),
+        rect_cut_center(frame.size(), -height, -width.max(40))
+    );
+}
+
+pub fn ui_scoreboard<B: Backend>(
+    frame: &mut Frame<B>, names: Vec<String>, roomid: String, mi: MatchInfo
+) {
+    render_game_info(frame, roomid);
+
+    render_score_table(frame, &names, &mi);
+
+    let leaders: Vec<String> = mi.leaders.iter().map(
+        |&l| names.get(l as usize).map(name_shorten).unwrap_or_default()
+    ).collect();
+    let (msg, color) = if !mi.over {
+        (format!("Leading: {}", leaders.join(", ")), RESULT_MSG_GREY)
+    } else if mi.leaders.contains(&0) {
+        ("󰱱󰱱󰱱 You win the match!".into(), RESULT_MSG_WIN)
+    } else {
+        (format!("󰱶󰱶󰱶 {} wins the match.", leaders.join(", ")), RESULT_MSG_LOSE)
+    };
+
+    render_result_msg(frame, msg, color);
+    render_result_button(frame);
+}
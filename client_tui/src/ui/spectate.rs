@@ -0,0 +1,80 @@
+use ratatui::{
+    backend::Backend,
+    layout::*,
+    style::*,
+    widgets::*,
+    Frame
+};
+use super::*;
+use super::players::*;
+use super::desk::*;
+use super::gaming::*;
+
+// no hand to show for seat 0, only its hold count
+fn render_bottom_hold_num<B: Backend>(frame: &mut Frame<B>, num: u32) {
+    let mut a = Layout::default()
+        .direction(Direction::Vertical)
+        .vertical_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(9),
+                Constraint::Length(1),
+            ].as_ref()
+        )
+        .split(frame.size())[1];
+    a = rect_cut_center(a, -3, -16);
+
+    frame.render_widget(
+        Paragraph::new(format!("HOLD: {}", num))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+            )
+            .style(Style::default().fg(HOLD_NUM).add_modifier(Modifier::BOLD)),
+        a
+    );
+}
+
+pub fn ui_spectate<B: Backend>(
+    frame: &mut Frame<B>, names: Vec<String>, hold_nums: Vec<u32>, next: usize,
+    roomid: String, last: Option<(usize, Option<Card>)>,
+    chains_small: Vec<Vec<(Card, CardStyleOnDesk)>>,
+    chains_big: Vec<Vec<(Card, CardStyleOnDesk)>>,
+    has_done: bool, msg: Option<String>, clock: Option<TurnClock>
+) {
+    let seats = names.len();
+    render_players(frame, &names, vec![false; seats], Some(hold_nums.clone()));
+
+    render_game_info(frame, roomid);
+
+    render_desk(frame, chains_small, chains_big);
+
+    render_bottom_hold_num(frame, hold_nums[0]);
+
+    if let Some(m) = msg {
+        render_msg(frame, m);
+    } else if !has_done {
+        match clock.filter(|tc| tc.player == next) {
+            Some(tc) => render_msg(frame, format!("Waiting for {}...... {}s", names[next], tc.secs)),
+            None => render_msg(frame, format!("Waiting for {}......", names[next])),
+        }
+    }
+
+    if let Some((who, opc)) = last {
+        render_last(frame, opc, seat_pos(seats, who));
+    }
+}
+
+pub fn ui_spectate_waiting<B: Backend>(
+    frame: &mut Frame<B>, roomid: String, msg: Option<String>
+) {
+    render_game_info(frame, roomid);
+
+    render_center_msg(frame, vec![
+        "Spectating".into(),
+        msg.unwrap_or_default(),
+    ]);
+}
@@ -7,6 +7,7 @@ use ratatui::{
     Frame
 };
 use super::players::*;
+use super::game_result::name_shorten;
 use super::*;
 
 pub fn ui_wait_player<B: Backend>(
@@ -15,19 +16,19 @@ pub fn ui_wait_player<B: Backend>(
 {
     render_players(frame,
         players.iter().map(|p| p.0.clone()).collect::<Vec<String>>().as_ref(),
-        vec![false; 4], None
+        vec![false; players.len()], None
     );
 
     render_center_msg(frame, msg.clone());
 
     render_game_info(frame, roomid.clone());
 
-    render_ready_button(frame, false);
+    render_wait_button(frame, "Add Bot!");
 }
 
 pub fn ui_wait_ready<B: Backend>(
     frame: &mut Frame<B>, players: Vec<(String, usize, bool)>,
-    msg: Vec<String>, roomid: String)
+    msg: Vec<String>, roomid: String, rules: RuleSet, profiles: Vec<Profile>)
 {
     render_players(frame,
         players.iter().map(|p| p.0.clone()).collect::<Vec<String>>().as_ref(),
@@ -38,12 +39,103 @@ pub fn ui_wait_ready<B: Backend>(
 
     render_game_info(frame, roomid.clone());
 
+    render_rules(frame, &rules);
+
+    render_profiles(frame, &profiles);
+
     if !players[0].2 {
-        render_ready_button(frame, true);
+        render_wait_button(frame, "Get Ready!");
+    }
+}
+
+fn render_rules<B: Backend>(frame: &mut Frame<B>, rules: &RuleSet) {
+    let mut a = Layout::default()
+        .direction(Direction::Vertical)
+        .vertical_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(5),
+            ].as_ref()
+        )
+        .split(frame.size())[1];
+    a = Layout::default()
+        .direction(Direction::Horizontal)
+        .horizontal_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(36),
+            ].as_ref()
+        )
+        .split(a)[1];
+
+    let text: Vec<Line> = rules.describe().into_iter().map(
+        |r| Line::styled(r, Style::default().fg(TEXT_NORMAL))
+    ).collect();
+
+    frame.render_widget(
+        Paragraph::new(Text::from(text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(if rules.is_house_rules() { "House Rules" } else { "Rules" })
+                    .style(Style::default().fg(BORDER_NORMAL))
+            ),
+        a
+    );
+}
+
+fn render_profiles<B: Backend>(frame: &mut Frame<B>, profiles: &Vec<Profile>) {
+    if profiles.is_empty() {
+        return;
     }
+
+    let mut a = Layout::default()
+        .direction(Direction::Vertical)
+        .vertical_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(profiles.len() as u16 + 2),
+                // the rules box
+                Constraint::Length(5),
+            ].as_ref()
+        )
+        .split(frame.size())[1];
+    a = Layout::default()
+        .direction(Direction::Horizontal)
+        .horizontal_margin(1)
+        .constraints(
+            [
+                Constraint::Min(1),
+                Constraint::Length(44),
+            ].as_ref()
+        )
+        .split(a)[1];
+
+    let text: Vec<Line> = profiles.iter().map(
+        |p| Line::styled(
+            format!("{:<10} {}", name_shorten(&p.name), p.describe()),
+            Style::default().fg(TEXT_NORMAL)
+        )
+    ).collect();
+
+    frame.render_widget(
+        Paragraph::new(Text::from(text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title("Ratings")
+                    .style(Style::default().fg(BORDER_NORMAL))
+            ),
+        a
+    );
 }
 
-fn render_ready_button<B: Backend>(frame: &mut Frame<B>, active: bool) {
+fn render_wait_button<B: Backend>(frame: &mut Frame<B>, text: &str) {
     let mut button = Layout::default()
         .direction(Direction::Vertical)
         .vertical_margin(1)
@@ -56,10 +148,10 @@ fn render_ready_button<B: Backend>(frame: &mut Frame<B>, active: bool) {
         .split(frame.size())[1];
     button = rect_cut_center(button, -3, 20);
 
-    frame.render_widget(get_button("Get Ready!", active), button);
+    frame.render_widget(get_button(text, true), button);
 }
 
-fn render_center_msg<B: Backend>(frame: &mut Frame<B>, msg: Vec<String>) {
+pub(super) fn render_center_msg<B: Backend>(frame: &mut Frame<B>, msg: Vec<String>) {
     let msg: Vec<Line> = msg.into_iter().map(
         |m| Line::styled(m, Style::default().fg(CENTER_MSG).bold())
     ).collect();
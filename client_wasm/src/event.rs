@@ -13,21 +13,39 @@ pub fn handle_click(
         // pass
     } else if csbrief.exitmenu.0 {
         handle_click_exit_menu(get_button_num_from_brief(&csbrief), csbrief.exitmenu.1, x, y, tx)?;
+    } else if let Some(which) = csbrief.host_menu {
+        handle_click_host_menu(which, x, y, tx)?;
+    } else if CHAT_BUTTON.is_clicked_in(x, y) {
+        // the client ignores it where there is no chat
+        hidden_input_blur();
+        spawn_tx_send(tx, ClientEvent::ToggleChat);
+    } else if csbrief.picking_reaction {
+        handle_click_reaction_picker(x, y, tx)?;
+    } else if REACT_BUTTON.is_clicked_in(x, y)
+        && matches!(csbrief.fsm, ClientStateMachineBrief::Gaming {..})
+    {
+        spawn_tx_send(tx, ClientEvent::ToggleReactions);
+    } else if csbrief.chat_open {
+        handle_click_chat(x, y, tx)?;
     } else {
         match csbrief.fsm {
             ClientStateMachineBrief::GetServer{input}
                 => handle_click_get_server(x, y, tx, input)?,
             ClientStateMachineBrief::AskName{button, is_input, input}
                 => handle_click_ask_name(x, y, tx, button, is_input, input)?,
-            ClientStateMachineBrief::NewRoom{input}
-                => handle_click_new_room(x, y, tx, input)?,
-            ClientStateMachineBrief::JoinRoom{input}
-                => handle_click_join_room(x, y, tx, input)?,
+            ClientStateMachineBrief::NewRoom{input, masked}
+                => handle_click_new_room(x, y, tx, input, masked)?,
+            ClientStateMachineBrief::Lobby{choose, row_num}
+                => handle_click_lobby(x, y, tx, choose, row_num)?,
+            ClientStateMachineBrief::JoinRoom{input, masked}
+                => handle_click_join_room(x, y, tx, input, masked)?,
             ClientStateMachineBrief::WaitPlayer => handle_click_wait_player(x, y, tx)?,
             ClientStateMachineBrief::WaitReady => handle_click_wait_ready(x, y, tx)?,
             ClientStateMachineBrief::Gaming{ choose, card_num, button, my_turn }
                 => handle_click_gaming(x, y, tx, choose, card_num, button, my_turn)?,
             ClientStateMachineBrief::GameResult => handle_click_game_result(x, y, tx)?,
+            // nothing to click but ESC
+            ClientStateMachineBrief::Spectating => {}
         }
     }
 
@@ -58,6 +76,26 @@ fn handle_click_exit_menu(
     Ok(())
 }
 
+// like the exit menu, arrows to the clicked item then Enter
+fn handle_click_host_menu(which: usize, x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<()> {
+    if HOST_MENU_PREV.iter().any(|b| b.is_clicked_in(x, y)) {
+        spawn_tx_send(tx, ClientEvent::LeftArrow);
+    } else if HOST_MENU_NEXT.iter().any(|b| b.is_clicked_in(x, y)) {
+        spawn_tx_send(tx, ClientEvent::RightArrow);
+    } else if let Some(clicked) = HOST_MENU_BUTTON.iter().position(|b| b.is_clicked_in(x, y)) {
+        let dis = clicked as i32 - which as i32;
+        let e = if dis > 0 {
+            ClientEvent::DownArrow
+        } else {
+            ClientEvent::UpArrow
+        };
+        let mut payload = vec![e; dis.abs() as usize];
+        payload.push(ClientEvent::Enter);
+        spawn_tx_send_multiple(tx, payload);
+    }
+    Ok(())
+}
+
 // return true if handled as esc button
 fn handle_click_esc_button(x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<bool> {
     let ret = if ESC_BUTTON.is_clicked_in(x, y) {
@@ -69,8 +107,39 @@ fn handle_click_esc_button(x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<
     Ok(ret)
 }
 
+// any click closes the picker, sending the reaction if on one
+fn handle_click_reaction_picker(x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<()> {
+    if let Some(clicked) = REACT_PICK.iter().position(|b| b.is_clicked_in(x, y)) {
+        spawn_tx_send(tx, ClientEvent::React(Reaction::ALL[clicked]));
+    } else {
+        spawn_tx_send(tx, ClientEvent::ToggleReactions);
+    }
+    Ok(())
+}
+
+// the buttons of the state are covered while the window is open
+fn handle_click_chat(x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<()> {
+    if CHAT_INPUT.is_clicked_in(x, y) {
+        hidden_input_focus();
+        return Ok(());
+    }
+
+    hidden_input_blur();
+    if CHAT_SEND_BUTTON.is_clicked_in(x, y) {
+        spawn_tx_send(tx, ClientEvent::Enter);
+    } else if CHAT_OLDER_BUTTON.is_clicked_in(x, y) {
+        spawn_tx_send(tx, ClientEvent::UpArrow);
+    } else if CHAT_NEWER_BUTTON.is_clicked_in(x, y) {
+        spawn_tx_send(tx, ClientEvent::DownArrow);
+    } else if CHAT_CLOSE_BUTTON.is_clicked_in(x, y) {
+        spawn_tx_send(tx, ClientEvent::ToggleChat);
+    }
+    Ok(())
+}
+
+// a masked input is measured by what is drawn
 fn handle_click_prompt_input_cursor(
-    dx: f64, tx: Sender<ClientEvent>, input: Input
+    dx: f64, tx: Sender<ClientEvent>, input: Input, masked: bool
 ) {
     let new_cursor = |cursor| {
         hidden_input_set_cursor(cursor);
@@ -82,8 +151,9 @@ fn handle_click_prompt_input_cursor(
         );
     };
 
+    let shown = if masked { input.masked() } else { input.clone() };
     let mut width = 0f64;
-    for (i, c) in input.value().chars().enumerate() {
+    for (i, c) in shown.value().chars().enumerate() {
         let w = get_text_metric(&c.to_string()).0;
         if dx <= width + w/2.0 {
             if input.cursor() != i {
@@ -94,20 +164,20 @@ fn handle_click_prompt_input_cursor(
         width += w;
     }
 
-    let input_len = input.value().len();
+    let input_len = shown.value().len();
     if input.cursor() != input_len {
         new_cursor(input_len);
     }
 }
 
 fn handle_click_prompt_single_button(
-    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input
+    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input, masked: bool
 ) -> JsResult<()> {
     if PROMPT_INPUT.is_clicked_in(x, y) {
         hidden_input_focus();
         if hidden_input_is_focused() {
             if PROMPT_INPUT_TEXT.is_clicked_in(x, y) {
-                handle_click_prompt_input_cursor(x - PROMPT_INPUT_TEXT.x, tx, input);
+                handle_click_prompt_input_cursor(x - PROMPT_INPUT_TEXT.x, tx, input, masked);
             }
         }
     } else {
@@ -122,7 +192,7 @@ fn handle_click_prompt_single_button(
 fn handle_click_get_server(
     x: f64, y: f64, tx: Sender<ClientEvent>, input: Input
 ) -> JsResult<()> {
-    handle_click_prompt_single_button(x, y, tx, input)
+    handle_click_prompt_single_button(x, y, tx, input, false)
 }
 
 fn handle_click_ask_name(
@@ -133,7 +203,7 @@ fn handle_click_ask_name(
         hidden_input_focus();
         if hidden_input_is_focused() {
             if PROMPT_INPUT_TEXT.is_clicked_in(x, y) {
-                handle_click_prompt_input_cursor(x - PROMPT_INPUT_TEXT.x, tx, input);
+                handle_click_prompt_input_cursor(x - PROMPT_INPUT_TEXT.x, tx, input, false);
             }
         }
     } else {
@@ -154,18 +224,42 @@ fn handle_click_ask_name(
 }
 
 fn handle_click_new_room(
-    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input
+    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input, masked: bool
 ) -> JsResult<()> {
-    handle_click_prompt_single_button(x, y, tx, input)
+    handle_click_prompt_single_button(x, y, tx, input, masked)
+}
+
+fn handle_click_lobby(
+    x: f64, y: f64, tx: Sender<ClientEvent>, choose: usize, row_num: usize,
+) -> JsResult<()> {
+    let first = lobby_first_row(choose);
+    if let Some(clicked) = (first..row_num.min(first + LOBBY_ROWS)).find(
+        |i| lobby_row_rect(i - first).is_clicked_in(x, y)
+    ) {
+        let dis = clicked as i32 - choose as i32;
+        let e = if dis > 0 {
+            ClientEvent::DownArrow
+        } else {
+            ClientEvent::UpArrow
+        };
+        let mut payload = vec![e; dis.abs() as usize];
+        payload.push(ClientEvent::Enter);
+        spawn_tx_send_multiple(tx, payload);
+    }
+    Ok(())
 }
 
 fn handle_click_join_room(
-    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input
+    x: f64, y: f64, tx: Sender<ClientEvent>, input: Input, masked: bool
 ) -> JsResult<()> {
-    handle_click_prompt_single_button(x, y, tx, input)
+    handle_click_prompt_single_button(x, y, tx, input, masked)
 }
 
-fn handle_click_wait_player(_x: f64, _y: f64, _tx: Sender<ClientEvent>) -> JsResult<()> {
+fn handle_click_wait_player(x: f64, y: f64, tx: Sender<ClientEvent>) -> JsResult<()> {
+    // Add Bot button takes the place of Get Ready button
+    if WAIT_READY_BUTTON.is_clicked_in(x, y) {
+        spawn_tx_send(tx, ClientEvent::Enter);
+    }
     Ok(())
 }
 
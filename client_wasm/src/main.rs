@@ -32,6 +32,16 @@ pub fn spawn_tx_send_multiple(tx: Sender<ClientEvent>, payload: Vec<ClientEvent>
     });
 }
 
+// roomid of "?invite=ID" in the page URL
+fn get_invite() -> Option<String> {
+    let search = gloo::utils::window().location().search().ok()?;
+    let code = search.trim_start_matches('?').split('&').find_map(
+        |kv| kv.strip_prefix("invite=")
+    )?;
+    let code: String = js_sys::decode_uri_component(code).ok()?.into();
+    if code.is_empty() { None } else { Some(code) }
+}
+
 async fn build_client(addr: String) -> Result<RpcClient, String> {
     let (ip, port): (String, String) = match addr.find(':') {
         Some(i) => (addr[0..i].into(), addr[i+1..].into()),
@@ -55,6 +65,7 @@ fn spawn_event_handler(tx: Sender<ClientEvent>, csm: CSMType) -> JsResult<()> {
     // canvas click event
     info!("Starting canvas click handler...");
     let txc = tx.clone();
+    let csmc = csm.clone();
     let listener = gloo::events::EventListener::new(&get_canvas(), "click", move |e| {
         // warn!("inside click handler");
         let event = e.dyn_ref::<web_sys::MouseEvent>().unwrap_throw();
@@ -63,7 +74,7 @@ fn spawn_event_handler(tx: Sender<ClientEvent>, csm: CSMType) -> JsResult<()> {
             event.client_x() as f64 - left,
             event.client_y() as f64 - top,
             txc.clone(),
-            csm.borrow().get_client_state_brief(),
+            csmc.borrow().get_client_state_brief(),
         ).unwrap_throw();
     });
     listener.forget();
@@ -116,10 +127,22 @@ fn spawn_event_handler(tx: Sender<ClientEvent>, csm: CSMType) -> JsResult<()> {
         }
     });
     listener.forget();
+
+    // enter sends the chat, prompts keep their buttons
+    info!("Starting chat enter handler...");
+    let txc = tx.clone();
+    let listener = gloo::events::EventListener::new(&get_hidden_input(), "keyup", move |e| {
+        let event = e.dyn_ref::<web_sys::KeyboardEvent>().unwrap_throw();
+        if event.key() == "Enter" && csm.borrow().get_client_state_brief().chat_open {
+            spawn_tx_send(txc.clone(), ClientEvent::Enter);
+        }
+    });
+    listener.forget();
     Ok(())
 }
 
 struct StreamCancelToken;
+struct LobbyCancelToken;
 
 type CSMType = Rc<RefCell<ClientStateManager>>;
 
@@ -129,19 +152,32 @@ struct ClientWasm {
     rx: Receiver<ClientEvent>,
     stream_tx: Sender<StreamCancelToken>,
     stream_rx: Receiver<StreamCancelToken>,
+    lobby_tx: Sender<LobbyCancelToken>,
+    lobby_rx: Receiver<LobbyCancelToken>,
 }
 
 impl ClientWasm {
-    pub fn new(default_addr: String) -> Self {
+    pub fn new(default_addr: String, invite: Option<String>) -> Self {
         let (tx, rx) = bounded(DEFAULT_CHANNEL_SIZE);
         let (stream_tx, stream_rx) = bounded(2);
+        let (lobby_tx, lobby_rx) = bounded(2);
+
+        let mut csm = ClientStateManager::new(default_addr.clone());
+        if let Some(roomid) = invite {
+            info!("Invited to room {}, connecting to the default server", roomid);
+            csm = csm.with_invite(roomid);
+            // straight to asking the name
+            spawn_tx_send(tx.clone(), ClientEvent::Enter);
+        }
 
         Self {
-            csm: Rc::new(RefCell::new(ClientStateManager::new(default_addr.clone()))),
+            csm: Rc::new(RefCell::new(csm)),
             tx,
             rx,
             stream_tx,
             stream_rx,
+            lobby_tx,
+            lobby_rx,
         }
     }
 
@@ -157,7 +193,12 @@ impl ClientWasm {
                     break;
                 }
                 match gs.message().await {
-                    Err(s) => panic!("GameStream error: {}", s),
+                    Err(s) => {
+                        warn!("GameStream error: {}", s);
+                        txc.send(ClientEvent::StreamLost(s.to_string())).await
+                            .expect("Send Action::StreamLost to client");
+                        break;
+                    }
                     Ok(None) => {
                         info!("GameStream closed! Stream listener exits!");
                         break;
@@ -169,6 +210,49 @@ impl ClientWasm {
         });
     }
 
+    fn spawn_lobby_listener(&mut self, mut ls: LobbyStream) {
+        let txc = self.tx.clone();
+        let cancel_rx = self.lobby_rx.clone();
+        // drop stale cancel tokens left by the previous listener
+        while let Ok(_) = cancel_rx.try_recv() {}
+        info!("Spawning LobbyStream listener...");
+        spawn_local(async move {
+            loop {
+                if let Ok(_) = cancel_rx.try_recv() {
+                    break;
+                }
+                match ls.message().await {
+                    Err(s) => {
+                        // the lobby is only a convenience, rooms can still be joined by ID
+                        warn!("LobbyStream error: {}", s);
+                        break;
+                    }
+                    Ok(None) => {
+                        info!("LobbyStream closed! Lobby listener exits!");
+                        break;
+                    }
+                    Ok(Some(rl)) => txc.send(ClientEvent::LobbyMsg(rl)).await
+                        .expect("Send Action::LobbyMsg to client"),
+                }
+            }
+        });
+    }
+
+    fn spawn_resume_timer(&mut self, millis: u32) {
+        let txc = self.tx.clone();
+        gloo::timers::callback::Timeout::new(millis, move || {
+            spawn_tx_send(txc, ClientEvent::ResumeSession);
+        }).forget();
+    }
+
+    // the turn clock counts down on these, for as long as the page lives
+    fn spawn_clock_ticker(&mut self) {
+        let txc = self.tx.clone();
+        gloo::timers::callback::Interval::new(1000, move || {
+            spawn_tx_send(txc.clone(), ClientEvent::ClockTick);
+        }).forget();
+    }
+
     fn spawn_rpc_client(&mut self, addr: String) {
         let txc = self.tx.clone();
         spawn_local(async move {
@@ -180,6 +264,7 @@ impl ClientWasm {
 
     pub async fn run(&mut self) -> JsResult<()> {
         spawn_event_handler(self.tx.clone(), self.csm.clone())?;
+        self.spawn_clock_ticker();
         ui_init()?;
 
         // draw first anyway
@@ -200,12 +285,21 @@ impl ClientWasm {
                     if reply.cancel_stream_listener {
                         self.cancel_stream_listener();
                     }
+                    if reply.cancel_lobby_listener {
+                        self.cancel_lobby_listener();
+                    }
                     if let Some(addr) = reply.spawn_rpc_client {
                         self.spawn_rpc_client(addr);
                     }
                     if let Some(gs) = reply.spawn_stream_listener {
                         self.spawn_stream_listener(gs);
                     }
+                    if let Some(ls) = reply.spawn_lobby_listener {
+                        self.spawn_lobby_listener(ls);
+                    }
+                    if let Some(millis) = reply.resume_after {
+                        self.spawn_resume_timer(millis);
+                    }
                     if reply.need_redraw {
                         self.draw()?;
                     }
@@ -224,15 +318,20 @@ impl ClientWasm {
         let _ = self.stream_tx.send(StreamCancelToken{});
     }
 
+    fn cancel_lobby_listener(&self) {
+        let _ = self.lobby_tx.try_send(LobbyCancelToken{});
+    }
+
     pub fn exit(self) -> JsResult<()> {
         self.cancel_stream_listener();
+        self.cancel_lobby_listener();
         Ok(())
     }
 }
 
 fn main() {
     spawn_local(async move {
-        let mut client = ClientWasm::new(format!("{}:{}", DEFAULT_IP, DEFAULT_PORT));
+        let mut client = ClientWasm::new(format!("{}:{}", DEFAULT_IP, DEFAULT_PORT), get_invite());
 
         info!("Heart7 Client Starts!");
         client.run().await.expect("Running ClientWasm");
@@ -0,0 +1,132 @@
+use super::*;
+
+fn draw_text_oneline_color(rect: &Rect, t: &str, color: &str) {
+    let ctx = get_canvas_ctx();
+    let old = ctx.fill_style();
+    ctx.set_fill_style_str(color);
+    draw_text_oneline_with_descent(rect, t, get_ascii_max_descent());
+    #[allow(deprecated)]
+    ctx.set_fill_style(&old);
+}
+
+// split t into pieces of at most w wide, the first one after a prefix of pw
+fn wrap_text(t: &str, pw: f64, w: f64) -> Vec<String> {
+    let mut rows = vec![];
+    let mut row = String::new();
+    let mut width = pw;
+    for c in t.chars() {
+        let cw = get_text_metric(&c.to_string()).0;
+        if width + cw > w && !row.is_empty() {
+            rows.push(std::mem::take(&mut row));
+            width = 0.0;
+        }
+        row.push(c);
+        width += cw;
+    }
+    rows.push(row);
+    rows
+}
+
+// (name, text) of each row shown, the name only on the first row of a line
+fn chat_rows(chat: &Chat) -> Vec<(String, String)> {
+    let mut rows = vec![];
+    let end = chat.lines.len().saturating_sub(chat.scroll);
+    for (name, text) in chat.lines[..end].iter().rev() {
+        let name = if name.is_empty() {
+            String::new()
+        } else {
+            format!("{}: ", fit_in_name(80.0, name.clone()))
+        };
+        let pw = get_text_metric(&name).0;
+        let mut line = wrap_text(text, pw, CHAT_LINE_START.w);
+        // newest first, so the rows of a line go in reversed
+        let first = line.remove(0);
+        rows.extend(line.into_iter().rev().map(|t| (String::new(), t)));
+        rows.push((name, first));
+        if rows.len() >= CHAT_LINES {
+            break;
+        }
+    }
+    rows.truncate(CHAT_LINES);
+    rows.reverse();
+    rows
+}
+
+fn ui_chat_lines(chat: &Chat) {
+    let rows = chat_rows(chat);
+    let mut r = CHAT_LINE_START.clone();
+    r.y += r.h * (CHAT_LINES - rows.len()) as f64;
+    for (name, text) in rows {
+        let mut tr = r.clone();
+        if !name.is_empty() {
+            draw_text_oneline_color(&tr, &name, NAME);
+            let nw = get_text_metric(&name).0;
+            tr.x += nw;
+            tr.w -= nw;
+        }
+        // notices have no sender
+        let color = if name.is_empty() && text.starts_with("Not sent: ") { GAME_MSG } else { TEXT_NORMAL };
+        draw_text_oneline_color(&tr, &text, color);
+        r.y += r.h;
+    }
+}
+
+fn ui_chat_input(input: &Input) {
+    let color = if hidden_input_is_focused() { INPUT_BORDER } else { INPUT_BORDER_BLOCK };
+    draw_rect(&CHAT_INPUT, color);
+
+    // keep the cursor in sight, dropping chars from the front
+    let chars: Vec<char> = input.value().chars().collect();
+    let width = |cs: &[char]| get_text_metric(&cs.iter().collect::<String>()).0;
+    let cursor = input.cursor().min(chars.len());
+    let mut first = 0;
+    while first < cursor && width(&chars[first..cursor]) > CHAT_INPUT_TEXT.w {
+        first += 1;
+    }
+    let mut last = chars.len();
+    while last > cursor && width(&chars[first..last]) > CHAT_INPUT_TEXT.w {
+        last -= 1;
+    }
+    draw_text_oneline_with_descent(
+        &CHAT_INPUT_TEXT, &chars[first..last].iter().collect::<String>(), get_ascii_max_descent()
+    );
+
+    if hidden_input_is_focused() {
+        let cursor_rect = Rect {
+            x: CHAT_INPUT_TEXT.x + width(&chars[first..cursor]),
+            y: CHAT_INPUT_TEXT.y,
+            w: 1.0,
+            h: CHAT_INPUT_TEXT.h,
+        };
+        get_canvas_ctx().fill_rect(cursor_rect.x, cursor_rect.y, cursor_rect.w, cursor_rect.h);
+    }
+}
+
+pub fn ui_chat(chat: Chat) {
+    if !chat.open {
+        let title = if chat.unread == 0 {
+            "Chat".into()
+        } else {
+            format!("Chat {}", chat.unread.min(99))
+        };
+        draw_button(&CHAT_BUTTON, &title, true);
+        return;
+    }
+
+    draw_button(&CHAT_BUTTON, "Chat", false);
+
+    clear_rect(&CHAT_WINDOW);
+    draw_rounded_rect(&CHAT_WINDOW, BORDER_NORMAL);
+
+    set_font_small();
+    ui_chat_lines(&chat);
+    set_font_normal();
+
+    hidden_input_set_value(chat.input.value());
+    ui_chat_input(&chat.input);
+
+    draw_button(&CHAT_SEND_BUTTON, "Send", !chat.input.value().trim().is_empty());
+    draw_button(&CHAT_OLDER_BUTTON, "Older", chat.scroll + 1 < chat.lines.len());
+    draw_button(&CHAT_NEWER_BUTTON, "Newer", chat.scroll != 0);
+    draw_button(&CHAT_CLOSE_BUTTON, "Close", true);
+}
@@ -43,9 +43,13 @@ pub const DIAMOND:               &str = "#FF7B54";
 pub const HOLD_BORDER:           &str = "#FF7777";
 
 pub const NEXT_TURN:             &str = "#FF6701";
+pub const NEXT_TURN_BANK:        &str = "#F90716";
 
 pub const GAME_MSG:              &str = "#F90716";
 
+pub const BUBBLE_BORDER:         &str = "#C38154";
+pub const BUBBLE_TEXT:           &str = "#2CD3E1";
+
 pub const DESK_RESULT_0:         &str = "#95CD41";
 pub const DESK_RESULT_1:         &str = "#F90716";
 pub const DESK_RESULT_2:         &str = "#FFCE45";
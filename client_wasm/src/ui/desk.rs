@@ -30,11 +30,13 @@ pub fn ui_desk_hold_num(hold_nums: Vec<u32>) {
     //     ])[1].clone(),
     // ];
 
-    for (r, hn) in DESK_HOLD_NUM.into_iter().zip(hold_nums).skip(1) {
+    let positions = seat_positions(hold_nums.len());
+    for (pos, hn) in positions.iter().zip(hold_nums).skip(1) {
+        let r = &DESK_HOLD_NUM[*pos as usize];
         // warn!("{:?}", r);
         // draw_rect(&r, BORDER_LIGHT);
         draw_text_oneline_center_color(
-            &r,
+            r,
             &format!("HOLD: {}", hn),
             HOLD_NUM,
         );
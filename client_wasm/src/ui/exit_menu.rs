@@ -1,6 +1,7 @@
 use super::*;
 
-pub fn ui_exit_menu(button_num: u32) {
+// the host of a wait room has four buttons too, with Host Menu for Exit Game
+pub fn ui_exit_menu(button_num: u32, host: bool) {
     let menu_rect = get_canvas_rect().center_cut(Percent(40), Percent(80));
     draw_rounded_rect(&menu_rect, BORDER_LIGHT);
 
@@ -55,7 +56,7 @@ pub fn ui_exit_menu(button_num: u32) {
             // warn!("Button rect {:?}", slices[7]);
 
             draw_button(&EM_BUTTON_4[0], "Back", true);
-            draw_button(&EM_BUTTON_4[1], "Exit Game", true);
+            draw_button(&EM_BUTTON_4[1], if host { "Host Menu" } else { "Exit Game" }, true);
             draw_button(&EM_BUTTON_4[2], "Exit Room", true);
             draw_button(&EM_BUTTON_4[3], "Exit Program", true);
         }
@@ -116,23 +116,47 @@ fn ui_hold_result_left(mut hold: Vec<Card>) {
     }
 }
 
-fn ui_hold_result(mut holds: Vec<Vec<Card>>) {
-    ui_hold_result_left(holds.pop().unwrap());
-    ui_hold_result_top(holds.pop().unwrap());
-    ui_hold_result_right(holds.pop().unwrap());
+fn ui_hold_result_compact(hold: Vec<Card>, pos: SeatPos) {
+    if hold.len() == 0 {
+        draw_text_oneline_center_color(
+            &RESULT_HOLD_COMPACT_EMPTY[pos as usize], "CLEAR!", CARD_CLEAR_BORDER
+        );
+        return;
+    }
+
+    let mut r = RESULT_HOLD_COMPACT_START[pos as usize].clone();
+    for c in hold {
+        ui_card_vertical(&r, Some(c), Some(MYCARD_BORDER));
+        r.x += RESULT_HOLD_COMPACT_GAP_WIDTH;
+    }
+}
+
+fn ui_hold_result(holds: Vec<Vec<Card>>) {
+    let positions = seat_positions(holds.len());
+    for (hold, pos) in holds.into_iter().zip(positions) {
+        match pos {
+            // shown as my holds
+            SeatPos::Bottom => {}
+            SeatPos::Right => ui_hold_result_right(hold),
+            SeatPos::Top => ui_hold_result_top(hold),
+            SeatPos::Left => ui_hold_result_left(hold),
+            _ => ui_hold_result_compact(hold, *pos),
+        }
+    }
 }
 
 fn ui_hold_points(points: Vec<u32>) {
-    for (r, p) in RESULT_HOLD_POINTS.into_iter().zip(points).skip(1) {
+    let positions = seat_positions(points.len());
+    for (pos, p) in positions.iter().zip(points).skip(1) {
         draw_text_oneline_center_color(
-            &r,
+            &RESULT_HOLD_POINTS[*pos as usize],
             &format!("POINTS: {}", p),
             HOLD_NUM,
         );
     }
 }
 
-fn fit_in_name(w: f64, mut name: String) -> String {
+pub(super) fn fit_in_name(w: f64, mut name: String) -> String {
     // warn!("{}", get_text_metric("Player  wins.").0);
     // warn!("{}", get_text_metric("Player  clears.").0);
     // warn!("{}", get_text_metric("Player  clears with SEVEN.").0);
@@ -48,7 +48,7 @@ fn ui_gaming_my_cards(my_cards: Vec<Card>, choose: usize, hints: Vec<bool>) {
     }
 }
 
-fn ui_gameing_msg(msg: String) {
+pub(crate) fn ui_gameing_msg(msg: String) {
     // let r = get_canvas_rect().cut_width([
     //     Percent(32),
     //     Percent(43),
@@ -63,7 +63,7 @@ fn ui_gameing_msg(msg: String) {
     set_font_normal();
 }
 
-fn ui_gaming_next(next: usize) {
+fn ui_gaming_next(pos: SeatPos, clock: Option<TurnClock>) {
     // let r = match next {
     //     // myself
     //     0 => {
@@ -110,19 +110,23 @@ fn ui_gaming_next(next: usize) {
     // warn!("{:?}", r);
     // draw_rect(&r, BORDER_LIGHT);
 
-    draw_text_oneline_center_color(
-        &GAMING_NEXT[next],
-        if next == 0 {
-            "Your Turn!"
-        } else {
-            "Waiting..."
-        },
-        NEXT_TURN,
-    );
+    let text = if pos == SeatPos::Bottom {
+        "Your Turn!"
+    } else {
+        "Waiting..."
+    };
+    match clock {
+        None => draw_text_oneline_center_color(&GAMING_NEXT[pos as usize], text, NEXT_TURN),
+        Some(tc) => draw_text_oneline_center_color(
+            &GAMING_NEXT[pos as usize],
+            &format!("{} {}s", text, tc.secs),
+            if tc.in_bank() { NEXT_TURN_BANK } else { NEXT_TURN },
+        ),
+    }
 }
 
-fn ui_gaming_last(last: Option<Card>, who: usize) {
-    if who == 0 {
+pub(crate) fn ui_gaming_last(last: Option<Card>, pos: SeatPos) {
+    if pos == SeatPos::Bottom {
         return;
     }
 
@@ -161,7 +165,7 @@ fn ui_gaming_last(last: Option<Card>, who: usize) {
     // };
     // warn!("{:?}", r);
 
-    ui_card_vertical(&GAMING_LAST[who], last.clone(), Some(NEXT_TURN));
+    ui_card_vertical(&GAMING_LAST[pos as usize], last.clone(), Some(NEXT_TURN));
 }
 
 fn ui_gaming_button() {
@@ -187,10 +191,12 @@ pub fn ui_gaming(
     names: Vec<String>, hold_nums: Vec<u32>, next: usize,
     roomid: String, choose: usize, last: Option<(usize, Option<Card>)>,
     my_cards: Vec<Card>, my_holds: Vec<Card>, hints: Vec<bool>,
-    desk: Vec<Vec<Card>>, has_done: bool, msg: Option<String>
+    desk: Vec<Vec<Card>>, has_done: bool, msg: Option<String>,
+    clock: Option<TurnClock>
 ) {
     ui_room_id(roomid);
 
+    let seats = names.len();
     ui_players(names);
 
     ui_desk_hold_num(hold_nums);
@@ -207,12 +213,12 @@ pub fn ui_gaming(
     }
 
     if !has_done {
-        ui_gaming_next(next);
+        ui_gaming_next(seat_pos(seats, next), clock.filter(|tc| tc.player == next));
     }
 
+    // not always the one before next, who has played all cards is skipped
     if let Some((who, last)) = last {
-        // assert_eq!((next+3)%4, who);
-        ui_gaming_last(last, who);
+        ui_gaming_last(last, seat_pos(seats, who));
     }
 
     ui_desk_my_holds(my_holds, false);
@@ -0,0 +1,21 @@
+use super::*;
+
+pub fn ui_host_menu(menu: HostMenu) {
+    clear_rect(&HOST_MENU_WINDOW);
+    draw_rounded_rect(&HOST_MENU_WINDOW, BORDER_LIGHT);
+    draw_text_oneline_center(&HOST_MENU_TITLE, "Host Menu");
+
+    set_font_small();
+    for (item, rect) in menu.items.iter().zip(HOST_MENU_BUTTON.iter()) {
+        draw_button(rect, item, true);
+    }
+    // the player of the last two is picked by the arrows
+    for rect in HOST_MENU_PREV.iter() {
+        draw_button(rect, "<", true);
+    }
+    for rect in HOST_MENU_NEXT.iter() {
+        draw_button(rect, ">", true);
+    }
+    draw_text_oneline_center_color(&HOST_MENU_MSG, &menu.msg, GAME_MSG);
+    set_font_normal();
+}
@@ -29,24 +29,53 @@ pub const PROMPT_BUTTON_2: [Rect; 2] = [
     Rect { x: 388.8, y: 229.9, w: 115.2, h: 26.6 },
 ];
 
+// lobby
+pub const LOBBY_ROW_START: Rect = Rect { x: 187.2, y: 150.0, w: 345.6, h: 26.6 };
+pub const LOBBY_ROW_GAP: f64 = 6.0;
+pub const LOBBY_ROWS: usize = 5;
+
 // room id
 pub const ROOM_ID:    Rect = Rect { x: 10.0,  y: 40.0,  w: 125.0,  h: 20.0 };
 
 // wait
 pub const WAIT_CENTER_MSG:   Rect = Rect { x: 180.0, y: 161.5, w: 360.0, h: 57.0 };
 pub const WAIT_READY_BUTTON: Rect = Rect { x: 295.2, y: 304.0, w: 129.6, h: 30.4 };
-pub const WAIT_PLAYER_READY: [Rect; 4] = [
+pub const WAIT_RULES:        Rect = Rect { x: 180.0, y: 228.0, w: 360.0, h: 57.0 };
+pub const WAIT_PROFILES:     Rect = Rect { x: 180.0, y: 70.0,  w: 360.0, h: 76.0 };
+pub const WAIT_PROFILE_NAME_WIDTH: f64 = 100.0;
+// the seat tables below are indexed by SeatPos: myself, right, top, left,
+// then the compact top left, top mid and top right of 5 and 6 seat tables
+pub const WAIT_PLAYER_READY: [Rect; 7] = [
     Rect { x: 321.1, y: 304.0, w: 77.8, h: 30.4 },
     Rect { x: 612.0, y: 180.5, w: 72.0, h: 19.0 },
     Rect { x: 324.0, y: 38.0,  w: 72.0, h: 19.0 },
-    Rect { x: 14.4,  y: 197.6, w: 72.0, h: 19.0 }
+    Rect { x: 14.4,  y: 197.6, w: 72.0, h: 19.0 },
+    Rect { x: 218.0, y: 28.5,  w: 76.0, h: 19.0 },
+    Rect { x: 408.0, y: 28.5,  w: 76.0, h: 19.0 },
+    Rect { x: 598.0, y: 28.5,  w: 76.0, h: 19.0 },
 ];
 
 // player
-pub const PLAYER_MYSELF: Rect = Rect { x: 36.0,  y: 285.0, w: 72.0, h: 57.0 };
-pub const PLAYER_RIGHT:  Rect = Rect { x: 626.4, y: 95.0,  w: 72.0, h: 45.6 };
-pub const PLAYER_TOP:    Rect = Rect { x: 176.0, y: 11.4,  w: 72.0, h: 45.6 };
-pub const PLAYER_LEFT:   Rect = Rect { x: 21.6,  y: 114.0, w: 72.0, h: 45.6 };
+pub const PLAYER: [Rect; 7] = [
+    Rect { x: 36.0,  y: 285.0, w: 72.0, h: 57.0 },
+    Rect { x: 626.4, y: 95.0,  w: 72.0, h: 45.6 },
+    Rect { x: 176.0, y: 11.4,  w: 72.0, h: 45.6 },
+    Rect { x: 21.6,  y: 114.0, w: 72.0, h: 45.6 },
+    Rect { x: 140.0, y: 11.4,  w: 72.0, h: 45.6 },
+    Rect { x: 330.0, y: 11.4,  w: 72.0, h: 45.6 },
+    Rect { x: 520.0, y: 11.4,  w: 72.0, h: 45.6 },
+];
+
+// reaction bubbles, by the card toward the middle of the table
+pub const BUBBLE: [Rect; 7] = [
+    Rect { x: 20.0,  y: 252.0, w: 104.0, h: 26.0 },
+    Rect { x: 516.0, y: 104.0, w: 104.0, h: 26.0 },
+    Rect { x: 160.0, y: 60.0,  w: 104.0, h: 22.0 },
+    Rect { x: 100.0, y: 123.0, w: 104.0, h: 26.0 },
+    Rect { x: 124.0, y: 60.0,  w: 104.0, h: 22.0 },
+    Rect { x: 314.0, y: 60.0,  w: 104.0, h: 22.0 },
+    Rect { x: 504.0, y: 60.0,  w: 104.0, h: 22.0 },
+];
 
 // gaming
 pub const MY_CARD_WIDTH: f64 = 60.0;
@@ -57,19 +86,28 @@ pub const MY_CARD_LEFT_START: Rect = Rect { x: 217.6, y: 285.0, w: MY_CARD_WIDTH
 pub const GAMING_BUTTON_PLAY: Rect = Rect { x: 126.8, y: 282.0, w: 72.0,  h: 34.2 };
 pub const GAMING_BUTTON_HOLD: Rect = Rect { x: 126.8, y: 327.6, w: 72.0,  h: 34.2 };
 pub const GAMING_MSG:         Rect = Rect { x: 210.4, y: 247.0, w: 309.6, h: 19.0 };
-pub const GAMING_NEXT: [Rect; 4] = [
+pub const GAMING_NEXT: [Rect; 7] = [
     Rect { x: 116.6, y: 247.0, w: 93.6, h: 19.0 },
     Rect { x: 612.0, y: 190.0, w: 93.6, h: 19.0 },
     Rect { x: 262.4, y: 45.4,  w: 93.6, h: 19.0 },
     Rect { x: 7.2,   y: 209.0, w: 93.6, h: 19.0 },
+    Rect { x: 218.0, y: 45.4,  w: 76.0, h: 19.0 },
+    Rect { x: 408.0, y: 45.4,  w: 76.0, h: 19.0 },
+    Rect { x: 598.0, y: 45.4,  w: 76.0, h: 19.0 },
 ];
-pub const GAMING_LAST: [Rect; 4] = [
+pub const GAMING_LAST: [Rect; 7] = [
     Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
     Rect { x: 576.0, y: 133.0, w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
     Rect { x: 432.0, y: 19.0,  w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
     Rect { x: 107.4, y: 140.6, w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
+    Rect { x: 298.0, y: 19.0,  w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
+    Rect { x: 488.0, y: 19.0,  w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
+    Rect { x: 678.0, y: 19.0,  w: CARD_V_WIDTH, h: CARD_V_HEIGHT },
 ];
 
+// spectate
+pub const SPECTATE_HOLD_NUM: Rect = Rect { x: 116.6, y: 304.0, w: 93.6, h: 19.0 };
+
 // card
 pub const CARD_V_WIDTH: f64 = 22.0;
 pub const CARD_V_HEIGHT: f64 = 40.0;
@@ -79,11 +117,14 @@ pub const CARD_ICON_WIDTH:  f64 = 15.0;
 pub const CARD_ICON_HEIGHT: f64 = 15.0;
 
 // desk
-pub const DESK_HOLD_NUM: [Rect; 4] = [
+pub const DESK_HOLD_NUM: [Rect; 7] = [
     Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
     Rect { x: 612.0, y: 163.4, w: 93.6, h: 19.0 },
     Rect { x: 262.4, y: 18.4,  w: 93.6, h: 19.0 },
     Rect { x: 7.2,   y: 182.4, w: 93.6, h: 19.0 },
+    Rect { x: 218.0, y: 18.4,  w: 76.0, h: 19.0 },
+    Rect { x: 408.0, y: 18.4,  w: 76.0, h: 19.0 },
+    Rect { x: 598.0, y: 18.4,  w: 76.0, h: 19.0 },
 ];
 pub const DESK_MY_HOLD_BORDER: Rect = Rect { x: 532.8, y: 247.0, w: 180.0, h: 125.4 };
 pub const DESK_MY_HOLD_TITLE:  Rect = Rect { x: 532.8, y: 249.5, w: 180.0, h: 23.8 };
@@ -103,11 +144,14 @@ pub const DESK_CHAIN: [Rect; 4] = [
 ];
 
 // game result
-pub const RESULT_HOLD_POINTS: [Rect; 4] = [
+pub const RESULT_HOLD_POINTS: [Rect; 7] = [
     Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
     Rect { x: 610.8, y: 190.0, w: 96.0, h: 19.0 },
     Rect { x: 261.2, y: 45.4,  w: 96.0, h: 19.0 },
     Rect { x: 6.0,   y: 209.0, w: 96.0, h: 19.0 },
+    Rect { x: 218.0, y: 45.4,  w: 76.0, h: 19.0 },
+    Rect { x: 408.0, y: 45.4,  w: 76.0, h: 19.0 },
+    Rect { x: 598.0, y: 45.4,  w: 76.0, h: 19.0 },
 ];
 pub const RESULT_CONTINUE_BUTTON: Rect = Rect { x: 140.0, y: 301.6, w: 115.2, h: 38.0 };
 pub const RESULT_MSG: Rect = Rect { x: 268.0, y: 311.6, w: 250.0, h: 20.0 };
@@ -119,4 +163,73 @@ pub const RESULT_HOLD_RIGHT_START: Rect = Rect { x: 576.0, y: 95.0,  w: 22.0, h:
 pub const RESULT_HOLD_LEFT_EMPTY:  Rect = Rect { x: 118.0, y: 154.0, w: 85.0, h: 20.0 };
 pub const RESULT_HOLD_TOP_EMPTY:   Rect = Rect { x: 370.2, y: 30.0,  w: 85.0, h: 20.0 };
 pub const RESULT_HOLD_RIGHT_EMPTY: Rect = Rect { x: 496.0, y: 145.0,  w: 85.0, h: 20.0 };
+// compact top ones, overlapped cards under the name
+pub const RESULT_HOLD_COMPACT_GAP_WIDTH: f64 = 8.0;
+pub const RESULT_HOLD_COMPACT_START: [Rect; 7] = [
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x: 140.0, y: 62.0,  w: 22.0, h: 40.0 },
+    Rect { x: 330.0, y: 62.0,  w: 22.0, h: 40.0 },
+    Rect { x: 520.0, y: 62.0,  w: 22.0, h: 40.0 },
+];
+pub const RESULT_HOLD_COMPACT_EMPTY: [Rect; 7] = [
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x:   0.0, y:   0.0, w:  0.0, h:  0.0 },
+    Rect { x: 133.5, y: 62.0,  w: 85.0, h: 20.0 },
+    Rect { x: 323.5, y: 62.0,  w: 85.0, h: 20.0 },
+    Rect { x: 513.5, y: 62.0,  w: 85.0, h: 20.0 },
+];
 pub const RESULT_MSG_FMT_STRLEN: [f64; 3] = [90.7, 101.4, 193.0];
+
+// scoreboard, sharing the continue button and msg of the game result
+pub const SCORE_BOARD:        Rect = Rect { x: 140.0, y: 40.0,  w: 440.0, h: 256.0 };
+pub const SCORE_NAME_START:   Rect = Rect { x: 150.0, y: 52.0,  w: 120.0, h: 22.0 };
+pub const SCORE_CELL_WIDTH:   f64 = 40.0;
+pub const SCORE_TOTAL_WIDTH:  f64 = 60.0;
+pub const SCORE_SHOWN_HANDS:  usize = 6;
+pub const SCORE_CONFIG:       Rect = Rect { x: 140.0, y: 216.0, w: 440.0, h: 76.0 };
+
+// chat, the button below my seat and the window over the right side
+pub const CHAT_BUTTON:        Rect = Rect { x: 10.0,  y: 348.0, w: 60.0,  h: 25.0 };
+pub const CHAT_WINDOW:        Rect = Rect { x: 392.0, y: 92.0,  w: 318.0, h: 278.0 };
+pub const CHAT_LINE_START:    Rect = Rect { x: 402.0, y: 100.0, w: 298.0, h: 18.0 };
+pub const CHAT_LINES:         usize = 10;
+pub const CHAT_INPUT:         Rect = Rect { x: 402.0, y: 290.0, w: 220.0, h: 30.0 };
+pub const CHAT_INPUT_TEXT:    Rect = Rect { x: 407.0, y: 297.0, w: 210.0, h: 16.0 };
+pub const CHAT_SEND_BUTTON:   Rect = Rect { x: 630.0, y: 290.0, w: 70.0,  h: 30.0 };
+pub const CHAT_OLDER_BUTTON:  Rect = Rect { x: 402.0, y: 334.0, w: 70.0,  h: 25.0 };
+pub const CHAT_NEWER_BUTTON:  Rect = Rect { x: 480.0, y: 334.0, w: 70.0,  h: 25.0 };
+pub const CHAT_CLOSE_BUTTON:  Rect = Rect { x: 630.0, y: 334.0, w: 70.0,  h: 25.0 };
+
+// reactions, the button next to chat and the picker over my seat
+pub const REACT_BUTTON:       Rect = Rect { x: 76.0,  y: 348.0, w: 46.0,  h: 25.0 };
+pub const REACT_PICK: [Rect; 4] = [
+    Rect { x: 10.0,  y: 221.0, w: 112.0, h: 25.0 },
+    Rect { x: 10.0,  y: 252.0, w: 112.0, h: 25.0 },
+    Rect { x: 10.0,  y: 283.0, w: 112.0, h: 25.0 },
+    Rect { x: 10.0,  y: 314.0, w: 112.0, h: 25.0 },
+];
+
+// host menu, over the middle with arrows to pick the player to kick or make host
+pub const HOST_MENU_WINDOW:   Rect = Rect { x: 230.0, y: 30.0,  w: 260.0, h: 320.0 };
+pub const HOST_MENU_TITLE:    Rect = Rect { x: 230.0, y: 40.0,  w: 260.0, h: 20.0 };
+pub const HOST_MENU_BUTTON: [Rect; HOST_MENU_ITEMS] = [
+    Rect { x: 280.0, y: 72.0,  w: 160.0, h: 30.0 },
+    Rect { x: 280.0, y: 118.0, w: 160.0, h: 30.0 },
+    Rect { x: 280.0, y: 164.0, w: 160.0, h: 30.0 },
+    Rect { x: 280.0, y: 210.0, w: 160.0, h: 30.0 },
+    Rect { x: 280.0, y: 256.0, w: 160.0, h: 30.0 },
+];
+pub const HOST_MENU_PREV: [Rect; 2] = [
+    Rect { x: 242.0, y: 210.0, w: 30.0,  h: 30.0 },
+    Rect { x: 242.0, y: 256.0, w: 30.0,  h: 30.0 },
+];
+pub const HOST_MENU_NEXT: [Rect; 2] = [
+    Rect { x: 448.0, y: 210.0, w: 30.0,  h: 30.0 },
+    Rect { x: 448.0, y: 256.0, w: 30.0,  h: 30.0 },
+];
+pub const HOST_MENU_MSG:      Rect = Rect { x: 240.0, y: 302.0, w: 240.0, h: 30.0 };
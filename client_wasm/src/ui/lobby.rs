@@ -0,0 +1,27 @@
+use super::*;
+
+// scroll the list so that the chosen row is always visible
+pub fn lobby_first_row(choose: usize) -> usize {
+    (choose + 1).max(LOBBY_ROWS) - LOBBY_ROWS
+}
+
+pub fn lobby_row_rect(i: usize) -> Rect {
+    let mut r = LOBBY_ROW_START.clone();
+    r.y += (r.h + LOBBY_ROW_GAP) * i as f64;
+    r
+}
+
+pub fn ui_lobby(rooms: Vec<RoomSummary>, choose: usize, msg: String) {
+    draw_rounded_rect(&PROMPT_WINDOW, BORDER_NORMAL);
+
+    draw_paragraph(&PROMPT_MSG, &msg);
+
+    // the last row is for entering room ID by hand
+    let first = lobby_first_row(choose);
+    rooms.iter().map(lobby_row)
+        .chain(std::iter::once("Enter Room ID......".to_string()))
+        .enumerate()
+        .skip(first)
+        .take(LOBBY_ROWS)
+        .for_each(|(i, r)| draw_button(&lobby_row_rect(i - first), &r, i == choose));
+}
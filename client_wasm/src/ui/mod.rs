@@ -1,6 +1,7 @@
 mod color;
 mod utils;
 mod prompt_win;
+mod lobby;
 mod wait;
 mod gaming;
 mod game_result;
@@ -10,6 +11,11 @@ mod exit_menu;
 mod layout;
 mod players;
 mod common;
+mod spectate;
+mod scoreboard;
+mod chat;
+mod reaction;
+mod host_menu;
 
 use crate::*;
 pub(crate) use color::*;
@@ -18,6 +24,7 @@ pub(crate) use Slice1D::*;
 pub(crate) use desk::*;
 pub(crate) use card::*;
 pub(crate) use prompt_win::*;
+pub(crate) use lobby::*;
 pub(crate) use wait::*;
 pub(crate) use gaming::*;
 pub(crate) use game_result::*;
@@ -25,13 +32,19 @@ pub(crate) use exit_menu::*;
 pub(crate) use layout::*;
 pub(crate) use players::*;
 pub(crate) use common::*;
+pub(crate) use spectate::*;
+pub(crate) use scoreboard::*;
+pub(crate) use chat::*;
+pub(crate) use reaction::*;
+pub(crate) use host_menu::*;
 
 fn draw_normal(cs: ClientState) -> JsResult<()> {
     ui_esc_button();
 
     if cs.exitmenu.0 {
-        ui_exit_menu(get_button_num(&cs));
+        ui_exit_menu(get_button_num(&cs), cs.is_host);
     } else {
+        let gaming = matches!(cs.fsm, ClientStateMachine::Gaming {..});
         match cs.fsm {
             ClientStateMachine::GetServer {connecting, input, msg} => {
                 hidden_input_set_value(input.value());
@@ -41,20 +54,22 @@ fn draw_normal(cs: ClientState) -> JsResult<()> {
                 hidden_input_set_value(input.value());
                 ui_ask_name(input, msg, is_input);
             }
-            ClientStateMachine::NewRoom { input, msg, ..} => {
+            ClientStateMachine::NewRoom { input, masked, msg, ..} => {
                 hidden_input_set_value(input.value());
-                ui_new_room(input, msg);
+                ui_new_room(input, masked, msg);
             }
-            ClientStateMachine::JoinRoom {input, msg, ..} => {
+            ClientStateMachine::Lobby {rooms, choose, msg, ..}
+                => ui_lobby(rooms, choose, msg),
+            ClientStateMachine::JoinRoom {input, masked, msg, ..} => {
                 hidden_input_set_value(input.value());
-                ui_join_room(input, msg);
+                ui_join_room(input, masked, msg);
             }
             ClientStateMachine::WaitPlayer {players, msg, roomid, ..}
                 => ui_wait_player(players, msg, roomid),
-            ClientStateMachine::WaitReady {players, msg, roomid, ..}
-                => ui_wait_ready(players, msg, roomid),
+            ClientStateMachine::WaitReady {players, msg, roomid, rules, profiles}
+                => ui_wait_ready(players, msg, roomid, rules, profiles),
             ClientStateMachine::Gaming {
-                choose, mut game, roomid, msg, ..
+                choose, mut game, roomid, msg, clock, ..
             } => {
                 let names = game.get_player_names();
                 let hold_nums = game.get_hold_nums();
@@ -67,9 +82,11 @@ fn draw_normal(cs: ClientState) -> JsResult<()> {
                 let desk = game.export_desk();
 
                 ui_gaming(names, hold_nums, next, roomid, choose, last,
-                    my_cards, my_holds, hints, desk, has_done, msg
+                    my_cards, my_holds, hints, desk, has_done, msg, clock
                 );
             }
+            ClientStateMachine::GameResult {players, roomid, scoreboard: Some(mi), ..}
+                => ui_scoreboard(players.into_iter().map(|(n, _)| n).collect(), roomid, mi),
             ClientStateMachine::GameResult {ds, players, roomid, winner, winner_state, ..} => {
                 let names = players.iter().map(|(n, _)| n.clone()).collect();
                 let holds = players.into_iter().map(|(_, h)| h).collect();
@@ -78,6 +95,30 @@ fn draw_normal(cs: ClientState) -> JsResult<()> {
                 }).collect();
                 ui_game_result(desk, names, holds, roomid, winner, winner_state);
             }
+            ClientStateMachine::Spectating {game: Some(game), roomid, msg, clock} => {
+                let names = game.get_player_names();
+                let hold_nums = game.get_hold_nums();
+                let next = game.get_next();
+                let last = game.get_last();
+                let has_done = game.has_done();
+                let desk = game.export_desk();
+
+                ui_spectate(names, hold_nums, next, roomid, last, desk, has_done, msg, clock);
+            }
+            ClientStateMachine::Spectating {game: None, roomid, msg, ..}
+                => ui_spectate_waiting(roomid, msg),
+        }
+
+        ui_bubbles(&cs.bubbles);
+        if gaming {
+            ui_reactions(cs.picking_reaction);
+        }
+        // over the state, the window may cover part of it
+        if let Some(chat) = cs.chat {
+            ui_chat(chat);
+        }
+        if let Some(menu) = cs.host_menu {
+            ui_host_menu(menu);
         }
     }
 
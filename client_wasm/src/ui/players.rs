@@ -23,48 +23,23 @@ pub fn ui_one_player(mut r: Rect, name: &str) {
 }
 
 pub fn ui_players(names: Vec<String>) {
-    // myself
-    // let r = get_canvas_rect().cut_height([
-    //     Percent(75),
-    //     Percent(15),
-    // ])[1].cut_width([
-    //     Percent(5),
-    //     Percent(10),
-    // ])[1].clone();
-    // warn!("{:?}", r);
-    ui_one_player(PLAYER_MYSELF.clone(), &names[0]);
-
-    // right
-    // let r = get_canvas_rect().cut_height([
-    //     Percent(25),
-    //     Percent(12),
-    // ])[1].cut_width([
-    //     Percent(87),
-    //     Percent(10),
-    //     Percent(3),
-    // ])[1].clone();
-    // warn!("{:?}", r);
-    ui_one_player(PLAYER_RIGHT.clone(), &names[1]);
-
-    // top
-    // let r = get_canvas_rect().cut_height([
-    //     Percent(3),
-    //     Percent(12),
-    // ])[1].cut_width([
-    //     Percent(30),
-    //     Percent(10),
-    // ])[1].clone();
-    // warn!("{:?}", r);
-    ui_one_player(PLAYER_TOP.clone(), &names[2]);
+    for (name, pos) in names.iter().zip(seat_positions(names.len())) {
+        ui_one_player(PLAYER[*pos as usize].clone(), name);
+    }
+}
 
-    // left
-    // let r = get_canvas_rect().cut_height([
-    //     Percent(30),
-    //     Percent(12),
-    // ])[1].cut_width([
-    //     Percent(3),
-    //     Percent(10),
-    // ])[1].clone();
-    // warn!("{:?}", r);
-    ui_one_player(PLAYER_LEFT.clone(), &names[3]);
+pub fn ui_bubbles(bubbles: &[Option<Reaction>]) {
+    if bubbles.is_empty() {
+        return;
+    }
+    set_font_small();
+    for (b, pos) in bubbles.iter().zip(seat_positions(bubbles.len())) {
+        if let Some(r) = b {
+            let rect = &BUBBLE[*pos as usize];
+            clear_rect(rect);
+            draw_rounded_rect(rect, BUBBLE_BORDER);
+            draw_text_oneline_center_color(rect, r.text(), BUBBLE_TEXT);
+        }
+    }
+    set_font_normal();
 }
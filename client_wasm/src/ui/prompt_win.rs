@@ -123,20 +123,22 @@ pub fn ui_ask_name(input: Input, msg: String, is_input: bool) {
     );
 }
 
-pub fn ui_new_room(input: Input, msg: String) {
+pub fn ui_new_room(input: Input, masked: bool, msg: String) {
+    let (input, title) = if masked { (input.masked(), "Password") } else { (input, "Room ID") };
     ui_prompt_window(
-        input.into(),
-        "Room ID".into(),
+        input,
+        title.into(),
         msg,
         true,
         [("Create Room!".into(), true)].to_vec(),
     );
 }
 
-pub fn ui_join_room(input: Input, msg: String) {
+pub fn ui_join_room(input: Input, masked: bool, msg: String) {
+    let (input, title) = if masked { (input.masked(), "Password") } else { (input, "Room ID") };
     ui_prompt_window(
-        input.into(),
-        "Room ID".into(),
+        input,
+        title.into(),
         msg,
         true,
         [("Join Room!".into(), true)].to_vec(),
@@ -0,0 +1,15 @@
+use super::*;
+
+pub fn ui_reactions(picking: bool) {
+    draw_button(&REACT_BUTTON, "React", !picking);
+    if !picking {
+        return;
+    }
+
+    set_font_small();
+    for (r, rect) in Reaction::ALL.iter().zip(REACT_PICK.iter()) {
+        clear_rect(rect);
+        draw_button(rect, r.text(), true);
+    }
+    set_font_normal();
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+// one row of the table, name first and TOTAL last
+fn ui_score_row(row: usize, name: &str, cells: Vec<String>, total: String, color: &str) {
+    let mut r = SCORE_NAME_START.clone();
+    r.y += r.h * row as f64;
+    draw_text_oneline_center_color(&r, name, color);
+
+    r.x += r.w;
+    r.w = SCORE_CELL_WIDTH;
+    for c in cells {
+        draw_text_oneline_center_color(&r, &c, color);
+        r.x += r.w;
+    }
+
+    r.w = SCORE_TOTAL_WIDTH;
+    draw_text_oneline_center_color(&r, &total, color);
+}
+
+fn ui_score_table(names: &Vec<String>, mi: &MatchInfo) {
+    draw_rounded_rect(&SCORE_BOARD, BORDER_NORMAL);
+
+    // older hands only count in TOTAL
+    let first = mi.history.len().saturating_sub(SCORE_SHOWN_HANDS);
+    let shown = &mi.history[first..];
+
+    ui_score_row(0, "PLAYER",
+        (first..mi.history.len()).map(|i| format!("H{}", i + 1)).collect(),
+        "TOTAL".into(), RESULT_MSG_GREY
+    );
+    for (pid, name) in names.iter().enumerate() {
+        let color = if mi.leaders.contains(&(pid as u32)) { READY } else { TEXT_NORMAL };
+        ui_score_row(pid + 1, &fit_in_name(SCORE_NAME_START.w, name.clone()),
+            shown.iter().map(
+                |hs| hs.points.get(pid).copied().unwrap_or_default().to_string()
+            ).collect(),
+            mi.totals.get(pid).copied().unwrap_or_default().to_string(), color
+        );
+    }
+
+    let mut config = mi.get_config().describe();
+    config.insert(0, format!("{} hands played", mi.hands));
+    draw_paragraph_vec(&SCORE_CONFIG, config);
+}
+
+pub fn ui_scoreboard(names: Vec<String>, roomid: String, mi: MatchInfo) {
+    ui_room_id(roomid);
+
+    draw_button(&RESULT_CONTINUE_BUTTON, "Continue", true);
+
+    ui_score_table(&names, &mi);
+
+    let leaders: Vec<String> = mi.leaders.iter().map(
+        |&l| names.get(l as usize).cloned().unwrap_or_default()
+    ).collect();
+    let (msg, color) = if !mi.over {
+        (format!("Leading: {}", fit_in_name(RESULT_MSG.w - 60.0, leaders.join(", "))),
+            RESULT_MSG_GREY)
+    } else if mi.leaders.contains(&0) {
+        ("You win the match!".into(), RESULT_MSG_WIN)
+    } else {
+        (format!("{} wins the match.", fit_in_name(RESULT_MSG.w - 120.0, leaders.join(", "))),
+            RESULT_MSG_LOSE)
+    };
+    draw_text_oneline_center_color(&RESULT_MSG, &msg, color);
+}
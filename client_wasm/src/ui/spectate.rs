@@ -0,0 +1,41 @@
+use super::*;
+
+pub fn ui_spectate(
+    names: Vec<String>, hold_nums: Vec<u32>, next: usize,
+    roomid: String, last: Option<(usize, Option<Card>)>,
+    desk: Vec<Vec<Card>>, has_done: bool, msg: Option<String>,
+    clock: Option<TurnClock>
+) {
+    ui_room_id(roomid);
+
+    ui_players(names.clone());
+
+    // no hand to show for seat 0, only its hold count
+    draw_text_oneline_center_color(
+        &SPECTATE_HOLD_NUM,
+        &format!("HOLD: {}", hold_nums[0]),
+        HOLD_NUM,
+    );
+    ui_desk_hold_num(hold_nums);
+
+    if let Some(m) = msg {
+        ui_gameing_msg(m);
+    } else if !has_done {
+        match clock.filter(|tc| tc.player == next) {
+            Some(tc) => ui_gameing_msg(format!("Waiting for {}...... {}s", names[next], tc.secs)),
+            None => ui_gameing_msg(format!("Waiting for {}......", names[next])),
+        }
+    }
+
+    if let Some((who, last)) = last {
+        ui_gaming_last(last, seat_pos(names.len(), who));
+    }
+
+    ui_desk(desk);
+}
+
+pub fn ui_spectate_waiting(roomid: String, msg: Option<String>) {
+    ui_room_id(roomid);
+
+    ui_center_msg(vec!["Spectating".into(), msg.unwrap_or_default()]);
+}
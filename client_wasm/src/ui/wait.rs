@@ -1,13 +1,13 @@
 use super::*;
 
-fn ui_center_msg(msg: Vec<String>) {
+pub(crate) fn ui_center_msg(msg: Vec<String>) {
     // let r = get_canvas_rect().center_cut(Percent(50), Percent(15));
     // draw_rect(&r, BORDER_NORMAL);
     // warn!("{:?}", r);
     draw_paragraph_vec(&WAIT_CENTER_MSG, msg);
 }
 
-fn ui_ready_button(active: bool) {
+fn ui_wait_button(text: &str) {
     // let r = get_canvas_rect().center_cut_width(Percent(18));
     // let slices = r.cut_height([
     //     Percent(80),
@@ -16,7 +16,7 @@ fn ui_ready_button(active: bool) {
     // ]);
     // warn!("{:?}", &slices[1]);
 
-    draw_button(&WAIT_READY_BUTTON, "Get Ready!", active);
+    draw_button(&WAIT_READY_BUTTON, text, true);
 }
 
 pub fn ui_wait_player(
@@ -29,7 +29,7 @@ pub fn ui_wait_player(
 
     ui_players(players.iter().map(|p| p.0.clone()).collect());
 
-    ui_ready_button(false);
+    ui_wait_button("Add Bot!");
 }
 
 fn ui_players_ready(ready: Vec<bool>) {
@@ -56,27 +56,45 @@ fn ui_players_ready(ready: Vec<bool>) {
     // let r = vec![myself_r, right_r, top_r, left_r];
     // warn!("{:?}", r);
 
-    let r = &WAIT_PLAYER_READY;
-    ready.into_iter().zip(r.into_iter()).for_each(
-        |(ready, r)| if ready {
-            draw_text_oneline_center_color(r, "READY!", READY);
+    let seats = ready.len();
+    ready.into_iter().zip(seat_positions(seats)).for_each(
+        |(ready, pos)| if ready {
+            draw_text_oneline_center_color(&WAIT_PLAYER_READY[*pos as usize], "READY!", READY);
         }
     )
 }
 
+// two players a line, only names and ratings fit
+fn ui_profiles(profiles: Vec<Profile>) {
+    if profiles.is_empty() {
+        return;
+    }
+
+    let ratings: Vec<String> = profiles.into_iter().map(
+        |p| format!("{} {:.0}", fit_in_name(WAIT_PROFILE_NAME_WIDTH, p.name), p.rating)
+    ).collect();
+    draw_paragraph_vec(&WAIT_PROFILES,
+        ratings.chunks(2).map(|c| c.join("      ")).collect()
+    );
+}
+
 pub fn ui_wait_ready(
     players: Vec<(String, usize, bool)>,
-    msg: Vec<String>, roomid: String,
+    msg: Vec<String>, roomid: String, rules: RuleSet, profiles: Vec<Profile>,
 ) {
     ui_room_id(roomid);
 
     ui_center_msg(msg);
 
+    draw_paragraph_vec(&WAIT_RULES, rules.describe());
+
+    ui_profiles(profiles);
+
     ui_players(players.iter().map(|p| p.0.clone()).collect());
 
     ui_players_ready(players.iter().map(|p| p.2.clone()).collect());
 
     if !players[0].2 {
-        ui_ready_button(true);
+        ui_wait_button("Get Ready!");
     }
 }
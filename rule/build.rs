@@ -1,4 +1,16 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_build::configure().compile_protos(&["../proto/heart7.proto"], &[".."])?;
+    let serde = "#[cfg_attr(feature = \"serde\", derive(serde::Serialize, serde::Deserialize))]";
+    tonic_build::configure()
+        // Card holds a CardSuit
+        .type_attribute("heart7_rpc.CardSuit", serde)
+        // GameEnding is kept in a GameRecord
+        .type_attribute("heart7_rpc.GameEnding", serde)
+        .type_attribute("heart7_rpc.DeskResult", serde)
+        .type_attribute("heart7_rpc.CardResult", serde)
+        .type_attribute("heart7_rpc.HoldList", serde)
+        .type_attribute("heart7_rpc.CardInfo", serde)
+        // and so is the RuleSet it was played under
+        .type_attribute("heart7_rpc.RuleSet", serde)
+        .compile_protos(&["../proto/heart7.proto"], &[".."])?;
     Ok(())
 }
@@ -0,0 +1,123 @@
+use crate::*;
+use super::desk::Desk;
+use rand::{SeedableRng, rngs::SmallRng, seq::IndexedRandom};
+
+// everything one player is allowed to know about the game
+#[derive(Debug, Clone)]
+pub struct PlayerView {
+    pub pid: usize,
+    pub cards: Vec<Card>,
+    pub holds: Vec<Card>,
+    pub desk: Desk,
+    pub hold_nums: Vec<u32>,
+    pub thisround: Vec<(Card, usize)>,
+    pub first_hold: bool,
+    pub someone_has_clear: bool,
+    pub rules: RuleSet,
+}
+
+impl PlayerView {
+    // same rules as Game::check_play, assuming it is our turn
+    pub fn legal_plays(&self) -> Vec<Play> {
+        let playable: Vec<&Card> = self.cards.iter().filter(
+            |c| !self.holds.contains(c)
+        ).collect();
+
+        if !self.someone_has_clear || self.rules.play_on_after_clear {
+            let cand = self.desk.get_discard_candidates();
+            let discards: Vec<Play> = playable.iter().filter(
+                |c| cand.contains(c)
+            ).map(
+                |c| Play::Discard((*c).clone(), self.pid)
+            ).collect();
+            if !discards.is_empty() {
+                return discards;
+            }
+        }
+
+        playable.into_iter().filter(
+            |c| !(self.first_hold && !self.rules.ace_first_hold && c.num == 1)
+        ).map(
+            |c| Play::Hold(c.clone(), self.pid)
+        ).collect()
+    }
+}
+
+pub trait Strategy: Send {
+    // None only if there is nothing legal to play
+    fn choose_play(&mut self, view: &PlayerView) -> Option<Play>;
+}
+
+pub struct RandomBot {
+    rng: SmallRng,
+}
+
+impl RandomBot {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomBot {
+    fn choose_play(&mut self, view: &PlayerView) -> Option<Play> {
+        view.legal_plays().choose(&mut self.rng).cloned()
+    }
+}
+
+#[derive(Default)]
+pub struct HeuristicBot;
+
+impl HeuristicBot {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // cards of the same suit that can only be played after c
+    fn is_beyond(c: &Card, other: &Card) -> bool {
+        if c.suit != other.suit || c == other {
+            return false;
+        }
+        match c.num {
+            7 => true,
+            n if n < 7 => other.num < n,
+            n => other.num > n,
+        }
+    }
+
+    // prefer discards that open the way for our own cards,
+    // and avoid opening long runs for the others
+    fn discard_score(view: &PlayerView, c: &Card) -> i32 {
+        let on_desk = view.desk.export();
+        let mine = view.cards.iter().filter(
+            |o| Self::is_beyond(c, o)
+        ).count() as i32;
+        let played = on_desk.iter().flatten().filter(
+            |o| Self::is_beyond(c, o)
+        ).count() as i32;
+        let total = match c.num {
+            7 => 12,
+            n if n < 7 => n as i32 - 1,
+            n => 13 - n as i32,
+        };
+        let others = total - mine - played;
+
+        2 * mine - others
+    }
+}
+
+impl Strategy for HeuristicBot {
+    fn choose_play(&mut self, view: &PlayerView) -> Option<Play> {
+        let plays = view.legal_plays();
+        match plays.first()? {
+            Play::Discard(..) => plays.into_iter().max_by_key(
+                |p| Self::discard_score(view, &p.clone().split().1)
+            ),
+            // holding costs its number, so hold the smallest one
+            Play::Hold(..) => plays.into_iter().min_by_key(
+                |p| p.clone().split().1.num
+            ),
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use crate::*;
+use rand::{Rng, SeedableRng, rngs::SmallRng, seq::SliceRandom};
+use std::fmt::Debug;
+
+pub const DECK_SIZE: usize = 52;
+
+pub const MIN_SEATS: usize = 3;
+pub const MAX_SEATS: usize = 6;
+pub const DEFAULT_SEATS: usize = 4;
+
+// whoever holds it goes first
+pub const HEART_SEVEN: u32 = 19;
+
+// kings stripped at 5 and 6 seats so that everyone gets the same
+const STRIPPED_KINGS: [&[u32]; MAX_SEATS + 1] = [&[], &[], &[], &[], &[], &[38, 51], &[12, 25, 38, 51]];
+
+pub fn check_seats(seats: usize) -> GameResult<()> {
+    if !(MIN_SEATS..=MAX_SEATS).contains(&seats) {
+        return Err(GameError::PermissionDenied(
+            format!("A table seats {} to {} players, not {}!", MIN_SEATS, MAX_SEATS, seats)
+        ))
+    }
+    Ok(())
+}
+
+// every card played at a table of `seats`, in order
+pub fn deck_for(seats: usize) -> Vec<u32> {
+    (0..DECK_SIZE as u32).filter(
+        |c| !STRIPPED_KINGS[seats].contains(c)
+    ).collect()
+}
+
+pub fn deck_size(seats: usize) -> usize {
+    DECK_SIZE - STRIPPED_KINGS[seats].len()
+}
+
+// cards each seat gets, the first seats get one more when it doesn't split evenly
+pub fn hand_sizes(seats: usize) -> Vec<usize> {
+    let (each, more) = (deck_size(seats) / seats, deck_size(seats) % seats);
+    (0..seats).map(|pid| each + (pid < more) as usize).collect()
+}
+
+// a deck is every card of deck_for(seats) exactly once, dealt to each seat in order
+pub fn check_deck(deck: &[u32], seats: usize) -> GameResult<()> {
+    check_seats(seats)?;
+    let size = deck_size(seats);
+    if deck.len() != size {
+        return Err(GameError::PermissionDenied(
+            format!("A deck for {} players has {} cards, not {}!", seats, size, deck.len())
+        ))
+    }
+
+    let mut seen = [false; DECK_SIZE];
+    for &c in deck {
+        if STRIPPED_KINGS[seats].contains(&c) {
+            return Err(GameError::PermissionDenied(
+                format!("Card {} is not in a deck for {} players!", c, seats)
+            ))
+        }
+        match seen.get_mut(c as usize) {
+            None => return Err(GameError::PermissionDenied(
+                format!("Card {} is not in a deck!", c)
+            )),
+            Some(true) => return Err(GameError::PermissionDenied(
+                format!("Card {} is dealt twice!", c)
+            )),
+            Some(s) => *s = true,
+        }
+    }
+
+    Ok(())
+}
+
+// where the deck of each hand comes from
+pub trait DealSource: Send + Sync + Debug {
+    fn next_deck(&mut self, seats: usize) -> Vec<u32>;
+}
+
+// shuffled by any RNG, a seeded one gives the same hands every time
+#[derive(Debug)]
+pub struct RandomDeal<R> {
+    rng: R,
+}
+
+impl<R: Rng + Send + Sync + Debug> RandomDeal<R> {
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl RandomDeal<SmallRng> {
+    pub fn seeded(seed: u64) -> Self {
+        Self::new(SmallRng::seed_from_u64(seed))
+    }
+}
+
+impl<R: Rng + Send + Sync + Debug> DealSource for RandomDeal<R> {
+    fn next_deck(&mut self, seats: usize) -> Vec<u32> {
+        let mut deck = deck_for(seats);
+        deck.shuffle(&mut self.rng);
+        deck
+    }
+}
+
+// the same decks in turn, for puzzles and tests
+#[derive(Debug)]
+pub struct FixedDeal {
+    decks: Vec<Vec<u32>>,
+    next: usize,
+}
+
+impl FixedDeal {
+    pub fn new(decks: Vec<Vec<u32>>, seats: usize) -> GameResult<Self> {
+        if decks.is_empty() {
+            return Err(GameError::PermissionDenied("No deck to deal!".into()))
+        }
+        for deck in &decks {
+            check_deck(deck, seats)?;
+        }
+
+        Ok(Self { decks, next: 0 })
+    }
+}
+
+impl DealSource for FixedDeal {
+    // checked for its table when made
+    fn next_deck(&mut self, _seats: usize) -> Vec<u32> {
+        let deck = self.decks[self.next].clone();
+        self.next = (self.next + 1) % self.decks.len();
+        deck
+    }
+}
+
+// hands out a checked deck for every hand
+#[derive(Debug)]
+pub struct Dealer {
+    source: Box<dyn DealSource>,
+}
+
+impl Dealer {
+    pub fn new(source: impl DealSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+        }
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Self::new(RandomDeal::seeded(seed))
+    }
+
+    pub fn fixed(decks: Vec<Vec<u32>>, seats: usize) -> GameResult<Self> {
+        Ok(Self::new(FixedDeal::new(decks, seats)?))
+    }
+
+    pub fn deal(&mut self, seats: usize) -> GameResult<Vec<u32>> {
+        let deck = self.source.next_deck(seats);
+        check_deck(&deck, seats)?;
+        Ok(deck)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hand_sizes_cover_the_deck() {
+        assert_eq!(hand_sizes(3), vec![18, 17, 17]);
+        assert_eq!(hand_sizes(4), vec![13; 4]);
+        assert_eq!(hand_sizes(5), vec![10; 5]);
+        assert_eq!(hand_sizes(6), vec![8; 6]);
+        for seats in MIN_SEATS..=MAX_SEATS {
+            assert_eq!(hand_sizes(seats).iter().sum::<usize>(), deck_size(seats));
+            assert_eq!(deck_for(seats).len(), deck_size(seats));
+        }
+    }
+
+    #[test]
+    fn kings_are_stripped_at_big_tables() {
+        for seats in MIN_SEATS..=DEFAULT_SEATS {
+            assert_eq!(deck_for(seats), (0..DECK_SIZE as u32).collect::<Vec<_>>());
+        }
+        let five = deck_for(5);
+        assert!(!five.contains(&38) && !five.contains(&51));
+        assert!(five.contains(&12) && five.contains(&25));
+        let six = deck_for(6);
+        assert!([12, 25, 38, 51].iter().all(|k| !six.contains(k)));
+        // never the heart seven, who goes first
+        assert!((MIN_SEATS..=MAX_SEATS).all(|seats| deck_for(seats).contains(&HEART_SEVEN)));
+    }
+
+    #[test]
+    fn check_deck_refuses_bad_decks() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            assert!(check_deck(&deck_for(seats), seats).is_ok());
+        }
+        assert!(check_deck(&deck_for(4), 2).is_err());
+        assert!(check_deck(&deck_for(4), 7).is_err());
+
+        let mut short = deck_for(4);
+        short.pop();
+        assert!(check_deck(&short, 4).is_err());
+
+        let mut twice = deck_for(4);
+        twice[1] = twice[0];
+        assert!(check_deck(&twice, 4).is_err());
+
+        let mut outside = deck_for(4);
+        outside[0] = DECK_SIZE as u32;
+        assert!(check_deck(&outside, 4).is_err());
+
+        // a full deck has the stripped kings
+        assert!(check_deck(&deck_for(4)[..deck_size(5)], 5).is_err());
+        let mut king = deck_for(5);
+        king[0] = 51;
+        assert!(check_deck(&king, 5).is_err());
+    }
+
+    #[test]
+    fn fixed_deal_checks_and_repeats() {
+        assert!(FixedDeal::new(vec![], 4).is_err());
+        assert!(FixedDeal::new(vec![deck_for(4)], 5).is_err());
+        assert!(FixedDeal::new(vec![deck_for(5), deck_for(4)], 5).is_err());
+
+        let mut backwards = deck_for(4);
+        backwards.reverse();
+        let mut dealer = Dealer::fixed(vec![deck_for(4), backwards.clone()], 4).unwrap();
+        assert_eq!(dealer.deal(4).unwrap(), deck_for(4));
+        assert_eq!(dealer.deal(4).unwrap(), backwards);
+        assert_eq!(dealer.deal(4).unwrap(), deck_for(4));
+        // checked again for the table it is dealt to
+        assert!(dealer.deal(5).is_err());
+    }
+
+    #[test]
+    fn same_seed_same_deal() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            let mut a = Dealer::seeded(7);
+            let mut b = Dealer::seeded(7);
+            let mut c = Dealer::seeded(8);
+            for _ in 0..3 {
+                let deck = a.deal(seats).unwrap();
+                assert_eq!(deck, b.deal(seats).unwrap());
+                assert_ne!(deck, c.deal(seats).unwrap());
+                assert!(check_deck(&deck, seats).is_ok());
+            }
+        }
+    }
+}
@@ -4,11 +4,14 @@ use std::collections::{VecDeque, HashSet};
 pub type ChainType = VecDeque<(Card, usize)>;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Desk {
     spade:   ChainType,
     heart:   ChainType,
     club:    ChainType,
     diamond: ChainType,
+    // only a cache, computed again when needed
+    #[cfg_attr(feature = "serde", serde(skip))]
     cand: Option<HashSet<Card>>,
 }
 
@@ -94,23 +97,35 @@ impl Desk {
         }
     }
 
+    fn compute_discard_candidates(&self) -> HashSet<Card> {
+        let mut cand = HashSet::new();
+        if self.is_empty() {
+            cand.insert(Card{ suit: CardSuit::Heart, num: 7});
+        } else {
+            get_candidates_for_one_suit!(cand, self.spade,   CardSuit::Spade);
+            get_candidates_for_one_suit!(cand, self.heart,   CardSuit::Heart);
+            get_candidates_for_one_suit!(cand, self.club,    CardSuit::Club);
+            get_candidates_for_one_suit!(cand, self.diamond, CardSuit::Diamond);
+        }
+        cand
+    }
+
     fn discard_candidates(&mut self) -> &HashSet<Card> {
         if self.cand.is_none() {
-            let mut cand = HashSet::new();
-            if self.is_empty() {
-                cand.insert(Card{ suit: CardSuit::Heart, num: 7});
-            } else {
-                get_candidates_for_one_suit!(cand, self.spade,   CardSuit::Spade);
-                get_candidates_for_one_suit!(cand, self.heart,   CardSuit::Heart);
-                get_candidates_for_one_suit!(cand, self.club,    CardSuit::Club);
-                get_candidates_for_one_suit!(cand, self.diamond, CardSuit::Diamond);
-            }
-            self.cand = Some(cand);
+            self.cand = Some(self.compute_discard_candidates());
         }
 
         self.cand.as_ref().unwrap()
     }
 
+    // same as discard_candidates, but usable without mutable access
+    pub fn get_discard_candidates(&self) -> HashSet<Card> {
+        match self.cand {
+            Some(ref cand) => cand.clone(),
+            None => self.compute_discard_candidates(),
+        }
+    }
+
     pub fn is_discard_candidates(&mut self, c: &Card) -> bool {
         self.discard_candidates().iter().find(|&cc| cc == c).is_some()
     }
@@ -128,6 +143,21 @@ impl Desk {
         }
     }
 
+    pub fn from_desk_result(dr: &DeskResult) -> Self {
+        let chain = |v: &Vec<CardResult>| -> ChainType {
+            v.iter().map(
+                |cr| (cr.card.as_ref().unwrap().into(), cr.whose as usize)
+            ).collect()
+        };
+        Desk {
+            spade: chain(&dr.spade),
+            heart: chain(&dr.heart),
+            club: chain(&dr.club),
+            diamond: chain(&dr.diamond),
+            cand: None,
+        }
+    }
+
     pub fn export(&self) -> Vec<Vec<Card>> {
         [&self.spade, &self.heart, &self.club, &self.diamond].into_iter().map(
             |v| v.iter().map(|(c, _)| c.clone() ).collect()
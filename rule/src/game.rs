@@ -1,6 +1,8 @@
 use crate::*;
 use super::desk::*;
 use super::player::*;
+use super::ai::PlayerView;
+use super::dealer::*;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -28,7 +30,16 @@ impl From<GameError> for tonic::Status {
 
 pub type GameResult<T> = Result<T, GameError>;
 
+// bump on any change to the serialized Game, older ones are refused
+#[cfg(feature = "serde")]
+pub const GAME_SCHEMA_VERSION: u32 = 3;
+
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "GameRepr", try_from = "GameRepr")
+)]
 pub struct Game {
     desk: Desk,
     players: Vec<Player>,
@@ -41,38 +52,151 @@ pub struct Game {
     // (clear, seven)
     someone_has_clear: (bool, bool),
     first_hold: bool,
+    // every play of this hand, in order
+    history: Vec<Play>,
+    // kept across hands
+    rules: RuleSet,
+    // how many players the table is for, kept across hands
+    seats: usize,
+}
+
+// what a serialized Game looks like, with its schema version
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct GameRepr {
+    version: u32,
+    desk: Desk,
+    players: Vec<Player>,
+    start: usize,
+    next: usize,
+    ready_cnt: u32,
+    thisround: Vec<(Card, usize)>,
+    last: Option<Play>,
+    play_cnt: u32,
+    someone_has_clear: (bool, bool),
+    first_hold: bool,
+    history: Vec<Play>,
+    rules: RuleSet,
+    seats: usize,
+}
+
+#[cfg(feature = "serde")]
+impl From<Game> for GameRepr {
+    fn from(g: Game) -> Self {
+        GameRepr {
+            version: GAME_SCHEMA_VERSION,
+            desk: g.desk,
+            players: g.players,
+            start: g.start,
+            next: g.next,
+            ready_cnt: g.ready_cnt,
+            thisround: g.thisround,
+            last: g.last,
+            play_cnt: g.play_cnt,
+            someone_has_clear: g.someone_has_clear,
+            first_hold: g.first_hold,
+            history: g.history,
+            rules: g.rules,
+            seats: g.seats,
+        }
+    }
 }
 
-static END_GAME_CNT: u32 = 52;
+#[cfg(feature = "serde")]
+impl TryFrom<GameRepr> for Game {
+    type Error = String;
+
+    fn try_from(r: GameRepr) -> Result<Self, Self::Error> {
+        if r.version != GAME_SCHEMA_VERSION {
+            return Err(format!(
+                "Game schema version {} is not supported, expecting {}",
+                r.version, GAME_SCHEMA_VERSION
+            ));
+        }
+
+        Ok(Game {
+            desk: r.desk,
+            players: r.players,
+            start: r.start,
+            next: r.next,
+            ready_cnt: r.ready_cnt,
+            thisround: r.thisround,
+            last: r.last,
+            play_cnt: r.play_cnt,
+            someone_has_clear: r.someone_has_clear,
+            first_hold: r.first_hold,
+            history: r.history,
+            rules: r.rules,
+            seats: r.seats,
+        })
+    }
+}
 
 impl Game {
     pub fn new() -> Self {
         Self {
             first_hold: true,
+            seats: DEFAULT_SEATS,
             ..Default::default()
         }
     }
 
+    pub fn get_seats(&self) -> usize {
+        self.seats
+    }
+
+    pub fn set_seats(&mut self, seats: usize) -> GameResult<()> {
+        check_seats(seats)?;
+        if self.players.len() > seats {
+            return Err(GameError::PermissionDenied(
+                format!("{} players cannot fit in {} seats!", self.players.len(), seats)
+            ))
+        }
+        self.seats = seats;
+        Ok(())
+    }
+
+    // a hand ends when the whole deck is played
+    fn end_game_cnt(&self) -> u32 {
+        deck_size(self.seats) as u32
+    }
+
+    pub fn get_rules(&self) -> RuleSet {
+        self.rules
+    }
+
+    pub fn set_rules(&mut self, rules: RuleSet) -> GameResult<()> {
+        rules.check()?;
+        self.rules = rules;
+        Ok(())
+    }
+
     pub fn add_player(&mut self, name: String) -> usize {
         self.players.push(Player::new(name));
         self.players.len() - 1
     }
 
     pub fn new_game(&mut self, cards: Vec<u32>) -> GameResult<()> {
-        if self.players.len() != 4 {
+        if self.players.len() != self.seats {
             return Err(GameError::PermissionDenied("Player not enough!".into()))
         }
 
-        if self.ready_cnt != 4 || self.players.iter().any(|p| !p.is_ready()) {
+        if self.ready_cnt as usize != self.seats || self.players.iter().any(|p| !p.is_ready()) {
             return Err(GameError::PermissionDenied("Not everyone ready!".into()))
         }
 
+        check_deck(&cards, self.seats)?;
+
         self.clear();
 
-        for pi in 0..=3 {
-            for c in &cards[pi*13 .. (pi+1)*13] {
+        let mut rest = &cards[..];
+        for (pi, n) in hand_sizes(self.seats).into_iter().enumerate() {
+            let (hand, more) = rest.split_at(n);
+            rest = more;
+            for c in hand {
                 self.players[pi].add_card(Card::from(*c))?;
-                if *c == 19 {
+                if *c == HEART_SEVEN {
+                    self.start = pi;
                     self.next = pi;
                 }
             }
@@ -93,6 +217,16 @@ impl Game {
         self.next = next;
     }
 
+    // who plays first in this hand, and so plays next
+    pub fn set_start(&mut self, start: usize) {
+        self.start = start;
+        self.next = start;
+    }
+
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
     pub fn has_just_begin(&self) -> bool {
         self.play_cnt == 0
     }
@@ -124,6 +258,9 @@ impl Game {
         self.play_cnt = 0;
         self.next = 0;
         self.last = None;
+        self.someone_has_clear = (false, false);
+        self.first_hold = true;
+        self.history.clear();
     }
 
     fn check_pid(&self, pid: usize) -> GameResult<()> {
@@ -142,6 +279,11 @@ impl Game {
         Ok(())
     }
 
+    // everyone has to get ready again, as after the table changed
+    pub fn reset_ready(&mut self) {
+        self.clear();
+    }
+
     pub fn player_exit(&mut self, pid: usize) -> GameResult<usize> {
         self.check_pid(pid)?;
 
@@ -198,7 +340,7 @@ impl Game {
 
         self.ready_cnt += 1;
 
-        Ok(4 - self.ready_cnt)
+        Ok(self.seats as u32 - self.ready_cnt)
     }
 
     pub fn get_someone_cards(&self, pid: usize) -> GameResult<Vec<Card>> {
@@ -253,9 +395,14 @@ impl Game {
         )
     }
 
+    // discards are over once someone clears, unless the rules say to play on
+    fn is_hold_only(&self) -> bool {
+        self.someone_has_clear.0 && !self.rules.play_on_after_clear
+    }
+
     pub fn get_my_hint(&mut self) -> Vec<bool> {
         let cards = self.get_my_cards();
-        if self.someone_has_clear.0 {
+        if self.is_hold_only() {
             vec![false; cards.len()]
         } else {
             cards.iter().map(
@@ -264,8 +411,24 @@ impl Game {
         }
     }
 
+    // every play pid may make now, nothing if it is not pid's turn,
+    // a play passes check_play if and only if it is in here
+    pub fn legal_plays(&self, pid: usize) -> Vec<Play> {
+        if self.next != pid {
+            return Vec::new();
+        }
+        match self.get_player_view(pid) {
+            Ok(view) => view.legal_plays(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    pub fn can_hold(&self, pid: usize) -> bool {
+        self.legal_plays(pid).iter().any(|p| matches!(p, Play::Hold(..)))
+    }
+
     pub fn someone_has_discard_candidates(&mut self, pid: usize) -> bool {
-        if self.someone_has_clear.0 {
+        if self.is_hold_only() {
             return false;
         }
 
@@ -299,7 +462,7 @@ impl Game {
             ))
         }
 
-        if is_discard && self.someone_has_clear.0 {
+        if is_discard && self.is_hold_only() {
             return Err(GameError::PermissionDenied(
                 "Someone clears! Hold only!".into()
             ))
@@ -317,7 +480,7 @@ impl Game {
             ))
         }
 
-        if !is_discard && self.first_hold && c.num == 1 {
+        if !is_discard && self.first_hold && !self.rules.ace_first_hold && c.num == 1 {
             return Err(GameError::PermissionDenied(
                 "First hold cannot be an Ace!".into()
             ))
@@ -332,25 +495,33 @@ impl Game {
         let pid = play.get_pid();
         match self.players.get_mut(pid).unwrap().play_card(play.clone()) {
             PlayCardResult::Normal => {},
+            // more may clear when playing on, the first one counts
+            _ if self.someone_has_clear.0 => assert!(self.rules.play_on_after_clear),
             _ => {
-                assert_eq!(self.someone_has_clear.0, false);
                 self.someone_has_clear.0 = true;
             }
         }
 
-        if self.play_cnt % 4 == 0 {
+        // a round is one pass around the table from the start seat,
+        // over once the turn wraps, however many seats out of cards were skipped
+        let seats = self.seats;
+        let from_start = |p: usize| (p + seats - self.start) % seats;
+        if self.last.as_ref().is_some_and(|l| from_start(pid) <= from_start(l.get_pid())) {
             self.thisround.clear();
         }
         self.play_cnt += 1;
 
-        self.next += 1;
-        self.next %= 4;
+        // with uneven hands, those out of cards are skipped
+        self.next = (1..=seats).map(|i| (self.next + i) % seats).find(
+            |&p| self.players[p].has_card_left()
+        ).unwrap_or((self.next + 1) % seats);
 
         if let Play::Discard(c, _) = &play {
             self.thisround.push((c.clone(), pid));
         } else if self.first_hold {
             self.first_hold = false;
         }
+        self.history.push(play.clone());
         self.last = Some(play);
 
         Ok(())
@@ -359,7 +530,7 @@ impl Game {
     pub fn play_card(&mut self, play: Play) -> GameResult<bool> {
         self.check_play(&play)?;
         let _ = self.play_card_no_check(play)?;
-        Ok(self.play_cnt == END_GAME_CNT)
+        Ok(self.play_cnt == self.end_game_cnt())
     }
 
     pub fn get_desk_info(&self) -> DeskInfo {
@@ -398,7 +569,7 @@ impl Game {
     }
 
     fn get_hold_list(&self) -> Vec<HoldList> {
-        (0..4).map(
+        (0..self.players.len()).map(
             |pid| HoldList {
                 holds: self.get_someone_holds(pid).unwrap().into_iter().map(
                     |c| c.into()
@@ -408,15 +579,25 @@ impl Game {
     }
 
     fn get_winner(&self) -> GameResult<(usize, GameWinnerState)> {
-        if self.play_cnt != END_GAME_CNT {
+        if self.play_cnt != self.end_game_cnt() {
             return Err(GameError::PermissionDenied("Game has not ended!".into()))
         }
 
         // start_id == 0 means he's the first one to play (Heart7 owner)
-        let mut start_id = vec![0, 1, 2, 3];
+        let last_id = self.seats - 1;
+        let mut start_id: Vec<usize> = (0..self.seats).collect();
         start_id.rotate_right(self.start);
-        // (pid, (hn, start_id))
-        let mut hn: Vec<_> = self.get_hold_nums().into_iter().zip(start_id).enumerate().collect();
+        let tie_break = self.rules.tie_break();
+        // lower wins a tie of hold nums
+        let tie_key = |pid: usize| match tie_break {
+            TieBreak::EarlierSeat => (0, start_id[pid]),
+            TieBreak::LaterSeat => (0, last_id - start_id[pid]),
+            TieBreak::LowerPoints => (self.players[pid].get_hold_points(), start_id[pid]),
+        };
+        // (pid, (hn, tie_key))
+        let mut hn: Vec<_> = self.get_hold_nums().into_iter().enumerate().map(
+            |(pid, n)| (pid, (n, tie_key(pid)))
+        ).collect();
         hn.sort_by(
             |a, b| a.1.cmp(&b.1)
         );
@@ -436,7 +617,7 @@ impl Game {
     }
 
     pub fn end_game(&self) -> GameResult<GameEnding> {
-        if self.play_cnt != END_GAME_CNT {
+        if self.play_cnt != self.end_game_cnt() {
             return Err(GameError::PermissionDenied("Game has not ended!".into()))
         }
 
@@ -456,13 +637,158 @@ impl Game {
         })
     }
 
-    pub fn init_my_cards(&mut self, cards: Vec<Card>) {
-        assert_eq!(self.players.len(), 4);
+    pub fn get_player_view(&self, pid: usize) -> GameResult<PlayerView> {
+        self.check_pid(pid)?;
+
+        Ok(PlayerView {
+            pid,
+            cards: self.get_someone_cards(pid)?,
+            holds: self.get_someone_holds(pid)?,
+            desk: self.desk.clone(),
+            hold_nums: self.get_hold_nums(),
+            thisround: self.thisround.clone(),
+            first_hold: self.first_hold,
+            someone_has_clear: self.someone_has_clear.0,
+            rules: self.rules,
+        })
+    }
+
+    // cards dealt to pid at the start of this hand
+    pub fn get_dealt_cards(&self, pid: usize) -> GameResult<Vec<Card>> {
+        let mut cards = self.get_someone_cards(pid)?;
+        cards.extend(self.history.iter().filter(
+            |p| p.get_pid() == pid
+        ).map(
+            |p| p.clone().split().1
+        ));
+        cards.sort();
+        Ok(cards)
+    }
+
+    // every play of this hand, nothing masked
+    pub fn get_history(&self) -> Vec<Play> {
+        self.history.clone()
+    }
+
+    // plays of this hand as seen by pid, holds of others are masked
+    pub fn get_history_for(&self, pid: usize) -> Vec<Play> {
+        self.history.iter().map(
+            |p| match p {
+                Play::Hold(_, who) if *who != pid => Play::Hold(DUMMY_CARD.clone(), *who),
+                _ => p.clone(),
+            }
+        ).collect()
+    }
+
+    // plays of this hand as seen by spectators, all holds are masked
+    pub fn get_public_history(&self) -> Vec<Play> {
+        self.history.iter().map(
+            |p| match p {
+                Play::Hold(_, who) => Play::Hold(DUMMY_CARD.clone(), *who),
+                _ => p.clone(),
+            }
+        ).collect()
+    }
+
+    // None for spectators, who see no cards and no holds
+    pub fn get_snapshot(&self, pid: Option<usize>) -> GameResult<GameSnapshot> {
+        let (your_id, cards, holds, history) = match pid {
+            Some(pid) => (
+                pid,
+                self.get_someone_cards(pid)?,
+                self.get_someone_holds(pid)?,
+                self.get_history_for(pid),
+            ),
+            None => (0, vec![], vec![], self.get_public_history()),
+        };
+        let last = self.last.clone().map(
+            |p| match p {
+                Play::Hold(_, who) if Some(who) != pid => Play::Hold(DUMMY_CARD.clone(), who),
+                _ => p,
+            }.into()
+        );
+
+        Ok(GameSnapshot {
+            your_id: your_id as u32,
+            players: self.get_player_names().into_iter().map(
+                |name| PlayerInfo { name }
+            ).collect(),
+            start: self.start as u32,
+            next: self.next as u32,
+            play_cnt: self.play_cnt,
+            cards: cards.into_iter().map(|c| c.into()).collect(),
+            holds: holds.into_iter().map(|c| c.into()).collect(),
+            card_nums: self.players.iter().map(|p| p.get_card_num() as u32).collect(),
+            hold_nums: self.get_hold_nums(),
+            desk: Some(self.desk.get_desk_result()),
+            thisround: self.thisround.iter().map(|c| c.into()).collect(),
+            last,
+            history: history.into_iter().map(|p| p.into()).collect(),
+            first_hold: self.first_hold,
+            someone_has_clear: self.someone_has_clear.0,
+            someone_has_seven: self.someone_has_clear.1,
+            rules: Some(self.rules),
+        })
+    }
+
+    // others get dummy cards and holds, like in init_my_cards
+    pub fn from_snapshot(gs: &GameSnapshot) -> GameResult<Self> {
+        let seats = gs.players.len();
+        if check_seats(seats).is_err() || gs.card_nums.len() != seats || gs.hold_nums.len() != seats {
+            return Err(GameError::Internal("Snapshot needs a full table!".into()))
+        }
+        let me = gs.your_id as usize;
+
+        let players = (0..seats).map(|pid| {
+            let mut p = Player::new(gs.players[pid].name.clone());
+            // spectators get no cards, not even for seat 0
+            let is_mine = pid == me
+                && gs.cards.len() == gs.card_nums[pid] as usize
+                && gs.holds.len() == gs.hold_nums[pid] as usize;
+            if is_mine {
+                p.restore(
+                    gs.cards.iter().map(|c| c.into()).collect(),
+                    gs.holds.iter().map(|c| c.into()).collect(),
+                );
+            } else {
+                p.restore(
+                    vec![DUMMY_CARD.clone(); gs.card_nums[pid] as usize],
+                    vec![DUMMY_CARD.clone(); gs.hold_nums[pid] as usize],
+                );
+            }
+            p
+        }).collect();
+
+        Ok(Self {
+            desk: gs.desk.as_ref().map(Desk::from_desk_result).unwrap_or_default(),
+            players,
+            start: gs.start as usize,
+            next: gs.next as usize,
+            ready_cnt: 0,
+            thisround: gs.thisround.iter().map(
+                |cr| (cr.card.as_ref().unwrap().into(), cr.whose as usize)
+            ).collect(),
+            last: gs.last.map(|pi| pi.into()),
+            play_cnt: gs.play_cnt,
+            someone_has_clear: (gs.someone_has_clear, gs.someone_has_seven),
+            first_hold: gs.first_hold,
+            history: gs.history.iter().map(|pi| (*pi).into()).collect(),
+            rules: gs.rules.unwrap_or_default(),
+            seats,
+        })
+    }
+
+    // my_seat is where seat 0 really sits, for how many cards the others are dealt
+    pub fn init_my_cards(&mut self, cards: Vec<Card>, my_seat: usize) {
+        assert_eq!(self.players.len(), self.seats);
+
+        let mut sizes = hand_sizes(self.seats);
+        sizes.rotate_left(my_seat);
 
         self.players[0].init_cards(cards);
 
-        (1..=3).for_each(
-            |pi| self.players[pi].init_dummy_cards()
+        (1..self.seats).for_each(
+            |pi| self.players[pi].init_dummy_cards(sizes[pi])
         );
     }
 
@@ -470,3 +796,197 @@ impl Game {
         self.desk.export()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::RandomBot;
+
+    const SEEDS: u64 = 20;
+
+    fn all_rules() -> Vec<RuleSet> {
+        vec![
+            RuleSet::default(),
+            RuleSet { ace_first_hold: true, ..Default::default() },
+            RuleSet { play_on_after_clear: true, ..Default::default() },
+        ]
+    }
+
+    fn key(p: &Play) -> (bool, Card, usize) {
+        p.clone().split()
+    }
+
+    // a dealt hand nobody has played yet
+    pub(super) fn new_hand(seats: usize, rules: RuleSet, seed: u64) -> Game {
+        let mut g = Game::new();
+        g.set_seats(seats).unwrap();
+        g.set_rules(rules).unwrap();
+        for pid in 0..seats {
+            g.add_player(format!("p{}", pid));
+            g.player_ready(pid).unwrap();
+        }
+        g.new_game(Dealer::seeded(seed).deal(seats).unwrap()).unwrap();
+        g
+    }
+
+    // one play picked by the bot, true once the hand is over
+    pub(super) fn play_one(g: &mut Game, bot: &mut RandomBot) -> bool {
+        let pid = g.get_next();
+        let play = bot.choose_play(&g.get_player_view(pid).unwrap()).expect("Nothing to play!");
+        g.play_card(play).unwrap()
+    }
+
+    // everything pid could try with its cards, legal or not
+    fn every_play(g: &Game, pid: usize) -> Vec<Play> {
+        g.get_someone_cards(pid).unwrap().into_iter().flat_map(
+            |c| [Play::Discard(c.clone(), pid), Play::Hold(c, pid)]
+        ).collect()
+    }
+
+    #[test]
+    fn legal_plays_agree_with_check_play() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            for rules in all_rules() {
+                for seed in 0..SEEDS {
+                    let mut g = new_hand(seats, rules, seed);
+                    let mut bot = RandomBot::new(seed);
+                    loop {
+                        let pid = g.get_next();
+                        let legal: Vec<_> = g.legal_plays(pid).iter().map(key).collect();
+                        assert!(!legal.is_empty());
+                        for p in every_play(&g, pid) {
+                            assert_eq!(
+                                g.check_play(&p).is_ok(), legal.contains(&key(&p)),
+                                "{:?} at {} seats, {:?}, seed {}", p, seats, rules, seed
+                            );
+                        }
+                        // nobody else may play out of turn
+                        let other = (pid + 1) % seats;
+                        assert!(g.legal_plays(other).is_empty());
+                        assert!(every_play(&g, other).iter().all(|p| g.check_play(p).is_err()));
+
+                        if pid == 0 {
+                            let hint = g.get_my_hint();
+                            for (c, h) in g.get_my_cards().into_iter().zip(hint) {
+                                assert_eq!(h, legal.contains(&(true, c, 0)));
+                            }
+                        }
+
+                        if play_one(&mut g, &mut bot) {
+                            break;
+                        }
+                    }
+                    assert!(g.end_game().is_ok());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rounds_follow_the_table() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            for seed in 0..SEEDS {
+                let mut g = new_hand(seats, RuleSet::default(), seed);
+                let mut bot = RandomBot::new(seed);
+                loop {
+                    let done = play_one(&mut g, &mut bot);
+                    // one pass from the start seat, everyone at most once
+                    let from_start: Vec<usize> = g.thisround.iter().map(
+                        |(_, p)| (p + seats - g.get_start()) % seats
+                    ).collect();
+                    assert!(
+                        from_start.windows(2).all(|w| w[0] < w[1]),
+                        "{:?} at {} seats, seed {}", g.thisround, seats, seed
+                    );
+                    if done {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rounds_skip_seats_out_of_cards() {
+        for seed in 0..SEEDS {
+            let mut g = new_hand(MIN_SEATS, RuleSet::default(), seed);
+            // the seat after the start runs out at once, as with uneven hands at the end
+            let out = (g.get_start() + 1) % MIN_SEATS;
+            g.players[out].restore(vec![], vec![]);
+            let mut bot = RandomBot::new(seed);
+            while g.players.iter().any(|p| p.has_card_left()) {
+                let pid = g.get_next();
+                assert_ne!(pid, out);
+                let play = bot.choose_play(&g.get_player_view(pid).unwrap()).unwrap();
+                g.play_card(play).unwrap();
+                let whose: Vec<usize> = g.thisround.iter().map(|(_, p)| *p).collect();
+                let mut once = whose.clone();
+                once.sort();
+                once.dedup();
+                assert_eq!(once.len(), whose.len(), "{:?}, seed {}", g.thisround, seed);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+    use super::tests::{new_hand, play_one};
+    use crate::ai::RandomBot;
+
+    fn round_trip(g: &Game) -> Game {
+        serde_json::from_str(&serde_json::to_string(g).unwrap()).unwrap()
+    }
+
+    fn legal_keys(g: &Game) -> Vec<Vec<(bool, Card, usize)>> {
+        (0..g.get_seats()).map(
+            |pid| g.legal_plays(pid).into_iter().map(|p| p.split()).collect()
+        ).collect()
+    }
+
+    #[test]
+    fn mid_hand_round_trip() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            for seed in 0..10 {
+                let mut g = new_hand(seats, RuleSet { play_on_after_clear: true, ..Default::default() }, seed);
+                let mut bot = RandomBot::new(seed);
+                for _ in 0..deck_size(seats) / 2 {
+                    play_one(&mut g, &mut bot);
+                }
+
+                let mut back = round_trip(&g);
+                assert_eq!(legal_keys(&back), legal_keys(&g));
+
+                // both play on the same way to the same result
+                let mut bot_back = RandomBot::new(seed + 1000);
+                let mut bot = RandomBot::new(seed + 1000);
+                loop {
+                    let pid = g.get_next();
+                    for p in g.legal_plays(pid) {
+                        assert!(back.check_play(&p).is_ok());
+                    }
+                    let done = play_one(&mut g, &mut bot);
+                    assert_eq!(play_one(&mut back, &mut bot_back), done);
+                    assert_eq!(legal_keys(&back), legal_keys(&g));
+                    if done {
+                        break;
+                    }
+                }
+                assert_eq!(back.get_winner().unwrap(), g.get_winner().unwrap());
+                assert_eq!(back.end_game().unwrap(), g.end_game().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn wrong_version_is_refused() {
+        let g = new_hand(DEFAULT_SEATS, RuleSet::default(), 7);
+        let mut v = serde_json::to_value(&g).unwrap();
+        assert!(serde_json::from_value::<Game>(v.clone()).is_ok());
+
+        v["version"] = (GAME_SCHEMA_VERSION + 1).into();
+        let e = serde_json::from_value::<Game>(v).unwrap_err();
+        assert!(e.to_string().contains("schema version"));
+    }
+}
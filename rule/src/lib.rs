@@ -1,9 +1,22 @@
+pub mod ai;
+pub mod dealer;
 pub mod desk;
 pub mod game;
 pub mod player;
+pub mod rating;
+pub mod rules;
+pub mod scoring;
+#[cfg(feature = "record")]
+pub mod record;
 
 pub use game::{Game, GameResult, GameError};
+#[cfg(feature = "serde")]
+pub use game::GAME_SCHEMA_VERSION;
 pub use player::Player;
+pub use ai::{Strategy, PlayerView, RandomBot, HeuristicBot};
+pub use dealer::{Dealer, DealSource, RandomDeal, FixedDeal};
+#[cfg(feature = "record")]
+pub use record::{GameRecord, TimedPlay, RecordError};
 
 pub mod heart7_rpc {
     tonic::include_proto!("heart7_rpc");
@@ -16,7 +29,33 @@ pub use crate::heart7_rpc::{
     room_info::*,
 };
 
+// gRPC metadata key carrying the session token given by JoinRoom
+pub const SESSION_KEY: &str = "x-heart7-session";
+// binary, so any password fits, for watching a private room without a seat
+pub const PASSWORD_KEY: &str = "x-heart7-password-bin";
+
+// Reaction comes from the proto
+impl Reaction {
+    pub const ALL: [Reaction; 4] = [
+        Reaction::NiceBlock,
+        Reaction::HurryUp,
+        Reaction::Oops,
+        Reaction::WellPlayed,
+    ];
+
+    // for showing in clients
+    pub fn text(&self) -> &'static str {
+        match self {
+            Reaction::NiceBlock => "Nice block!",
+            Reaction::HurryUp => "Hurry up!",
+            Reaction::Oops => "Oops!",
+            Reaction::WellPlayed => "Well played!",
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     pub suit: CardSuit,
     pub num: u32,
@@ -92,6 +131,7 @@ impl From<u32> for Card {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Play {
     Discard(Card, usize),
     Hold(Card, usize),
@@ -109,6 +149,19 @@ impl From<PlayInfo> for Play {
     }
 }
 
+impl From<Play> for PlayInfo {
+    fn from(value: Play) -> Self {
+        let (is_discard, c, pid) = value.split();
+        PlayInfo {
+            player: pid as u32,
+            playone: Some(PlayOne {
+                is_discard,
+                card: Some(c.into()),
+            }),
+        }
+    }
+}
+
 impl Play {
     pub fn split(self) -> (bool, Card, usize) {
         match self {
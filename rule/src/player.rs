@@ -2,6 +2,7 @@ use std::collections::HashSet;
 use super::*;
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Player {
     name: String,
     cards: Vec<Card>,
@@ -54,13 +55,18 @@ impl Player {
         self.holds.clear();
     }
 
-    pub fn init_dummy_cards(&mut self) {
-        let dc: Vec<Card> = (0..13).map(
+    pub fn init_dummy_cards(&mut self, n: usize) {
+        let dc: Vec<Card> = (0..n).map(
             |_| DUMMY_CARD.clone()
         ).collect();
         self.init_cards(dc);
     }
 
+    pub fn restore(&mut self, cards: Vec<Card>, holds: Vec<Card>) {
+        self.cards = cards;
+        self.holds = holds;
+    }
+
     pub fn clear(&mut self) {
         self.cards.clear();
         self.holds.clear();
@@ -84,6 +90,10 @@ impl Player {
         self.holds.len() as u32
     }
 
+    pub fn get_hold_points(&self) -> u32 {
+        self.holds.iter().map(|c| c.num).sum()
+    }
+
     pub fn get_holds(&self) -> Vec<Card> {
         self.holds.clone()
     }
@@ -0,0 +1,159 @@
+use crate::*;
+
+pub const INITIAL_RATING: f64 = 1500.0;
+
+// the most one hand can move a rating, split among the opponents
+const K_FACTOR: f64 = 32.0;
+
+// Profile comes from the proto
+impl Profile {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            rating: INITIAL_RATING,
+            ..Default::default()
+        }
+    }
+
+    pub fn avg_hold_points(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.hold_points as f64 / self.games as f64
+        }
+    }
+
+    // count the hand seen from seat pid, whose rating moved by delta
+    pub fn add_hand(&mut self, ge: &GameEnding, pid: usize, delta: f64) {
+        self.games += 1;
+        if ge.winner as usize == pid {
+            self.wins += 1;
+            match ge.winner_state() {
+                GameWinnerState::Normal => {}
+                GameWinnerState::Clear => self.clears += 1,
+                GameWinnerState::Seven => self.sevens += 1,
+            }
+        }
+        self.hold_points += hold_points(ge)[pid] as u64;
+        self.rating += delta;
+    }
+
+    // one line, for showing in clients
+    pub fn describe(&self) -> String {
+        if self.games == 0 {
+            format!("{:.0}  new player", self.rating)
+        } else {
+            format!("{:.0}  {}/{} won  avg {:.1}",
+                self.rating, self.wins, self.games, self.avg_hold_points())
+        }
+    }
+}
+
+// sum of held card numbers of each seat
+pub fn hold_points(ge: &GameEnding) -> Vec<u32> {
+    ge.hold.iter().map(
+        |hl| hl.holds.iter().map(|c| c.num).sum()
+    ).collect()
+}
+
+// the winner beats everyone, the others are ordered by hold points,
+// 1 for a win, 0.5 for a tie
+fn score_against(ge: &GameEnding, points: &[u32], a: usize, b: usize) -> f64 {
+    let winner = ge.winner as usize;
+    if a == winner {
+        1.0
+    } else if b == winner {
+        0.0
+    } else if points[a] < points[b] {
+        1.0
+    } else if points[a] > points[b] {
+        0.0
+    } else {
+        0.5
+    }
+}
+
+// Elo against each other seat of the hand, ratings by seat
+pub fn rate_hand(ratings: &[f64], ge: &GameEnding) -> Vec<f64> {
+    let seats = ratings.len();
+    let points = hold_points(ge);
+    if seats < 2 || points.len() != seats {
+        return vec![0.0; seats];
+    }
+
+    let k = K_FACTOR / (seats - 1) as f64;
+    (0..seats).map(|a| {
+        (0..seats).filter(|&b| b != a).map(|b| {
+            let expected = 1.0 / (1.0 + 10f64.powf((ratings[b] - ratings[a]) / 400.0));
+            k * (score_against(ge, &points, a, b) - expected)
+        }).sum()
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // each seat holds one card of its points
+    fn ending(points: &[u32], winner: usize) -> GameEnding {
+        GameEnding {
+            hold: points.iter().map(|&num| HoldList {
+                holds: if num == 0 { vec![] } else { vec![CardInfo { suit: 0, num }] },
+            }).collect(),
+            winner: winner as u32,
+            ..Default::default()
+        }
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn changes_sum_to_zero() {
+        let ratings = [1500.0, 1720.0, 1380.0, 1610.0, 1450.0, 1900.0];
+        let points = [0, 12, 3, 7, 7, 20];
+        for seats in 3..=6 {
+            for winner in 0..seats {
+                let deltas = rate_hand(&ratings[..seats], &ending(&points[..seats], winner));
+                assert_eq!(deltas.len(), seats);
+                assert!(close(deltas.iter().sum(), 0.0), "{:?}", deltas);
+            }
+        }
+    }
+
+    #[test]
+    fn winner_gains_the_same_at_any_table() {
+        // K is split among the opponents, so even ratings give the winner K / 2
+        for (seats, points) in [(3, vec![0, 4, 9]), (6, vec![0, 1, 2, 3, 4, 5])] {
+            let deltas = rate_hand(&vec![INITIAL_RATING; seats], &ending(&points, 0));
+            assert!(close(deltas[0], K_FACTOR / 2.0), "{:?}", deltas);
+            // the one holding most loses to everyone
+            assert!(close(deltas[seats - 1], -K_FACTOR / 2.0), "{:?}", deltas);
+        }
+
+        // beating weaker players is worth less
+        let even = rate_hand(&[1500.0; 3], &ending(&[0, 4, 9], 0));
+        let strong = rate_hand(&[1800.0, 1500.0, 1500.0], &ending(&[0, 4, 9], 0));
+        assert!(strong[0] > 0.0 && strong[0] < even[0]);
+    }
+
+    #[test]
+    fn tied_seats_move_together() {
+        let deltas = rate_hand(&[INITIAL_RATING; 3], &ending(&[0, 6, 6], 0));
+        assert!(close(deltas[0], 16.0));
+        assert!(close(deltas[1], -8.0) && close(deltas[2], -8.0));
+
+        let deltas = rate_hand(&[INITIAL_RATING; 6], &ending(&[0, 3, 3, 3, 5, 5], 0));
+        assert!(close(deltas[1], deltas[2]) && close(deltas[2], deltas[3]));
+        assert!(close(deltas[4], deltas[5]));
+        assert!(deltas[3] > deltas[4]);
+        assert!(close(deltas.iter().sum(), 0.0));
+    }
+
+    #[test]
+    fn nothing_to_rate() {
+        assert_eq!(rate_hand(&[INITIAL_RATING], &ending(&[0], 0)), vec![0.0]);
+        assert_eq!(rate_hand(&[INITIAL_RATING; 3], &ending(&[0, 1], 0)), vec![0.0; 3]);
+    }
+}
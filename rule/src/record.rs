@@ -0,0 +1,195 @@
+// GameRecord: one finished hand, saved as a JSON file
+//
+// {
+//   "version": 2,
+//   "names": ["alice", "bob", "carol", "dave"],
+//   "rules": { "ace_first_hold": false, "play_on_after_clear": false, "tie_break": 0 },
+//   "deal": [12, 40, 3, ...],
+//   "plays": [
+//     { "at": 1700000000000, "play": { "Discard": [{ "suit": "Spade", "num": 7 }, 2] } },
+//     { "at": 1700000003512, "play": { "Hold": [{ "suit": "Heart", "num": 13 }, 3] } },
+//     ...
+//   ],
+//   "ending": { "desk": {...}, "hold": [...], "winner": 1, "winner_state": 0 }
+// }
+//
+// names: seat names, in seat order, as many as the table seats
+// rules: the RuleSet of the room, tie_break as in the TieBreak enum
+// deal: the shuffled deck given to Game::new_game, all of dealer::deck_for the table
+// plays: every play of the hand in order, nothing masked,
+//        at is milliseconds since UNIX epoch, 0 if unknown
+// ending: what Game::end_game gave when the hand ended
+
+use crate::*;
+use thiserror::Error;
+
+// bump on any change to the record format, older ones are refused
+pub const RECORD_VERSION: u32 = 2;
+
+#[derive(Error, Debug)]
+pub enum RecordError {
+    #[error("Bad record: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Record version {0} is not supported, expecting {RECORD_VERSION}")]
+    Version(u32),
+    #[error("Cannot replay record: {0}")]
+    Game(#[from] GameError),
+    #[error("Replayed ending differs from the recorded one")]
+    Mismatch,
+}
+
+pub type RecordResult<T> = Result<T, RecordError>;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TimedPlay {
+    pub at: u64,
+    pub play: Play,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct GameRecord {
+    pub version: u32,
+    pub names: Vec<String>,
+    pub rules: RuleSet,
+    pub deal: Vec<u32>,
+    pub plays: Vec<TimedPlay>,
+    pub ending: GameEnding,
+}
+
+impl GameRecord {
+    pub fn new(
+        names: Vec<String>, rules: RuleSet, deal: Vec<u32>, plays: Vec<TimedPlay>,
+        ending: GameEnding
+    ) -> Self {
+        Self {
+            version: RECORD_VERSION,
+            names,
+            rules,
+            deal,
+            plays,
+            ending,
+        }
+    }
+
+    pub fn to_json(&self) -> RecordResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub fn from_json(s: &str) -> RecordResult<Self> {
+        let rec: Self = serde_json::from_str(s)?;
+        if rec.version != RECORD_VERSION {
+            return Err(RecordError::Version(rec.version));
+        }
+        Ok(rec)
+    }
+
+    // the hand just dealt, nothing played yet
+    pub fn new_game(&self) -> RecordResult<Game> {
+        let mut g = Game::new();
+        g.set_rules(self.rules)?;
+        g.set_seats(self.names.len())?;
+        for name in &self.names {
+            g.add_player(name.clone());
+        }
+        for pid in 0..g.get_player_num() {
+            g.player_ready(pid)?;
+        }
+        g.new_game(self.deal.clone())?;
+        Ok(g)
+    }
+
+    // the game before any play, then after each play, checking every play and the ending
+    pub fn replay_steps(&self) -> RecordResult<Vec<Game>> {
+        let mut steps = Vec::with_capacity(self.plays.len() + 1);
+        self.play_through(|g| steps.push(g.clone()))?;
+        Ok(steps)
+    }
+
+    // play the whole hand again, checking every play and the ending
+    pub fn replay(&self) -> RecordResult<Game> {
+        self.play_through(|_| {})
+    }
+
+    // step sees the game before any play, then after each play
+    fn play_through(&self, mut step: impl FnMut(&Game)) -> RecordResult<Game> {
+        let mut g = self.new_game()?;
+        step(&g);
+        for tp in &self.plays {
+            g.play_card(tp.play.clone())?;
+            step(&g);
+        }
+
+        if g.end_game()? != self.ending {
+            return Err(RecordError::Mismatch);
+        }
+        Ok(g)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::RandomBot;
+    use crate::dealer::{MIN_SEATS, MAX_SEATS};
+
+    fn keys(plays: Vec<Play>) -> Vec<(bool, Card, usize)> {
+        plays.into_iter().map(Play::split).collect()
+    }
+
+    // a whole hand played by random bots
+    fn played_hand(seats: usize, seed: u64) -> GameRecord {
+        let names: Vec<String> = (0..seats).map(|pid| format!("p{}", pid)).collect();
+        let rules = RuleSet { ace_first_hold: true, ..Default::default() };
+        let deal = Dealer::seeded(seed).deal(seats).unwrap();
+        let rec = GameRecord::new(names, rules, deal, vec![], GameEnding::default());
+
+        let mut g = rec.new_game().unwrap();
+        let mut bot = RandomBot::new(seed);
+        let mut plays = vec![];
+        loop {
+            let play = bot.choose_play(&g.get_player_view(g.get_next()).unwrap()).unwrap();
+            plays.push(TimedPlay { at: plays.len() as u64 * 1000, play: play.clone() });
+            if g.play_card(play).unwrap() {
+                break;
+            }
+        }
+        GameRecord { plays, ending: g.end_game().unwrap(), ..rec }
+    }
+
+    #[test]
+    fn round_trip_replays_to_the_ending() {
+        for seats in MIN_SEATS..=MAX_SEATS {
+            let rec = played_hand(seats, seats as u64);
+            let back = GameRecord::from_json(&rec.to_json().unwrap()).unwrap();
+            assert_eq!(back.names, rec.names);
+            assert_eq!(back.deal, rec.deal);
+            assert_eq!(back.plays.len(), rec.plays.len());
+
+            let g = back.replay().unwrap();
+            let played = keys(rec.plays.iter().map(|tp| tp.play.clone()).collect());
+            assert_eq!(keys(g.get_history()), played);
+            let steps = back.replay_steps().unwrap();
+            assert_eq!(steps.len(), rec.plays.len() + 1);
+            assert_eq!(keys(steps.last().unwrap().get_history()), played);
+        }
+    }
+
+    #[test]
+    fn bad_records_are_refused() {
+        let rec = played_hand(4, 1);
+
+        let mut old = rec.clone();
+        old.version = RECORD_VERSION - 1;
+        assert!(matches!(
+            GameRecord::from_json(&old.to_json().unwrap()), Err(RecordError::Version(_))
+        ));
+
+        let mut other = rec.clone();
+        other.ending.winner = (rec.ending.winner + 1) % 4;
+        assert!(matches!(other.replay(), Err(RecordError::Mismatch)));
+
+        let mut cut = rec.clone();
+        cut.plays.pop();
+        assert!(cut.replay().is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use crate::*;
+
+// RuleSet and TieBreak come from the proto, RuleSet::default() is the house rules
+impl RuleSet {
+    pub fn check(&self) -> GameResult<()> {
+        if TieBreak::try_from(self.tie_break).is_err() {
+            return Err(GameError::PermissionDenied(
+                format!("Unknown tie break {}!", self.tie_break)
+            ))
+        }
+
+        Ok(())
+    }
+
+    pub fn is_house_rules(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // one line a rule, for showing in clients
+    pub fn describe(&self) -> Vec<String> {
+        vec![
+            if self.ace_first_hold {
+                "Ace can be the first hold"
+            } else {
+                "No Ace as the first hold"
+            }.into(),
+            if self.play_on_after_clear {
+                "Play on after someone clears"
+            } else {
+                "Hold only after someone clears"
+            }.into(),
+            match self.tie_break() {
+                TieBreak::EarlierSeat => "Ties go to the earlier seat",
+                TieBreak::LaterSeat => "Ties go to the later seat",
+                TieBreak::LowerPoints => "Ties go to lower hold points",
+            }.into(),
+        ]
+    }
+}
+
+// TurnTimer comes from the proto, a zero turn_secs means no clock
+impl TurnTimer {
+    pub fn check(&self) -> GameResult<()> {
+        if self.turn_secs == 0 && self.bank_secs != 0 {
+            return Err(GameError::PermissionDenied(
+                "A time bank needs a turn clock!".into()
+            ))
+        }
+
+        Ok(())
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.turn_secs != 0
+    }
+
+    // one line, for showing in clients
+    pub fn describe(&self) -> String {
+        if !self.is_on() {
+            "No turn clock".into()
+        } else if self.bank_secs == 0 {
+            format!("{}s a turn", self.turn_secs)
+        } else {
+            format!("{}s a turn, {}s bank", self.turn_secs, self.bank_secs)
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use crate::*;
+
+// MatchConfig and MatchStatus come from the proto, lower totals are better
+impl MatchConfig {
+    pub fn check(&self) -> GameResult<()> {
+        if self.target_hands == 0 && self.target_points == 0 {
+            return Err(GameError::PermissionDenied(
+                "A match needs a target of hands or points!".into()
+            ))
+        }
+
+        Ok(())
+    }
+
+    // hold points of each seat, less the bonus of the winner
+    pub fn hand_points(&self, ge: &GameEnding) -> Vec<i32> {
+        let mut points: Vec<i32> = rating::hold_points(ge).into_iter().map(
+            |p| p as i32
+        ).collect();
+        let bonus = match ge.winner_state() {
+            GameWinnerState::Normal => 0,
+            GameWinnerState::Clear => self.clear_bonus,
+            GameWinnerState::Seven => self.seven_bonus,
+        };
+        if let Some(p) = points.get_mut(ge.winner as usize) {
+            *p -= bonus;
+        }
+
+        points
+    }
+
+    pub fn is_over(&self, hands: u32, totals: &[i32]) -> bool {
+        (self.target_hands != 0 && hands >= self.target_hands)
+            || (self.target_points != 0
+                && totals.iter().any(|&t| t >= self.target_points as i32))
+    }
+
+    // one line a target, for showing in clients
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = vec![];
+        if self.target_hands != 0 {
+            lines.push(format!("Match of {} hands", self.target_hands));
+        }
+        if self.target_points != 0 {
+            lines.push(format!("Match ends at {} points", self.target_points));
+        }
+        if self.clear_bonus != 0 {
+            lines.push(format!("Clear win: {:+} points", -self.clear_bonus));
+        }
+        if self.seven_bonus != 0 {
+            lines.push(format!("Seven win: {:+} points", -self.seven_bonus));
+        }
+        lines
+    }
+}
+
+impl MatchInfo {
+    pub fn new(config: MatchConfig, seats: usize) -> Self {
+        Self {
+            config: Some(config),
+            totals: vec![0; seats],
+            leaders: (0..seats as u32).collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn get_config(&self) -> MatchConfig {
+        self.config.unwrap_or_default()
+    }
+
+    pub fn add_hand(&mut self, ge: &GameEnding) -> GameResult<()> {
+        if self.over {
+            return Err(GameError::PermissionDenied("Match is over!".into()))
+        }
+
+        let points = self.get_config().hand_points(ge);
+        if points.len() != self.totals.len() {
+            return Err(GameError::PermissionDenied(
+                format!("A hand of {} players in a match of {}!", points.len(), self.totals.len())
+            ))
+        }
+
+        for (t, p) in self.totals.iter_mut().zip(&points) {
+            *t += p;
+        }
+        self.history.push(HandScore { points });
+        self.hands += 1;
+        self.over = self.get_config().is_over(self.hands, &self.totals);
+        self.leaders = self.get_leaders();
+
+        Ok(())
+    }
+
+    fn get_leaders(&self) -> Vec<u32> {
+        let Some(&low) = self.totals.iter().min() else {
+            return vec![];
+        };
+        self.totals.iter().enumerate().filter_map(
+            |(pid, &t)| (t == low).then_some(pid as u32)
+        ).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // each seat holds one card of its points
+    fn ending(points: &[u32], winner: usize, state: GameWinnerState) -> GameEnding {
+        GameEnding {
+            hold: points.iter().map(|&num| HoldList {
+                holds: if num == 0 { vec![] } else { vec![CardInfo { suit: 0, num }] },
+            }).collect(),
+            winner: winner as u32,
+            winner_state: state.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn match_ends_after_its_hands() {
+        let config = MatchConfig { target_hands: 2, ..Default::default() };
+        let mut mi = MatchInfo::new(config, 3);
+        mi.add_hand(&ending(&[0, 5, 9], 0, GameWinnerState::Normal)).unwrap();
+        assert!(!mi.over);
+        mi.add_hand(&ending(&[7, 0, 1], 1, GameWinnerState::Normal)).unwrap();
+        assert!(mi.over);
+        assert_eq!(mi.hands, 2);
+        assert_eq!(mi.totals, vec![7, 5, 10]);
+        assert_eq!(mi.leaders, vec![1]);
+        assert!(mi.add_hand(&ending(&[0, 1, 2], 0, GameWinnerState::Normal)).is_err());
+        assert_eq!(mi.hands, 2);
+    }
+
+    #[test]
+    fn tie_at_the_target() {
+        let config = MatchConfig { target_points: 10, ..Default::default() };
+        let mut mi = MatchInfo::new(config, 4);
+        assert_eq!(mi.leaders, vec![0, 1, 2, 3]);
+        mi.add_hand(&ending(&[3, 0, 3, 9], 1, GameWinnerState::Normal)).unwrap();
+        assert!(!mi.over);
+        // two seats reach the target at once, two share the lead
+        mi.add_hand(&ending(&[1, 4, 7, 1], 0, GameWinnerState::Normal)).unwrap();
+        assert!(mi.over);
+        assert_eq!(mi.totals, vec![4, 4, 10, 10]);
+        assert_eq!(mi.leaders, vec![0, 1]);
+    }
+
+    #[test]
+    fn totals_carry_across_hands() {
+        let config = MatchConfig { clear_bonus: 5, seven_bonus: 10, ..Default::default() };
+        let mut mi = MatchInfo::new(config, 3);
+        mi.add_hand(&ending(&[2, 6, 8], 0, GameWinnerState::Clear)).unwrap();
+        mi.add_hand(&ending(&[4, 0, 3], 1, GameWinnerState::Seven)).unwrap();
+        mi.add_hand(&ending(&[5, 1, 0], 2, GameWinnerState::Normal)).unwrap();
+        assert!(!mi.over);
+        assert_eq!(mi.hands, 3);
+        let history: Vec<Vec<i32>> = mi.history.iter().map(|h| h.points.clone()).collect();
+        assert_eq!(history, vec![vec![-3, 6, 8], vec![4, -10, 3], vec![5, 1, 0]]);
+        assert_eq!(mi.totals, vec![6, -3, 11]);
+        assert_eq!(mi.leaders, vec![1]);
+
+        // every hand is the same size as the match
+        assert!(mi.add_hand(&ending(&[1, 2, 3, 4], 0, GameWinnerState::Normal)).is_err());
+        assert_eq!(mi.hands, 3);
+    }
+}
@@ -0,0 +1,35 @@
+use crate::*;
+use crate::room::MsgRX;
+use crate::handle::WRoom;
+use tokio::time;
+
+// give humans a moment to see what happened before the bot moves
+const BOT_THINK_TIME: time::Duration = time::Duration::from_millis(800);
+
+pub fn new_strategy(kind: BotKind) -> Box<dyn Strategy> {
+    match kind {
+        BotKind::Heuristic => Box::new(HeuristicBot::new()),
+        BotKind::Random => Box::new(RandomBot::new(rand::random())),
+    }
+}
+
+// the bot listens on its own seat just like a human client does,
+// and exits when the room drops its seat
+pub fn spawn_bot(aroom: WRoom, mut rx: MsgRX, mut strategy: Box<dyn Strategy>) {
+    tokio::spawn(async move {
+        while let Some(Ok(GameMsg { your_id, msg, .. })) = rx.recv().await {
+            match msg {
+                Some(Msg::Start(_)) | Some(Msg::Play(_)) | Some(Msg::BotTakeover(_)) => {}
+                _ => continue,
+            }
+
+            time::sleep(BOT_THINK_TIME).await;
+
+            let Some(room) = aroom.upgrade() else {
+                break;
+            };
+            room.bot_play(your_id as usize, strategy.as_mut()).await;
+        }
+        info!("Bot task exits");
+    });
+}
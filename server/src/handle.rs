@@ -0,0 +1,243 @@
+use crate::*;
+use crate::room::{self, MsgRX};
+use tokio::sync::{mpsc, oneshot};
+
+pub type Reply<T> = oneshot::Sender<RPCResult<T>>;
+
+// everything that touches a room, handled one by one by the room task
+#[derive(Debug)]
+pub enum RoomCmd {
+    // the password is checked by RoomHandle before, Argon2 would hold up the room
+    PasswordHash { reply: Reply<String> },
+    JoinRoom { player: PlayerInfo, reply: Reply<(usize, String)> },
+    GameStream { pid: usize, session: Option<String>, reply: Reply<MsgRX> },
+    StreamReady { pid: usize, session: Option<String>, reply: Reply<()> },
+    RoomStatus { reply: Reply<RoomInfo> },
+    Summary { reply: Reply<RoomSummary> },
+    MatchStatus { reply: Reply<MatchInfo> },
+    GameReady { pid: usize, session: Option<String>, reply: Reply<u32> },
+    GameStatus { pid: usize, session: Option<String>, reply: Reply<GameInfo> },
+    // without session, get what spectators see
+    Snapshot { pid: usize, session: Option<String>, password_ok: bool, reply: Reply<GameSnapshot> },
+    PlayCard { pi: PlayInfo, session: Option<String>, reply: Reply<()> },
+    SendChat { pid: usize, text: String, session: Option<String>, reply: Reply<()> },
+    SendReaction { pid: usize, reaction: Reaction, session: Option<String>, reply: Reply<()> },
+    // host only, target is the seat acted on
+    KickPlayer { pid: usize, target: usize, session: Option<String>, reply: Reply<()> },
+    LockRoom { pid: usize, locked: bool, session: Option<String>, reply: Reply<()> },
+    TransferHost { pid: usize, target: usize, session: Option<String>, reply: Reply<()> },
+    ChangeSettings { pid: usize, settings: RoomSettings, session: Option<String>, reply: Reply<()> },
+    ExitGame { pid: usize, session: Option<String>, reply: Reply<()> },
+    // reply how many human players are left
+    ExitRoom { pid: usize, session: Option<String>, reply: Reply<usize> },
+    AddBot { kind: BotKind, session: Option<String>, reply: Reply<usize> },
+    ResumeSession { session: String, reply: Reply<MsgRX> },
+    // a private room needs its password or a member's session
+    Spectate { session: Option<String>, password_ok: bool, reply: Reply<MsgRX> },
+    // without session, get what spectators got
+    FetchSince {
+        pid: usize, session: Option<String>, password_ok: bool, seq: u64, reply: Reply<GameMsgList>
+    },
+    // None if it is not the bot's turn
+    BotView { pid: usize, reply: Reply<Option<PlayerView>> },
+    BotPlay { pid: usize, play: Play, reply: Reply<()> },
+    // reply whether the room was used since last time
+    WatchDog { reply: Reply<bool> },
+}
+
+// the room task exits after every handle is dropped
+#[derive(Debug, Clone)]
+pub struct RoomHandle {
+    tx: mpsc::Sender<RoomCmd>,
+}
+
+// for bot tasks, which should not keep a room open
+#[derive(Debug, Clone)]
+pub struct WRoom(mpsc::WeakSender<RoomCmd>);
+
+impl WRoom {
+    pub fn upgrade(&self) -> Option<RoomHandle> {
+        self.0.upgrade().map(|tx| RoomHandle { tx })
+    }
+}
+
+fn session_owned(session: Option<&str>) -> Option<String> {
+    session.map(String::from)
+}
+
+impl RoomHandle {
+    pub fn new(tx: mpsc::Sender<RoomCmd>) -> Self {
+        Self { tx }
+    }
+
+    pub fn downgrade(&self) -> WRoom {
+        WRoom(self.tx.downgrade())
+    }
+
+    pub fn same_room(&self, other: &Self) -> bool {
+        self.tx.same_channel(&other.tx)
+    }
+
+    async fn call<T>(&self, cmd: impl FnOnce(Reply<T>) -> RoomCmd) -> RPCResult<T> {
+        let closed = || Status::new(
+            Code::NotFound,
+            "Room is closed!"
+        );
+
+        let (reply, rx) = oneshot::channel();
+        self.tx.send(cmd(reply)).await.map_err(|_| closed())?;
+        rx.await.map_err(|_| closed())?
+    }
+
+    // verified on the caller's task, so a wrong password holds up nobody else
+    async fn password_ok(&self, password: &str) -> RPCResult<bool> {
+        let hash = self.call(|reply| RoomCmd::PasswordHash { reply }).await?;
+        Ok(room::check_password(&hash, password).await)
+    }
+
+    // return (pid, session)
+    pub async fn join_room(&self, player: PlayerInfo, password: &str) -> RPCResult<(usize, String)> {
+        if !self.password_ok(password).await? {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                "Wrong password for the room!"
+            ));
+        }
+        self.call(|reply| RoomCmd::JoinRoom { player, reply }).await
+    }
+
+    pub async fn game_stream(&self, pid: usize, session: Option<&str>) -> RPCResult<MsgRX> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::GameStream { pid, session, reply }).await
+    }
+
+    pub async fn stream_ready(&self, pid: usize, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::StreamReady { pid, session, reply }).await
+    }
+
+    pub async fn room_status(&self) -> RPCResult<RoomInfo> {
+        self.call(|reply| RoomCmd::RoomStatus { reply }).await
+    }
+
+    pub async fn summary(&self) -> RPCResult<RoomSummary> {
+        self.call(|reply| RoomCmd::Summary { reply }).await
+    }
+
+    pub async fn match_status(&self) -> RPCResult<MatchInfo> {
+        self.call(|reply| RoomCmd::MatchStatus { reply }).await
+    }
+
+    // return how many players are not ready
+    pub async fn game_ready(&self, pid: usize, session: Option<&str>) -> RPCResult<u32> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::GameReady { pid, session, reply }).await
+    }
+
+    pub async fn game_status(&self, pid: usize, session: Option<&str>) -> RPCResult<GameInfo> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::GameStatus { pid, session, reply }).await
+    }
+
+    pub async fn snapshot(
+        &self, pid: usize, session: Option<&str>, password: String
+    ) -> RPCResult<GameSnapshot> {
+        let password_ok = session.is_none() && self.password_ok(&password).await?;
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::Snapshot { pid, session, password_ok, reply }).await
+    }
+
+    pub async fn play_card(&self, pi: PlayInfo, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::PlayCard { pi, session, reply }).await
+    }
+
+    pub async fn send_chat(&self, pid: usize, text: String, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::SendChat { pid, text, session, reply }).await
+    }
+
+    pub async fn send_reaction(&self, pid: usize, reaction: Reaction, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::SendReaction { pid, reaction, session, reply }).await
+    }
+
+    pub async fn kick_player(&self, pid: usize, target: usize, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::KickPlayer { pid, target, session, reply }).await
+    }
+
+    pub async fn lock_room(&self, pid: usize, locked: bool, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::LockRoom { pid, locked, session, reply }).await
+    }
+
+    pub async fn transfer_host(&self, pid: usize, target: usize, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::TransferHost { pid, target, session, reply }).await
+    }
+
+    pub async fn change_settings(
+        &self, pid: usize, settings: RoomSettings, session: Option<&str>
+    ) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::ChangeSettings { pid, settings, session, reply }).await
+    }
+
+    pub async fn exit_game(&self, pid: usize, session: Option<&str>) -> RPCResult<()> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::ExitGame { pid, session, reply }).await
+    }
+
+    pub async fn exit_room(&self, pid: usize, session: Option<&str>) -> RPCResult<usize> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::ExitRoom { pid, session, reply }).await
+    }
+
+    pub async fn add_bot(&self, kind: BotKind, session: Option<&str>) -> RPCResult<usize> {
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::AddBot { kind, session, reply }).await
+    }
+
+    pub async fn resume_session(&self, session: String) -> RPCResult<MsgRX> {
+        self.call(|reply| RoomCmd::ResumeSession { session, reply }).await
+    }
+
+    pub async fn spectate(&self, session: Option<&str>, password: String) -> RPCResult<MsgRX> {
+        let password_ok = self.password_ok(&password).await?;
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::Spectate { session, password_ok, reply }).await
+    }
+
+    pub async fn fetch_since(
+        &self, pid: usize, session: Option<&str>, password: String, seq: u64
+    ) -> RPCResult<GameMsgList> {
+        let password_ok = session.is_none() && self.password_ok(&password).await?;
+        let session = session_owned(session);
+        self.call(|reply| RoomCmd::FetchSince { pid, session, password_ok, seq, reply }).await
+    }
+
+    pub async fn watch_dog(&self) -> RPCResult<bool> {
+        self.call(|reply| RoomCmd::WatchDog { reply }).await
+    }
+
+    // the strategy runs in the bot task, and the room checks again before playing
+    pub async fn bot_play(&self, pid: usize, strategy: &mut dyn Strategy) {
+        let view = match self.call(|reply| RoomCmd::BotView { pid, reply }).await {
+            Ok(Some(v)) => v,
+            Ok(None) => return,
+            Err(e) => {
+                error!("Bot {} cannot get its view: {}", pid, e);
+                return;
+            }
+        };
+
+        if let Some(play) = strategy.choose_play(&view) {
+            self.call(|reply| RoomCmd::BotPlay { pid, play, reply }).await.unwrap_or_else(
+                |e| error!("Bot {} failed to play: {}", pid, e)
+            );
+        } else {
+            error!("Bot {} has nothing to play!", pid);
+        }
+    }
+}
@@ -1,5 +1,10 @@
+mod bot;
+mod handle;
+mod profile;
+mod replay;
 mod room;
 mod server;
+mod store;
 
 use server::*;
 use heart7_rule::*;
@@ -11,11 +16,25 @@ use log4rs::config::{Appender, Config, Root};
 use clap::Parser;
 use log::*;
 pub use tonic::{Code, Request, Response, Status};
+use tonic::codegen::http::HeaderName;
+use tonic_web::GrpcWebLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use std::time::Duration;
+use std::path::PathBuf;
+use std::sync::Arc;
+use room::RoomManager;
+use store::FileStore;
+use replay::ReplayWriter;
+use profile::ProfileRegistry;
 
 pub const DEFAULT_PORT: u16 = 20007;
 
 pub const DEFAULT_CHANNEL_SIZE: usize = 64;
 
+// how many of the last seqs each room keeps for FetchSince,
+// one GameMsg per seat and one for spectators for each seq
+pub const DEFAULT_BACKLOG_SIZE: usize = 256;
+
 pub type RPCResult<T> = Result<T, tonic::Status>;
 
 #[derive(Parser, Debug)]
@@ -24,13 +43,27 @@ struct Args {
     /// Listen address: <IP>:<PORT>
     #[arg(long, default_value("0.0.0.0:20007"))]
     listen: String,
+
+    /// Save rooms in this directory, and restore them on startup
+    #[arg(long)]
+    store_dir: Option<PathBuf>,
+
+    /// Save a replay of every finished hand in this directory
+    #[arg(long)]
+    replay_dir: Option<PathBuf>,
+
+    /// Keep player profiles and ratings in this file
+    #[arg(long)]
+    profile_file: Option<PathBuf>,
 }
 
-// const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
-// const DEFAULT_EXPOSED_HEADERS: [&str; 3] =
-//     ["grpc-status", "grpc-message", "grpc-status-details-bin"];
-// const DEFAULT_ALLOW_HEADERS: [&str; 4] =
-//     ["x-grpc-web", "content-type", "x-user-agent", "grpc-timeout"];
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_EXPOSED_HEADERS: [&str; 3] =
+    ["grpc-status", "grpc-message", "grpc-status-details-bin"];
+// same as tonic_web::enable, plus the session and password headers
+const DEFAULT_ALLOW_HEADERS: [&str; 6] = [
+    "x-grpc-web", "content-type", "x-user-agent", "grpc-timeout", SESSION_KEY, PASSWORD_KEY
+];
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
@@ -46,35 +79,45 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
 
     let sock_addr = args.listen.parse()?;
-    let server = Heart7D::default();
+    let mut rm = match args.store_dir {
+        Some(dir) => RoomManager::new(Arc::new(FileStore::new(dir).await?)),
+        None => RoomManager::default(),
+    };
+    if let Some(dir) = args.replay_dir {
+        rm = rm.with_replays(ReplayWriter::new(dir).await?);
+    }
+    if let Some(path) = args.profile_file {
+        rm = rm.with_profiles(ProfileRegistry::new(path).await?);
+    }
+    rm.restore().await;
+    let server = Heart7D::new(rm);
     server.spawn_watch_dog();
 
     info!("Heart7 Server serving on {}..", sock_addr);
     Server::builder()
         .accept_http1(true)
-        .add_service(tonic_web::enable(Heart7Server::new(server)))
-        // .layer(
-        //     CorsLayer::new()
-        //         .allow_origin(AllowOrigin::mirror_request())
-        //         .allow_credentials(true)
-        //         .max_age(DEFAULT_MAX_AGE)
-        //         .expose_headers(
-        //             DEFAULT_EXPOSED_HEADERS
-        //                 .iter()
-        //                 .cloned()
-        //                 .map(HeaderName::from_static)
-        //                 .collect::<Vec<HeaderName>>(),
-        //         )
-        //         .allow_headers(
-        //             DEFAULT_ALLOW_HEADERS
-        //                 .iter()
-        //                 .cloned()
-        //                 .map(HeaderName::from_static)
-        //                 .collect::<Vec<HeaderName>>(),
-        //         ),
-        // )
-        // .layer(GrpcWebLayer::new())
-        // .add_service(Heart7Server::new(server))
+        .layer(
+            CorsLayer::new()
+                .allow_origin(AllowOrigin::mirror_request())
+                .allow_credentials(true)
+                .max_age(DEFAULT_MAX_AGE)
+                .expose_headers(
+                    DEFAULT_EXPOSED_HEADERS
+                        .iter()
+                        .cloned()
+                        .map(HeaderName::from_static)
+                        .collect::<Vec<HeaderName>>(),
+                )
+                .allow_headers(
+                    DEFAULT_ALLOW_HEADERS
+                        .iter()
+                        .cloned()
+                        .map(HeaderName::from_static)
+                        .collect::<Vec<HeaderName>>(),
+                ),
+        )
+        .layer(GrpcWebLayer::new())
+        .add_service(Heart7Server::new(server))
         .serve(sock_addr)
         .await?;
 
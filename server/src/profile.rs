@@ -0,0 +1,89 @@
+use crate::*;
+use heart7_rule::rating::{self, INITIAL_RATING};
+use prost::Message;
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+use tokio::fs;
+use tokio::sync::Mutex;
+
+pub const DEFAULT_LEADERBOARD_SIZE: usize = 10;
+
+// profiles by player name, kept in one protobuf file if given,
+// names are not owned by anyone, so whoever takes a name plays for its profile
+#[derive(Debug, Default)]
+pub struct ProfileRegistry {
+    path: Option<PathBuf>,
+    profiles: Mutex<HashMap<String, Profile>>,
+}
+
+impl ProfileRegistry {
+    pub async fn new(path: PathBuf) -> io::Result<Self> {
+        let profiles = match fs::read(&path).await {
+            Ok(buf) => ProfileList::decode(buf.as_slice())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+                .profiles.into_iter().map(|p| (p.name.clone(), p)).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        info!("Loaded {} player profiles", profiles.len());
+
+        Ok(Self {
+            path: Some(path),
+            profiles: Mutex::new(profiles),
+        })
+    }
+
+    pub async fn get(&self, name: &str) -> Profile {
+        self.profiles.lock().await.get(name).cloned().unwrap_or_else(
+            || Profile::new(name.into())
+        )
+    }
+
+    // highest rating first
+    pub async fn leaderboard(&self, limit: usize) -> ProfileList {
+        let limit = if limit == 0 { DEFAULT_LEADERBOARD_SIZE } else { limit };
+        let mut profiles: Vec<Profile> = self.profiles.lock().await.values().cloned().collect();
+        profiles.sort_by(
+            |a, b| b.rating.total_cmp(&a.rating).then(b.games.cmp(&a.games))
+        );
+        profiles.truncate(limit);
+
+        ProfileList { profiles }
+    }
+
+    // bots play at the initial rating and keep no profile
+    pub async fn record_hand(&self, names: &[String], humans: &[bool], ge: &GameEnding) -> io::Result<()> {
+        let mut profiles = self.profiles.lock().await;
+        let ratings: Vec<f64> = names.iter().zip(humans).map(
+            |(name, &human)| match profiles.get(name) {
+                Some(p) if human => p.rating,
+                _ => INITIAL_RATING,
+            }
+        ).collect();
+        let deltas = rating::rate_hand(&ratings, ge);
+
+        for (pid, name) in names.iter().enumerate() {
+            if !humans[pid] {
+                continue;
+            }
+            profiles.entry(name.clone()).or_insert_with(
+                || Profile::new(name.clone())
+            ).add_hand(ge, pid, deltas[pid]);
+        }
+
+        self.save(&profiles).await
+    }
+
+    async fn save(&self, profiles: &HashMap<String, Profile>) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let list = ProfileList { profiles: profiles.values().cloned().collect() };
+        // never leave a half written file behind
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, list.encode_to_vec()).await?;
+        fs::rename(&tmp, path).await
+    }
+}
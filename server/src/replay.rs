@@ -0,0 +1,32 @@
+use crate::*;
+use crate::store::hex_name;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs;
+
+// writes a GameRecord for every finished hand
+#[derive(Debug)]
+pub struct ReplayWriter {
+    dir: PathBuf,
+}
+
+impl ReplayWriter {
+    pub async fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    // <hex of room ID>-<ms when written>.json, so hands of a room sort by time
+    pub async fn write(&self, roomid: &str, rec: &GameRecord) -> io::Result<()> {
+        let name = format!("{}-{}", hex_name(roomid), now_ms());
+        let path = self.dir.join(name).with_extension("json");
+        let json = rec.to_json().map_err(io::Error::other)?;
+        fs::write(path, json).await
+    }
+}
+
+// for TimedPlay::at
+pub fn now_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64)
+}
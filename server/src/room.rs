@@ -1,31 +1,145 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use tokio::sync::RwLock;
 use std::sync::Arc;
-use tokio::sync::mpsc::{self, Sender, Receiver};
+use tokio::sync::mpsc::{self, Sender, Receiver, error::TrySendError};
+use tokio::sync::broadcast;
 use crate::*;
 use tokio::time;
-use tokio_util::sync::CancellationToken;
-use rand::{rng, seq::SliceRandom};
+use rand::{rng, Rng, SeedableRng, rngs::StdRng};
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier, password_hash::SaltString};
+use heart7_rule::dealer::{self, DEFAULT_SEATS};
+use crate::bot;
+use crate::handle::{RoomCmd, RoomHandle, WRoom};
+use crate::store::{RoomStore, NoStore};
+use crate::replay::{ReplayWriter, now_ms};
+use crate::profile::ProfileRegistry;
+
+const PLAYER_WATCH_DOG_PERIOD: time::Duration = time::Duration::from_secs(600);
+// how soon a stand-in bot plays for a human whose stream is gone mid-turn
+const STAND_IN_CHECK_PERIOD: time::Duration = time::Duration::from_secs(5);
+// longer chat msgs are refused, not cut
+const CHAT_MAX_CHARS: usize = 120;
+// at most CHAT_BURST msgs of one seat in any CHAT_WINDOW
+const CHAT_BURST: usize = 5;
+const CHAT_WINDOW: time::Duration = time::Duration::from_secs(10);
+// the same for reactions, which come quicker
+const REACTION_BURST: usize = 3;
+const REACTION_WINDOW: time::Duration = time::Duration::from_secs(5);
+// rooms asked for without a name get a code of these, no 0/O or 1/I to mix up
+const INVITE_CODE_CHARS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const INVITE_CODE_LEN: usize = 6;
+// hexed into file names of the store and the replays, which must fit in NAME_MAX
+const ROOM_ID_MAX_BYTES: usize = 100;
 
-type ARoom = Arc<RwLock<Room>>;
 type MsgTX = Sender<Result<GameMsg, Status>>;
 pub type MsgRX = Receiver<Result<GameMsg, Status>>;
 
-#[derive(Debug, Default)]
+// drop the sends out of the window, then take one more if under burst
+fn throttle(sent: &mut VecDeque<time::Instant>, burst: usize, window: time::Duration) -> bool {
+    let now = time::Instant::now();
+    while sent.front().is_some_and(|&t| now.duration_since(t) >= window) {
+        sent.pop_front();
+    }
+    if sent.len() >= burst {
+        return false
+    }
+    sent.push_back(now);
+    true
+}
+
+#[derive(Debug, Clone)]
 pub struct RoomManager {
-    rooms: Arc<RwLock<HashMap<String, ARoom>>>,
+    // only held for lookups, never across a room call
+    rooms: Arc<RwLock<HashMap<String, RoomHandle>>>,
+    // fired whenever any room changes, for lobby streams
+    lobby: broadcast::Sender<()>,
+    store: Arc<dyn RoomStore>,
+    replays: Option<Arc<ReplayWriter>>,
+    profiles: Arc<ProfileRegistry>,
+}
+
+impl Default for RoomManager {
+    fn default() -> Self {
+        Self::new(Arc::new(NoStore))
+    }
 }
 
+// owned by its own task, see Room::run
 #[derive(Debug)]
-pub struct Room {
+struct Room {
     state: RoomState,
     id: String,
     game: Game,
-    // (game_tx, stream_ready)
-    gamemsg_tx: Vec<(MsgTX, bool)>,
+    seats: Vec<Seat>,
+    // watchers don't take a seat, and only get public messages
+    spectators: Vec<MsgTX>,
+    // session token of each player, for resuming
+    sessions: Vec<String>,
+    // session of the host, passed on to the next human when the host leaves
+    host: String,
+    // nobody else may join while locked
+    locked: bool,
+    // PHC string of Argon2id, empty for anyone to join
+    password_hash: String,
     alive: bool,
-    watch_dog_cancel: CancellationToken,
     player_alive: bool,
+    // for handing out to bot tasks
+    myself: WRoom,
+    lobby: broadcast::Sender<()>,
+    // seq of the latest GameMsg
+    seq: u64,
+    // recently sent GameMsg, for clients who missed some
+    backlog: VecDeque<SentMsg>,
+    // the largest seq dropped from backlog
+    dropped_seq: u64,
+    // the shuffled deck of the current hand
+    deal: Vec<u32>,
+    dealer: Dealer,
+    // None for random hands
+    deal_config: Option<DealConfig>,
+    // how many hands dealt so far
+    hands: u32,
+    // when each play of the current hand was made, 0 if unknown
+    play_times: Vec<u64>,
+    // None for single hands, starts over with the next hand once over
+    match_info: Option<MatchInfo>,
+    // None for no clock
+    turn_timer: Option<TurnTimer>,
+    // time bank left of each seat, full again every hand
+    banks: Vec<time::Duration>,
+    // the clock of whoever plays next, None while nobody is on the clock
+    turn: Option<TurnClock>,
+    store: Arc<dyn RoomStore>,
+    replays: Option<Arc<ReplayWriter>>,
+    profiles: Arc<ProfileRegistry>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TurnClock {
+    pid: usize,
+    started: time::Instant,
+    deadline: time::Instant,
+}
+
+#[derive(Debug)]
+struct SentMsg {
+    // None for spectators
+    session: Option<String>,
+    gm: GameMsg,
+}
+
+#[derive(Debug, Clone)]
+struct Seat {
+    tx: MsgTX,
+    stream_ready: bool,
+    bot: Option<BotKind>,
+    // a bot playing for a human who lost connection, until this hand ends
+    stand_in: Option<MsgTX>,
+    // a stand-in played some of this hand, which then leaves the profile alone
+    stood_in: bool,
+    // when the recent chat msgs were sent, for throttling
+    chats: VecDeque<time::Instant>,
+    reactions: VecDeque<time::Instant>,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -37,109 +151,144 @@ enum RoomState {
 }
 
 impl RoomManager {
+    pub fn new(store: Arc<dyn RoomStore>) -> Self {
+        let (lobby, _) = broadcast::channel(DEFAULT_CHANNEL_SIZE);
+        Self {
+            rooms: Default::default(),
+            lobby,
+            store,
+            replays: None,
+            profiles: Default::default(),
+        }
+    }
+
+    // save every finished hand as a GameRecord
+    pub fn with_replays(mut self, replays: ReplayWriter) -> Self {
+        self.replays = Some(Arc::new(replays));
+        self
+    }
+
+    // keep player profiles in a file instead of only in memory
+    pub fn with_profiles(mut self, profiles: ProfileRegistry) -> Self {
+        self.profiles = Arc::new(profiles);
+        self
+    }
+
+    pub fn profiles(&self) -> &ProfileRegistry {
+        &self.profiles
+    }
+
+    // bring back the rooms saved before last shutdown
+    pub async fn restore(&self) {
+        let recs = match self.store.load_all().await {
+            Ok(recs) => recs,
+            Err(e) => {
+                error!("Cannot load saved rooms: {}", e);
+                return;
+            }
+        };
+
+        let mut rooms = self.rooms.write().await;
+        for rec in recs {
+            let id = rec.info.as_ref().map(|i| i.roomid.clone()).unwrap_or_default();
+            let deal_config = rec.deal_config.clone();
+            let rules = rec.info.as_ref().and_then(|i| i.rules);
+            let seats = rec.info.as_ref().map_or(0, |i| i.seats as usize);
+            let match_config = rec.info.as_ref().and_then(|i| i.match_config);
+            let turn_timer = rec.info.as_ref().and_then(|i| i.turn_timer);
+            match Room::spawn(
+                id.clone(), self, deal_config, rules, seats, match_config, turn_timer,
+                String::new(), Some(rec)
+            ) {
+                Ok(room) => {
+                    info!("Room {} restored", id);
+                    rooms.insert(id, room);
+                }
+                Err(e) => error!("Cannot restore room {}: {}", id, e),
+            }
+        }
+    }
+
     pub fn spawn_watch_dog(&self) {
         let arooms = self.rooms.clone();
+        let lobby = self.lobby.clone();
         tokio::spawn(async move {
             info!("Room watch dog running");
             loop {
                 time::sleep(time::Duration::from_secs(3600)).await;
 
-                let mut rooms = arooms.write().await;
-                let keys: Vec<String> = rooms.iter().map(
-                    |(id, _)| id.clone()
+                let rooms: Vec<(String, RoomHandle)> = arooms.read().await.iter().map(
+                    |(id, r)| (id.clone(), r.clone())
                 ).collect();
 
-                for id in keys.iter() {
-                    let aroom = rooms.get(id).unwrap().clone();
-                    let mut room = aroom.write().await;
-                    if room.is_alive() {
-                        room.unset_alive();
-                    } else {
+                for (id, room) in rooms {
+                    if room.watch_dog().await.unwrap_or(false) {
+                        continue;
+                    }
+                    let mut rooms = arooms.write().await;
+                    // it may have been replaced while we were asking
+                    if rooms.get(&id).is_some_and(|r| r.same_room(&room)) {
                         info!("Removing room {} by watch dog", id);
-                        room.cancel();
-                        rooms.remove(id).unwrap();
+                        rooms.remove(&id);
+                        let _ = lobby.send(());
                     }
                 }
             }
         });
     }
 
-    pub async fn new_room(&self, name: &String) -> RPCResult<ARoom> {
+    // an empty name gets an invite code, return the roomid
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_room(
+        &self, name: &str, deal_config: Option<DealConfig>, rules: Option<RuleSet>,
+        seats: usize, match_config: Option<MatchConfig>, turn_timer: Option<TurnTimer>,
+        password: &str
+    ) -> RPCResult<(String, RoomHandle)> {
+        if name.len() > ROOM_ID_MAX_BYTES {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                format!("Room ID longer than {} bytes!", ROOM_ID_MAX_BYTES),
+            ));
+        }
+        // slow on purpose, so not while holding the rooms
+        let password_hash = hash_password(password).await?;
         let mut rooms = self.rooms.write().await;
 
-        if let Some(_) = rooms.get(name) {
+        let name = if name.is_empty() {
+            let mut code = Self::invite_code();
+            while rooms.contains_key(&code) {
+                code = Self::invite_code();
+            }
+            code
+        } else if rooms.contains_key(name) {
             return Err(Status::new(
                 Code::AlreadyExists,
                 format!("Room {} already exists!", name),
             ));
-        }
-
-        let r = Room {
-            state: RoomState::NotFull,
-            id: name.clone(),
-            alive: true,
-            watch_dog_cancel: CancellationToken::new(),
-            player_alive: true,
-            game: Game::new(),
-            gamemsg_tx: vec![],
+        } else {
+            name.to_string()
         };
 
-        let cancel = r.watch_dog_cancel.clone();
+        let room = Room::spawn(
+            name.clone(), self, deal_config, rules, seats, match_config, turn_timer,
+            password_hash, None
+        )?;
+        rooms.insert(name.clone(), room.clone());
+        let _ = self.lobby.send(());
 
-        let ar = Arc::new(RwLock::new(r));
-        rooms.insert(name.clone(), ar.clone());
-
-        // spawn watch dog
-        let aroom = ar.clone();
-        tokio::spawn(async move {
-            loop {
-                tokio::select! {
-                    _ = cancel.cancelled() => {
-                        break;
-                    }
-                    _ = time::sleep(time::Duration::from_secs(600)) => {
-                        let mut room = aroom.write().await;
-                        info!("Player watch dog shoots for room {}", room.get_id());
-                        match room.state {
-                            RoomState::WaitReady => {
-                                if room.player_alive {
-                                    room.player_alive = false;
-                                } else {
-                                    info!("In WaitReady: player watch dog kills unready");
-                                    room.kill_unready().unwrap();
-                                    let ri = room.get_room_info().unwrap();
-                                    room.send_gamemsg(
-                                        Msg::LoseConnection(ri)
-                                    ).await;
-                                }
-                            }
-                            RoomState::Gaming => {
-                                if room.player_alive {
-                                    room.player_alive = false;
-                                } else {
-                                    let next = room.game.get_next();
-                                    info!("In Gaming: player watch dog kills {:?}", next);
-                                    room.exit_room(next).unwrap();
-                                    let ri = room.get_room_info().unwrap();
-                                    room.send_gamemsg(
-                                        Msg::LoseConnection(ri)
-                                    ).await;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        });
+        Ok((name, room))
+    }
 
-        Ok(ar)
+    fn invite_code() -> String {
+        let mut r = rng();
+        (0..INVITE_CODE_LEN).map(
+            |_| INVITE_CODE_CHARS[r.random_range(0..INVITE_CODE_CHARS.len())] as char
+        ).collect()
     }
 
-    pub async fn get_room(&self, id: &String) -> RPCResult<ARoom> {
-        if let Some(ar) = self.rooms.read().await.get(id) {
-            ar.write().await.set_alive();
-            Ok(ar.clone())
+    pub async fn get_room(&self, id: &String) -> RPCResult<RoomHandle> {
+        if let Some(room) = self.rooms.read().await.get(id) {
+            Ok(room.clone())
         } else {
             Err(Status::new(
                 Code::NotFound,
@@ -148,9 +297,10 @@ impl RoomManager {
         }
     }
 
+    // the room task exits once the calls still in flight are done
     pub async fn del_room(&self, id: &String) -> RPCResult<()> {
-        if let Some(ar) = self.rooms.write().await.remove(id) {
-            ar.read().await.cancel();
+        if self.rooms.write().await.remove(id).is_some() {
+            let _ = self.lobby.send(());
             Ok(())
         } else {
             Err(Status::new(
@@ -159,10 +309,565 @@ impl RoomManager {
             ))
         }
     }
+
+    pub async fn list_rooms(&self) -> RoomList {
+        let rooms: Vec<RoomHandle> = self.rooms.read().await.values().cloned().collect();
+        let mut list = Vec::with_capacity(rooms.len());
+        for room in rooms {
+            // closed in the meantime
+            if let Ok(summary) = room.summary().await {
+                list.push(summary);
+            }
+        }
+        list.sort_by_key(
+            |s| s.info.as_ref().map(|i| i.roomid.clone())
+        );
+
+        RoomList { rooms: list }
+    }
+
+    pub fn subscribe_lobby(&self) -> broadcast::Receiver<()> {
+        self.lobby.subscribe()
+    }
+}
+
+// empty for no password, Argon2 is slow on purpose, so never on a room task
+pub async fn hash_password(password: &str) -> RPCResult<String> {
+    if password.is_empty() {
+        return Ok(String::new())
+    }
+    let password = password.to_string();
+    let salt: [u8; 16] = rng().random();
+    let hashed = tokio::task::spawn_blocking(move || {
+        let salt = SaltString::encode_b64(&salt)?;
+        Argon2::default().hash_password(password.as_bytes(), &salt).map(|h| h.to_string())
+    }).await;
+    match hashed {
+        Ok(Ok(hash)) => Ok(hash),
+        Ok(Err(e)) => Err(Status::new(Code::Internal, format!("Cannot hash password: {}", e))),
+        Err(e) => Err(Status::new(Code::Internal, format!("Cannot hash password: {}", e))),
+    }
+}
+
+// an empty hash lets anyone in, no password never opens any other
+pub async fn check_password(hash: &str, password: &str) -> bool {
+    if hash.is_empty() {
+        return true
+    }
+    if password.is_empty() {
+        return false
+    }
+    let (hash, password) = (hash.to_string(), password.to_string());
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&hash).is_ok_and(
+            |h| Argon2::default().verify_password(password.as_bytes(), &h).is_ok()
+        )
+    }).await.unwrap_or_else(|e| {
+        error!("Cannot check password: {}", e);
+        false
+    })
 }
 
-// must hold Room lock before calling
 impl Room {
+    #[allow(clippy::too_many_arguments)]
+    fn spawn(
+        id: String, rm: &RoomManager, deal_config: Option<DealConfig>,
+        rules: Option<RuleSet>, seats: usize, match_config: Option<MatchConfig>,
+        turn_timer: Option<TurnTimer>, password_hash: String, rec: Option<RoomRecord>
+    ) -> RPCResult<RoomHandle> {
+        // 0 for the usual table
+        let seats = if seats == 0 { DEFAULT_SEATS } else { seats };
+        dealer::check_seats(seats)?;
+        let hands = rec.as_ref().map_or(0, |r| r.hands);
+        let dealer = Self::new_dealer(&deal_config, seats, hands)?;
+        if let Some(dc) = &deal_config {
+            info!("Room {} deals by {:?}", id, dc);
+        }
+        let mut game = Game::new();
+        let rules = rules.unwrap_or_default();
+        if !rules.is_house_rules() {
+            info!("Room {} plays by {:?}", id, rules);
+        }
+        game.set_rules(rules)?;
+        game.set_seats(seats)?;
+        if seats != DEFAULT_SEATS {
+            info!("Room {} seats {} players", id, seats);
+        }
+        if let Some(mc) = &match_config {
+            mc.check()?;
+            info!("Room {} plays a match of {:?}", id, mc);
+        }
+        if let Some(tt) = &turn_timer {
+            tt.check()?;
+        }
+        // a zero clock is no clock
+        let turn_timer = turn_timer.filter(|tt| tt.is_on());
+        if let Some(tt) = &turn_timer {
+            info!("Room {} plays on a clock of {:?}", id, tt);
+        }
+
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        let handle = RoomHandle::new(tx);
+
+        let mut room = Room {
+            state: RoomState::NotFull,
+            id,
+            alive: true,
+            player_alive: true,
+            game,
+            seats: vec![],
+            spectators: vec![],
+            sessions: vec![],
+            host: String::new(),
+            locked: false,
+            password_hash,
+            myself: handle.downgrade(),
+            lobby: rm.lobby.clone(),
+            seq: 0,
+            backlog: VecDeque::with_capacity(DEFAULT_BACKLOG_SIZE),
+            dropped_seq: 0,
+            deal: vec![],
+            dealer,
+            deal_config,
+            hands,
+            play_times: vec![],
+            match_info: match_config.map(|mc| MatchInfo::new(mc, seats)),
+            turn_timer,
+            banks: vec![],
+            turn: None,
+            store: rm.store.clone(),
+            replays: rm.replays.clone(),
+            profiles: rm.profiles.clone(),
+        };
+        if let Some(rec) = rec {
+            room.restore(rec)?;
+        }
+        tokio::spawn(room.run(rx));
+
+        Ok(handle)
+    }
+
+    // a dealer for the config, picking up after `hands` hands
+    fn new_dealer(deal_config: &Option<DealConfig>, seats: usize, hands: u32) -> RPCResult<Dealer> {
+        let mut dealer = match deal_config.as_ref().and_then(|dc| dc.source.as_ref()) {
+            None => return Ok(Dealer::new(RandomDeal::new(StdRng::from_rng(&mut rng())))),
+            Some(deal_config::Source::Seed(seed)) => Dealer::seeded(*seed),
+            Some(deal_config::Source::Preset(p)) => Dealer::fixed(
+                p.cards.chunks(dealer::deck_size(seats)).map(|d| d.to_vec()).collect(), seats
+            )?,
+        };
+        for _ in 0..hands {
+            dealer.deal(seats)?;
+        }
+
+        Ok(dealer)
+    }
+
+    // the only place the room state lives, so every command and
+    // every GameMsg happens in a single order
+    async fn run(mut self, mut rx: Receiver<RoomCmd>) {
+        let mut player_watch_dog = time::interval_at(
+            time::Instant::now() + PLAYER_WATCH_DOG_PERIOD,
+            PLAYER_WATCH_DOG_PERIOD,
+        );
+        let mut stand_in_check = time::interval_at(
+            time::Instant::now() + STAND_IN_CHECK_PERIOD,
+            STAND_IN_CHECK_PERIOD,
+        );
+        stand_in_check.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                cmd = rx.recv() => match cmd {
+                    Some(cmd) => {
+                        let read_only = matches!(cmd,
+                            RoomCmd::RoomStatus {..} | RoomCmd::Summary {..}
+                            | RoomCmd::MatchStatus {..}
+                            | RoomCmd::GameStatus {..} | RoomCmd::Snapshot {..}
+                            | RoomCmd::FetchSince {..} | RoomCmd::BotView {..}
+                            | RoomCmd::Spectate {..} | RoomCmd::WatchDog {..}
+                        );
+                        let seq = self.seq;
+                        self.handle(cmd).await;
+                        // a msg sent is a seq to keep, even from a read only cmd
+                        if !read_only || self.seq != seq {
+                            self.save().await;
+                        }
+                    }
+                    None => break,
+                },
+                _ = player_watch_dog.tick() => {
+                    self.player_watch_dog().await;
+                    self.save().await;
+                }
+                _ = stand_in_check.tick(), if self.state == RoomState::Gaming => {
+                    let seq = self.seq;
+                    self.takeover_closed_seats().await;
+                    if self.seq != seq {
+                        self.save().await;
+                    }
+                }
+                _ = time::sleep_until(self.turn_deadline()), if self.turn.is_some() => {
+                    self.turn_timeout().await;
+                    self.save().await;
+                }
+            }
+        }
+
+        info!("Room {} closed", self.id);
+        self.store.remove(&self.id).await.unwrap_or_else(
+            |e| error!("Cannot remove saved room {}: {}", self.id, e)
+        );
+    }
+
+    async fn save(&self) {
+        self.store.save(&self.to_record()).await.unwrap_or_else(
+            |e| error!("Cannot save room {}: {}", self.id, e)
+        );
+    }
+
+    fn to_record(&self) -> RoomRecord {
+        RoomRecord {
+            info: self.get_room_info().ok(),
+            seats: self.seats.iter().map(|s| SeatRecord {
+                stream_ready: s.stream_ready,
+                is_bot: s.bot.is_some(),
+                bot_kind: s.bot.unwrap_or_default().into(),
+                stand_in: s.stand_in.is_some(),
+                stood_in: s.stood_in,
+            }).collect(),
+            sessions: self.sessions.clone(),
+            deal: self.deal.clone(),
+            plays: self.game.get_history().into_iter().map(|p| p.into()).collect(),
+            seq: self.seq,
+            deal_config: self.deal_config.clone(),
+            hands: self.hands,
+            match_info: self.match_info.clone(),
+            host: self.host.clone(),
+            locked: self.locked,
+            password_hash: self.password_hash.clone(),
+        }
+    }
+
+    // replay the saved hand, humans come back later by ResumeSession
+    fn restore(&mut self, rec: RoomRecord) -> RPCResult<()> {
+        let info = rec.info.ok_or(
+            Status::new(
+                Code::DataLoss,
+                "Empty RoomInfo in room record!"
+            )
+        )?;
+
+        for p in info.players {
+            self.game.add_player(p.name);
+        }
+        self.sessions = rec.sessions;
+        self.host = rec.host;
+        self.locked = rec.locked;
+        // the salted sha256 of older records cannot be turned into Argon2id
+        if !rec.password_hash.is_empty() && PasswordHash::new(&rec.password_hash).is_err() {
+            return Err(Status::new(
+                Code::DataLoss,
+                format!("Room {} has a password hash of an old format, create it again!", &self.id)
+            ));
+        }
+        self.password_hash = rec.password_hash;
+        if rec.match_info.is_some() {
+            self.match_info = rec.match_info;
+        }
+
+        match info.state {
+            Some(State::WaitReady(rl)) => {
+                for pid in rl.l {
+                    self.game.player_ready(pid as usize)?;
+                }
+                self.state = RoomState::WaitReady;
+            }
+            Some(State::Gaming(_)) | Some(State::EndGame(_)) => {
+                for pid in 0..self.game.get_player_num() {
+                    self.game.player_ready(pid)?;
+                }
+                self.game.new_game(rec.deal.clone())?;
+                for pi in rec.plays {
+                    self.game.play_card(pi.into())?;
+                    self.play_times.push(0);
+                }
+                self.deal = rec.deal;
+                self.state = if let Some(State::Gaming(_)) = info.state {
+                    RoomState::Gaming
+                } else {
+                    RoomState::EndGame
+                };
+            }
+            _ => {}
+        }
+
+        // msgs before restarting are lost
+        self.seq = rec.seq;
+        self.dropped_seq = rec.seq;
+
+        for sr in rec.seats {
+            // the old streams are gone, and so are human seats' receivers
+            let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+            let bot = sr.is_bot.then(|| BotKind::try_from(sr.bot_kind).unwrap_or_default());
+            if let Some(kind) = bot {
+                bot::spawn_bot(self.myself.clone(), rx, bot::new_strategy(kind));
+            }
+
+            let stand_in = (sr.stand_in && self.state == RoomState::Gaming).then(|| {
+                let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+                bot::spawn_bot(self.myself.clone(), rx, bot::new_strategy(BotKind::Heuristic));
+                tx
+            });
+
+            self.seats.push(Seat {
+                tx,
+                stream_ready: sr.stream_ready,
+                bot,
+                stand_in,
+                stood_in: sr.stood_in,
+                chats: VecDeque::new(),
+                reactions: VecDeque::new(),
+            });
+        }
+        if self.state == RoomState::Gaming {
+            // banks are not saved, so everyone gets a full one again
+            self.fill_banks();
+            self.arm_turn();
+        }
+        self.wake_bot();
+
+        Ok(())
+    }
+
+    // bots only move on msgs they see, so tell the one whose turn it is
+    fn wake_bot(&self) {
+        let next = self.game.get_next();
+        if !self.is_bot_turn(next) {
+            return;
+        }
+
+        let seat = &self.seats[next];
+        let tx = seat.stand_in.as_ref().unwrap_or(&seat.tx);
+        tx.try_send(Ok(GameMsg {
+            msg: Some(Msg::Start(next as u32)),
+            your_id: next as u32,
+            seq: self.seq,
+        })).unwrap_or_else(
+            |e| error!("Cannot wake bot {}: {}", next, e)
+        );
+    }
+
+    // a dropped reply means the caller has gone, nothing to do about it
+    async fn handle(&mut self, cmd: RoomCmd) {
+        if !matches!(cmd, RoomCmd::WatchDog {..} | RoomCmd::BotView {..} | RoomCmd::BotPlay {..}) {
+            self.set_alive();
+        }
+
+        match cmd {
+            RoomCmd::PasswordHash { reply } => {
+                let _ = reply.send(Ok(self.password_hash.clone()));
+            }
+            RoomCmd::JoinRoom { player, reply } => {
+                let _ = reply.send(self.add_player(&player));
+            }
+            RoomCmd::GameStream { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.get_game_stream_rx(pid),
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::StreamReady { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.stream_ready(pid).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::RoomStatus { reply } => {
+                let _ = reply.send(self.get_room_info());
+            }
+            RoomCmd::Summary { reply } => {
+                let _ = reply.send(Ok(self.get_summary()));
+            }
+            RoomCmd::MatchStatus { reply } => {
+                let _ = reply.send(self.get_match_info());
+            }
+            RoomCmd::GameReady { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.player_ready(pid).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::GameStatus { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.get_game_info(pid as u32),
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::Snapshot { pid, session, password_ok, reply } => {
+                // players ask with their session, anyone else gets what spectators see
+                let r = match session {
+                    Some(s) => match self.check_session(pid, Some(&s)) {
+                        Ok(()) => self.get_game_snapshot(Some(pid)),
+                        Err(e) => Err(e),
+                    },
+                    None => match self.check_watcher(None, password_ok) {
+                        Ok(()) => self.get_game_snapshot(None),
+                        Err(e) => Err(e),
+                    },
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::PlayCard { pi, session, reply } => {
+                let r = match self.check_session(pi.player as usize, session.as_deref()) {
+                    Ok(()) => self.play_and_broadcast(pi).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::SendChat { pid, text, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.send_chat(pid, &text).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::SendReaction { pid, reaction, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.send_reaction(pid, reaction).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::KickPlayer { pid, target, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.kick_player(pid, target).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::LockRoom { pid, locked, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.lock_room(pid, locked).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::TransferHost { pid, target, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.transfer_host(pid, target).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::ChangeSettings { pid, settings, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.change_settings(pid, settings).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::ExitGame { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.exit_game(pid).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::ExitRoom { pid, session, reply } => {
+                let r = match self.check_session(pid, session.as_deref()) {
+                    Ok(()) => self.exit_room(pid).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::AddBot { kind, session, reply } => {
+                let r = match self.check_member(session.as_deref()) {
+                    Ok(_) => self.add_bot(kind).await,
+                    Err(e) => Err(e),
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::ResumeSession { session, reply } => {
+                let r = self.resume_session(&session);
+                let resumed = r.is_ok();
+                let _ = reply.send(r);
+                // the clock of the current turn is not in ResumeInfo
+                if resumed {
+                    self.announce_turn().await;
+                }
+            }
+            RoomCmd::Spectate { session, password_ok, reply } => {
+                let r = match self.check_watcher(session.as_deref(), password_ok) {
+                    Ok(()) => self.add_spectator(),
+                    Err(e) => Err(e),
+                };
+                let added = r.is_ok();
+                let _ = reply.send(r);
+                if added {
+                    self.announce_turn().await;
+                }
+            }
+            RoomCmd::FetchSince { pid, session, password_ok, seq, reply } => {
+                // players ask with their session, anyone else gets what spectators got
+                let r = match session {
+                    Some(s) => match self.check_session(pid, Some(&s)) {
+                        Ok(()) => self.fetch_since(Some(&s), seq),
+                        Err(e) => Err(e),
+                    },
+                    None => match self.check_watcher(None, password_ok) {
+                        Ok(()) => self.fetch_since(None, seq),
+                        Err(e) => Err(e),
+                    },
+                };
+                let _ = reply.send(r);
+            }
+            RoomCmd::BotView { pid, reply } => {
+                let _ = reply.send(self.bot_view(pid));
+            }
+            RoomCmd::BotPlay { pid, play, reply } => {
+                let _ = reply.send(self.bot_play(pid, play).await);
+            }
+            RoomCmd::WatchDog { reply } => {
+                let alive = self.is_alive();
+                self.unset_alive();
+                let _ = reply.send(Ok(alive));
+            }
+        }
+    }
+
+    async fn player_watch_dog(&mut self) {
+        info!("Player watch dog shoots for room {}", self.id);
+        match self.state {
+            RoomState::WaitReady => {
+                if self.player_alive {
+                    self.player_alive = false;
+                } else {
+                    info!("In WaitReady: player watch dog kills unready");
+                    self.kill_unready().unwrap();
+                    let ri = self.get_room_info().unwrap();
+                    self.send_gamemsg(
+                        Msg::LoseConnection(ri)
+                    ).await;
+                }
+            }
+            RoomState::Gaming => {
+                if self.player_alive {
+                    self.player_alive = false;
+                } else {
+                    let next = self.game.get_next();
+                    info!("In Gaming: bot takes over {:?} by player watch dog", next);
+                    self.bot_takeover(next).await.unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+
     pub fn is_alive(&self) -> bool {
         self.alive
     }
@@ -175,14 +880,6 @@ impl Room {
         self.alive = false;
     }
 
-    pub fn get_id(&self) -> String {
-        self.id.clone()
-    }
-
-    pub fn cancel(&self) {
-        self.watch_dog_cancel.cancel();
-    }
-
     fn get_ready_list(&self) -> ReadyList {
         ReadyList {
             l: self.game.get_ready_list().into_iter().map(|s| s as u32).collect()
@@ -201,117 +898,524 @@ impl Room {
                 RoomState::WaitReady => State::WaitReady(self.get_ready_list()),
                 RoomState::Gaming => State::Gaming(self.game.get_next() as u32),
                 RoomState::EndGame => State::EndGame(0),
-            })
+            }),
+            rules: Some(self.game.get_rules()),
+            seats: self.game.get_seats() as u32,
+            match_config: self.match_info.as_ref().and_then(|mi| mi.config),
+            turn_timer: self.turn_timer,
+            host: self.host_pid().unwrap_or_default() as u32,
+            locked: self.locked,
+            has_password: !self.password_hash.is_empty(),
         })
     }
 
-    pub fn add_player(&mut self, p: &PlayerInfo) -> RPCResult<usize> {
-        if self.state != RoomState::NotFull {
-            return Err(Status::new(
-                Code::ResourceExhausted,
-                format!("Room {} is full!", &self.id)
-            ));
+    pub fn get_summary(&self) -> RoomSummary {
+        RoomSummary {
+            info: self.get_room_info().ok(),
+            seats: self.game.get_seats() as u32,
         }
-
-        let pid = self.game.add_player(p.name.clone());
-
-        Ok(pid)
     }
 
-    pub fn get_game_stream_rx(&mut self, pid: usize) -> RPCResult<super::room::MsgRX> {
-        if self.state != RoomState::NotFull {
-            return Err(Status::new(
-                Code::ResourceExhausted,
-                format!("Room {} is full!", &self.id)
-            ));
-        }
-        if pid < self.gamemsg_tx.len() {
-            return Err(Status::new(
-                Code::AlreadyExists,
-                format!("Room {} Player {} already exists", &self.id, pid),
-            ));
-        }
-
-        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
-        self.gamemsg_tx.push((tx, false));
-
-        Ok(rx)
+    fn lobby_changed(&self) {
+        // nobody in lobby is fine
+        let _ = self.lobby.send(());
     }
 
-    pub fn stream_ready(&mut self, pid: usize) -> RPCResult<()> {
-        if self.state != RoomState::NotFull {
+    // return (pid, session)
+    // the password is checked by RoomHandle
+    pub fn add_player(&mut self, p: &PlayerInfo) -> RPCResult<(usize, String)> {
+        // every seat may be taken before all streams are open
+        if self.state != RoomState::NotFull
+            || self.game.get_player_num() >= self.game.get_seats()
+        {
             return Err(Status::new(
                 Code::ResourceExhausted,
                 format!("Room {} is full!", &self.id)
             ));
         }
-        if pid > self.gamemsg_tx.len() {
-            return Err(Status::new(
-                Code::NotFound,
-                format!("Room {} Player {} does not exist", &self.id, pid),
-            ));
-        }
-        let gtx = self.gamemsg_tx.get_mut(pid).unwrap();
-        if gtx.1 {
+        if self.locked {
             return Err(Status::new(
                 Code::PermissionDenied,
-                format!("Room {} Player {} has already been stream ready", &self.id, pid),
+                format!("Room {} is locked!", &self.id)
             ));
         }
-        gtx.1 = true;
 
-        if self.game.get_player_num() == 4
-            && self.gamemsg_tx.len() == 4
-            && self.gamemsg_tx.iter().all(|(_, rd)| *rd)
-        {
-            self.state = RoomState::WaitReady;
+        let pid = self.game.add_player(p.name.clone());
+        let session = Self::new_session();
+        self.sessions.push(session.clone());
+        // the first one in runs the room
+        if self.host.is_empty() {
+            self.host = session.clone();
         }
+        self.lobby_changed();
 
-        Ok(())
+        Ok((pid, session))
     }
 
-    pub async fn send_gamemsg(&self, msg: Msg) {
-        for i in 0..self.gamemsg_tx.len() {
-            self.send_gamemsg_to(msg.clone(), i).await;
+    // the session must belong to player pid
+    pub fn check_session(&self, pid: usize, session: Option<&str>) -> RPCResult<()> {
+        let session = session.ok_or(
+            Status::new(
+                Code::Unauthenticated,
+                "Missing session!"
+            )
+        )?;
+
+        match self.sessions.get(pid) {
+            Some(s) if !s.is_empty() && s == session => Ok(()),
+            _ => Err(Status::new(
+                Code::PermissionDenied,
+                format!("Session does not match player {}!", pid)
+            )),
         }
     }
 
-    pub async fn send_gamemsg_to(&self, msg: Msg, to: usize) {
-        let (tx, rd) = self.gamemsg_tx.get(to).unwrap();
+    // the session must belong to someone in this room
+    pub fn check_member(&self, session: Option<&str>) -> RPCResult<usize> {
+        let session = session.ok_or(
+            Status::new(
+                Code::Unauthenticated,
+                "Missing session!"
+            )
+        )?;
 
-        if *rd {
-            tx.send(Ok(
-                GameMsg {
-                    msg: Some(msg),
-                    your_id: to as u32,
-                }
-            )).await.unwrap_or_else(
-                |e| error!("Cannot send gamemsg: {}", e)
-            );
+        self.sessions.iter().position(
+            |s| !s.is_empty() && s == session
+        ).ok_or(
+            Status::new(
+                Code::PermissionDenied,
+                format!("Not a member of room {}!", &self.id)
+            )
+        )
+    }
+
+    // anyone may watch a public room, a private one needs its password or a member
+    pub fn check_watcher(&self, session: Option<&str>, password_ok: bool) -> RPCResult<()> {
+        if password_ok || self.check_member(session).is_ok() {
+            Ok(())
         } else {
-            info!("Player {} not stream ready, skip.", to);
+            Err(Status::new(
+                Code::PermissionDenied,
+                format!("Wrong password for room {}!", &self.id)
+            ))
         }
     }
 
-    pub async fn send_gamemsg_except(&self, msg: Msg, except: usize) {
-        for i in 0..self.gamemsg_tx.len() {
-            if i != except {
-                self.send_gamemsg_to(msg.clone(), i).await;
-            }
-        }
+    fn new_session() -> String {
+        let mut r = rng();
+        format!("{:016x}{:016x}", r.random::<u64>(), r.random::<u64>())
     }
 
-    pub fn player_ready(&mut self, pid: usize) -> RPCResult<u32> {
-        if self.state != RoomState::WaitReady {
+    pub async fn add_bot(&mut self, kind: BotKind) -> RPCResult<usize> {
+        if self.state != RoomState::NotFull
+            || self.game.get_player_num() >= self.game.get_seats()
+        {
             return Err(Status::new(
-                Code::PermissionDenied,
-                format!("Room {} is not full or game has begun!", &self.id)
-            ))
+                Code::ResourceExhausted,
+                format!("Room {} is full!", &self.id)
+            ));
+        }
+        // bot seat must line up with its pid
+        if self.seats.len() != self.game.get_player_num() {
+            return Err(Status::new(
+                Code::Unavailable,
+                format!("Someone is joining room {}, please retry!", &self.id)
+            ));
         }
 
-        let not_ready_cnt = self.game.player_ready(pid)?;
+        let pid = self.game.add_player(format!("Bot-{}", self.seats.len()));
+        // nobody resumes a bot
+        self.sessions.push(String::new());
 
-        self.player_alive = true;
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        self.seats.push(Seat {
+            tx,
+            stream_ready: true,
+            bot: Some(kind),
+            stand_in: None,
+            stood_in: false,
+            chats: VecDeque::new(),
+            reactions: VecDeque::new(),
+        });
+        bot::spawn_bot(self.myself.clone(), rx, bot::new_strategy(kind));
+
+        self.check_full();
+        self.lobby_changed();
+
+        let msg = Msg::RoomInfo(self.get_room_info()?);
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(pid)
+    }
+
+    fn human_num(&self) -> usize {
+        self.seats.iter().filter(|s| s.bot.is_none()).count()
+    }
+
+    // return the bots that got ready
+    fn check_full(&mut self) -> Vec<usize> {
+        let seats = self.game.get_seats();
+        if self.game.get_player_num() == seats
+            && self.seats.len() == seats
+            && self.seats.iter().all(|s| s.stream_ready)
+        {
+            self.state = RoomState::WaitReady;
+            let bots = self.bots_get_ready();
+            self.lobby_changed();
+            bots
+        } else {
+            vec![]
+        }
+    }
+
+    // bots are always ready once the room enters WaitReady
+    fn bots_get_ready(&mut self) -> Vec<usize> {
+        let bots: Vec<usize> = self.seats.iter().enumerate().filter_map(
+            |(i, s)| s.bot.is_some().then_some(i)
+        ).collect();
+
+        for pid in bots.iter() {
+            self.game.player_ready(*pid).unwrap_or_else(
+                |e| { error!("Bot {} cannot get ready: {}", pid, e); 0 }
+            );
+        }
+
+        bots
+    }
+
+    pub fn get_game_stream_rx(&mut self, pid: usize) -> RPCResult<super::room::MsgRX> {
+        if self.state != RoomState::NotFull {
+            return Err(Status::new(
+                Code::ResourceExhausted,
+                format!("Room {} is full!", &self.id)
+            ));
+        }
+        if pid < self.seats.len() {
+            return Err(Status::new(
+                Code::AlreadyExists,
+                format!("Room {} Player {} already exists", &self.id, pid),
+            ));
+        }
+
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        self.seats.push(Seat {
+            tx,
+            stream_ready: false,
+            bot: None,
+            stand_in: None,
+            stood_in: false,
+            chats: VecDeque::new(),
+            reactions: VecDeque::new(),
+        });
+
+        Ok(rx)
+    }
+
+    // swap in a new stream for the player holding this session,
+    // whose first message tells the client where the room is now
+    pub fn resume_session(&mut self, session: &str) -> RPCResult<MsgRX> {
+        let pid = self.sessions.iter().position(
+            |s| !s.is_empty() && s == session
+        ).ok_or(
+            Status::new(
+                Code::NotFound,
+                format!("Session not found in room {}", &self.id),
+            )
+        )?;
+
+        let seat = self.seats.get_mut(pid).ok_or(
+            Status::new(
+                Code::FailedPrecondition,
+                format!("Room {} Player {} has never opened a GameStream", &self.id, pid),
+            )
+        )?;
+
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        seat.tx = tx;
+        seat.stream_ready = true;
+        // player is back, stop the stand-in bot
+        seat.stand_in = None;
+
+        if self.state == RoomState::NotFull {
+            self.check_full();
+        }
+
+        let msg = Msg::Resume(self.get_resume_info(Some(pid))?);
+        info!("Sending GameMsg to {} only: {:?}", pid, msg);
+        // fresh channel, never full
+        self.seats[pid].tx.try_send(Ok(GameMsg {
+            msg: Some(msg),
+            your_id: pid as u32,
+            seq: self.seq,
+        })).unwrap_or_else(
+            |e| error!("Cannot send gamemsg: {}", e)
+        );
+        self.player_alive = true;
+
+        Ok(rx)
+    }
+
+    pub fn add_spectator(&mut self) -> RPCResult<MsgRX> {
+        self.spectators.retain(|tx| !tx.is_closed());
+
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        let msg = Msg::Resume(self.get_resume_info(None)?);
+        info!("Sending GameMsg to new spectator: {:?}", msg);
+        // fresh channel, never full
+        tx.try_send(Ok(GameMsg {
+            msg: Some(msg),
+            your_id: 0,
+            seq: self.seq,
+        })).unwrap_or_else(
+            |e| error!("Cannot send gamemsg: {}", e)
+        );
+        self.spectators.push(tx);
+
+        Ok(rx)
+    }
+
+    // None for spectators, who see no cards and no holds
+    fn get_resume_info(&self, pid: Option<usize>) -> RPCResult<ResumeInfo> {
+        let mut ri = ResumeInfo {
+            room_info: Some(self.get_room_info()?),
+            ..Default::default()
+        };
+
+        match self.state {
+            RoomState::Gaming => {
+                ri.start = self.game.get_start() as u32;
+                let plays = if let Some(pid) = pid {
+                    ri.cards = self.game.get_dealt_cards(pid)?.into_iter().map(
+                        |c| c.into()
+                    ).collect();
+                    self.game.get_history_for(pid)
+                } else {
+                    self.game.get_public_history()
+                };
+                ri.plays = plays.into_iter().map(|p| p.into()).collect();
+            }
+            RoomState::EndGame => {
+                ri.endgame = Some(self.game.end_game()?);
+            }
+            _ => {}
+        }
+
+        Ok(ri)
+    }
+
+    pub async fn stream_ready(&mut self, pid: usize) -> RPCResult<()> {
+        if self.state != RoomState::NotFull {
+            return Err(Status::new(
+                Code::ResourceExhausted,
+                format!("Room {} is full!", &self.id)
+            ));
+        }
+        if pid >= self.seats.len() {
+            return Err(Status::new(
+                Code::NotFound,
+                format!("Room {} Player {} does not exist", &self.id, pid),
+            ));
+        }
+        let seat = self.seats.get_mut(pid).unwrap();
+        if seat.stream_ready {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                format!("Room {} Player {} has already been stream ready", &self.id, pid),
+            ));
+        }
+        seat.stream_ready = true;
+
+        self.check_full();
+
+        let msg = Msg::RoomInfo(self.get_room_info()?);
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(())
+    }
+
+    pub async fn send_gamemsg(&mut self, msg: Msg) {
+        let seq = self.record_gamemsg(msg.clone());
+        self.deliver_gamemsg(msg, seq);
+    }
+
+    // give the msg the next seq and keep it for FetchSince
+    fn record_gamemsg(&mut self, msg: Msg) -> u64 {
+        let seq = self.next_seq();
+        for i in 0..self.seats.len() {
+            self.record_gamemsg_to(msg.clone(), seq, Some(i));
+        }
+        self.record_gamemsg_to(msg, seq, None);
+        seq
+    }
+
+    fn deliver_gamemsg(&mut self, msg: Msg, seq: u64) {
+        for i in 0..self.seats.len() {
+            self.deliver_gamemsg_to(msg.clone(), seq, i);
+        }
+        self.deliver_gamemsg_spectators(msg, seq);
+    }
+
+    fn next_seq(&mut self) -> u64 {
+        self.seq += 1;
+        self.seq
+    }
+
+    // None for spectators, bots never ask for missed msgs
+    fn record_gamemsg_to(&mut self, msg: Msg, seq: u64, to: Option<usize>) {
+        let session = match to {
+            Some(pid) => match self.sessions.get(pid) {
+                Some(s) if !s.is_empty() => Some(s.clone()),
+                _ => return,
+            },
+            None => None,
+        };
+
+        // each seq has a copy per seat, so keep the last seqs instead of entries
+        while let Some(old) = self.backlog.front() {
+            if old.gm.seq + DEFAULT_BACKLOG_SIZE as u64 > seq {
+                break;
+            }
+            self.dropped_seq = old.gm.seq;
+            self.backlog.pop_front();
+        }
+        self.backlog.push_back(SentMsg {
+            session,
+            gm: GameMsg {
+                msg: Some(msg),
+                your_id: to.unwrap_or(0) as u32,
+                seq,
+            },
+        });
+    }
+
+    // msgs after seq that were sent to the session, None for spectators
+    pub fn fetch_since(&self, session: Option<&str>, seq: u64) -> RPCResult<GameMsgList> {
+        if seq < self.dropped_seq {
+            return Err(Status::new(
+                Code::OutOfRange,
+                format!("GameMsg after {} in room {} are no longer kept", seq, &self.id),
+            ));
+        }
+
+        Ok(GameMsgList {
+            msgs: self.backlog.iter().filter(
+                |s| s.gm.seq > seq && s.session.as_deref() == session
+            ).map(|s| s.gm.clone()).collect()
+        })
+    }
+
+    async fn send_gamemsg_to(&mut self, msg: Msg, seq: u64, to: usize) {
+        self.record_gamemsg_to(msg.clone(), seq, Some(to));
+        self.deliver_gamemsg_to(msg, seq, to);
+    }
+
+    async fn send_gamemsg_except(&mut self, msg: Msg, seq: u64, except: usize) {
+        for i in 0..self.seats.len() {
+            if i != except {
+                self.send_gamemsg_to(msg.clone(), seq, i).await;
+            }
+        }
+    }
+
+    async fn send_gamemsg_spectators(&mut self, msg: Msg, seq: u64) {
+        self.record_gamemsg_to(msg.clone(), seq, None);
+        self.deliver_gamemsg_spectators(msg, seq);
+    }
+
+    // never wait on a stream, only the room sends on it so a free slot stays free,
+    // one that falls behind gets an error in its last slot and Full back
+    fn try_deliver(tx: &MsgTX, gm: GameMsg) -> Result<(), TrySendError<()>> {
+        if tx.is_closed() {
+            Err(TrySendError::Closed(()))
+        } else if tx.capacity() > 1 {
+            let _ = tx.try_send(Ok(gm));
+            Ok(())
+        } else {
+            let _ = tx.try_send(Err(Status::new(
+                Code::Unavailable,
+                "Falling behind the room, please resume!"
+            )));
+            Err(TrySendError::Full(()))
+        }
+    }
+
+    // spectators see everything from seat 0, the ones falling behind are dropped
+    fn deliver_gamemsg_spectators(&mut self, msg: Msg, seq: u64) {
+        let gm = GameMsg {
+            msg: Some(msg),
+            your_id: 0,
+            seq,
+        };
+        let id = &self.id;
+        self.spectators.retain(|tx| match Self::try_deliver(tx, gm.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                warn!("Spectator of room {} falls behind, dropping it", id);
+                false
+            }
+            Err(TrySendError::Closed(_)) => false,
+        });
+    }
+
+    // a human falling behind is cut off, taken as lost and resumes from the backlog
+    fn deliver_gamemsg_to(&mut self, msg: Msg, seq: u64, to: usize) {
+        let seat = self.seats.get_mut(to).unwrap();
+        let gm = GameMsg {
+            msg: Some(msg),
+            your_id: to as u32,
+            seq,
+        };
+
+        // bots keep their channel, an error would stop them
+        if let Some(ref tx) = seat.stand_in {
+            tx.try_send(Ok(gm.clone())).unwrap_or_else(
+                |e| error!("Cannot send gamemsg to stand-in bot: {}", e)
+            );
+        }
+
+        if seat.stand_in.is_some() && seat.tx.is_closed() {
+            // known to be gone, no need to complain again
+        } else if seat.stream_ready && seat.bot.is_some() {
+            seat.tx.try_send(Ok(gm)).unwrap_or_else(
+                |e| error!("Cannot send gamemsg to bot: {}", e)
+            );
+        } else if seat.stream_ready {
+            match Self::try_deliver(&seat.tx, gm) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    warn!("Player {} of room {} falls behind, closing the stream", to, self.id);
+                    // the receiver goes with it, so the seat looks closed
+                    seat.tx = mpsc::channel(1).0;
+                }
+                Err(e) => error!("Cannot send gamemsg: {}", e),
+            }
+        } else {
+            info!("Player {} not stream ready, skip.", to);
+        }
+    }
+
+    pub async fn player_ready(&mut self, pid: usize) -> RPCResult<u32> {
+        if self.state != RoomState::WaitReady {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                format!("Room {} is not full or game has begun!", &self.id)
+            ))
+        }
+
+        let not_ready_cnt = self.game.player_ready(pid)?;
+
+        self.player_alive = true;
+
+        if not_ready_cnt != 0 {
+            let msg = Msg::WhoReady(pid as u32);
+            info!("Sending GameMsg: {:?}", msg);
+            self.send_gamemsg(msg).await;
+        } else {
+            // start game, skip the fourth WhoReady
+            self.start_game().await;
+        }
 
         Ok(not_ready_cnt)
     }
@@ -332,18 +1436,143 @@ impl Room {
             error!("Room {} is not full or game has begun!", &self.id);
         }
  
-        let mut cards: Vec<u32> = (0..=51).collect();
-        cards.shuffle(&mut rng());
-
-        self.game.new_game(cards).unwrap_or_else(
-            |e| error!("Cannot start game: {}", e)
+        let dealt = self.dealer.deal(self.game.get_seats()).and_then(
+            |cards| self.game.new_game(cards.clone()).map(|_| cards)
         );
+        let cards = match dealt {
+            Ok(cards) => cards,
+            Err(e) => {
+                error!("Room {} cannot deal: {}", &self.id, e);
+                self.deal_failed(e.to_string()).await;
+                return;
+            }
+        };
+        self.hands += 1;
+        self.deal = cards;
+        self.play_times.clear();
+        self.seats.iter_mut().for_each(|s| s.stood_in = false);
+        if let Some(mi) = self.match_info.as_mut().filter(|mi| mi.over) {
+            info!("Room {} starts a new match", &self.id);
+            *mi = MatchInfo::new(mi.get_config(), self.game.get_seats());
+        }
 
         self.state = RoomState::Gaming;
+        self.lobby_changed();
 
         let msg = Msg::Start(self.game.get_next() as u32);
         info!("Sending GameMsg: {:?}", msg);
         self.send_gamemsg(msg).await;
+
+        self.fill_banks();
+        self.start_turn().await;
+    }
+
+    // stay in WaitReady with nobody but the bots ready, so the players can retry
+    async fn deal_failed(&mut self, e: String) {
+        self.game.reset_ready();
+        let msg = Msg::DealFailed(e);
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+        for bot in self.bots_get_ready() {
+            self.send_gamemsg(Msg::WhoReady(bot as u32)).await;
+        }
+    }
+
+    fn fill_banks(&mut self) {
+        let bank = self.turn_timer.map_or(0, |tt| tt.bank_secs);
+        self.banks = vec![time::Duration::from_secs(bank as u64); self.game.get_seats()];
+    }
+
+    // put whoever plays next on the clock, without telling anyone
+    fn arm_turn(&mut self) {
+        self.turn = None;
+        let Some(tt) = self.turn_timer else {
+            return;
+        };
+        if self.state != RoomState::Gaming {
+            return;
+        }
+
+        let pid = self.game.get_next();
+        let bank = self.banks.get(pid).copied().unwrap_or_default();
+        let started = time::Instant::now();
+        self.turn = Some(TurnClock {
+            pid,
+            started,
+            deadline: started + time::Duration::from_secs(tt.turn_secs as u64) + bank,
+        });
+    }
+
+    // tell everyone how long is left of the current turn
+    async fn announce_turn(&mut self) {
+        let Some(turn) = self.turn else {
+            return;
+        };
+
+        let left = turn.deadline.saturating_duration_since(time::Instant::now());
+        let bank = self.banks.get(turn.pid).copied().unwrap_or_default();
+        let msg = Msg::TurnDeadline(TurnDeadline {
+            player: turn.pid as u32,
+            millis: left.as_millis() as u32,
+            bank_millis: left.min(bank).as_millis() as u32,
+        });
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+    }
+
+    // whoever is gone by now is not waited for
+    async fn start_turn(&mut self) {
+        self.takeover_closed_seats().await;
+        self.arm_turn();
+        self.announce_turn().await;
+    }
+
+    // take what the player went over the turn out of the bank
+    fn stop_turn(&mut self) {
+        let (Some(turn), Some(tt)) = (self.turn.take(), self.turn_timer) else {
+            return;
+        };
+
+        let over = turn.started.elapsed().saturating_sub(
+            time::Duration::from_secs(tt.turn_secs as u64)
+        );
+        if let Some(bank) = self.banks.get_mut(turn.pid) {
+            *bank = bank.saturating_sub(over);
+        }
+    }
+
+    fn turn_deadline(&self) -> time::Instant {
+        self.turn.map_or_else(time::Instant::now, |t| t.deadline)
+    }
+
+    // out of time, play for them the way the heuristic bot would
+    async fn turn_timeout(&mut self) {
+        let Some(turn) = self.turn.take() else {
+            return;
+        };
+        if self.state != RoomState::Gaming || self.game.get_next() != turn.pid {
+            return;
+        }
+
+        if let Some(bank) = self.banks.get_mut(turn.pid) {
+            *bank = time::Duration::ZERO;
+        }
+        let play = match self.game.get_player_view(turn.pid) {
+            Ok(view) => HeuristicBot::new().choose_play(&view),
+            Err(e) => {
+                error!("Cannot see the cards of player {}: {}", turn.pid, e);
+                None
+            }
+        };
+        let Some(play) = play else {
+            error!("Player {} of room {} has nothing to play", turn.pid, self.id);
+            return;
+        };
+
+        info!("Player {} of room {} ran out of time, playing {:?}", turn.pid, self.id, play);
+        self.play_and_broadcast(play.into()).await.unwrap_or_else(
+            |e| error!("Cannot play for player {}: {}", turn.pid, e)
+        );
     }
 
     pub fn get_game_info(&self, pid: u32) -> RPCResult<GameInfo> {
@@ -368,6 +1597,17 @@ impl Room {
         })
     }
 
+    pub fn get_game_snapshot(&self, pid: Option<usize>) -> RPCResult<GameSnapshot> {
+        if self.state != RoomState::Gaming {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                "Not gaming!"
+            ));
+        }
+
+        Ok(self.game.get_snapshot(pid)?)
+    }
+
     pub fn play_card(&mut self, p: Play) -> RPCResult<bool> {
         if self.state != RoomState::Gaming {
             return Err(Status::new(
@@ -377,12 +1617,237 @@ impl Room {
         }
 
         let endgame = self.game.play_card(p)?;
+        self.play_times.push(now_ms());
+        self.stop_turn();
 
         self.player_alive = true;
 
         Ok(endgame)
     }
 
+    // play, then tell everyone, masking the held card for the others
+    pub async fn play_and_broadcast(&mut self, mut pi: PlayInfo) -> RPCResult<()> {
+        let who = pi.player as usize;
+        let is_discard = pi.playone.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty PlayCard"
+            )
+        )?.is_discard;
+
+        let endgame = self.play_card(pi.clone().into())?;
+
+        if is_discard {
+            let msg = Msg::Play(pi);
+            info!("Sending GameMsg: {:?}", msg);
+            self.send_gamemsg(msg).await;
+        } else {
+            // both versions are the same event
+            let seq = self.next_seq();
+            let msg = Msg::Play(pi.clone());
+            info!("Sending GameMsg to {} only: {:?}", who, msg);
+            self.send_gamemsg_to(msg, seq, who).await;
+
+            pi.playone.as_mut().unwrap().card = Some(DUMMY_CARD.clone().into());
+            let msg = Msg::Play(pi);
+            info!("Sending GameMsg except {}: {:?}", who, msg);
+            self.send_gamemsg_except(msg.clone(), seq, who).await;
+            self.send_gamemsg_spectators(msg, seq).await;
+        }
+
+        if endgame {
+            let ge = self.end_game()?;
+            self.write_replay(ge.clone()).await;
+            self.record_profiles(&ge).await;
+            let msg = Msg::Endgame(ge);
+            info!("Sending GameMsg: {:?}", msg);
+            self.send_gamemsg(msg).await;
+            self.release_stand_ins();
+        } else {
+            self.start_turn().await;
+        }
+
+        Ok(())
+    }
+
+    async fn write_replay(&self, ending: GameEnding) {
+        let Some(replays) = &self.replays else {
+            return;
+        };
+
+        let plays = self.game.get_history().into_iter().zip(&self.play_times).map(
+            |(play, &at)| TimedPlay { at, play }
+        ).collect();
+        let rec = GameRecord::new(
+            self.game.get_player_names(), self.game.get_rules(),
+            self.deal.clone(), plays, ending
+        );
+        replays.write(&self.id, &rec).await.unwrap_or_else(
+            |e| error!("Cannot write replay of room {}: {}", self.id, e)
+        );
+    }
+
+    // a human a stand-in played for is rated like a bot, the hand isn't theirs
+    async fn record_profiles(&self, ending: &GameEnding) {
+        let humans: Vec<bool> = self.seats.iter().map(
+            |s| s.bot.is_none() && !s.stood_in
+        ).collect();
+        let names = self.game.get_player_names();
+        self.profiles.record_hand(&names, &humans, ending).await.unwrap_or_else(
+            |e| error!("Cannot save profiles of room {}: {}", self.id, e)
+        );
+    }
+
+    // let a bot play the seat of a human who stopped responding
+    pub async fn bot_takeover(&mut self, pid: usize) -> RPCResult<()> {
+        if self.state != RoomState::Gaming {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                "Not gaming!"
+            ));
+        }
+
+        let seat = self.seats.get_mut(pid).ok_or(
+            Status::new(
+                Code::NotFound,
+                format!("Room {} Player {} does not exist", &self.id, pid),
+            )
+        )?;
+        if seat.bot.is_some() || seat.stand_in.is_some() {
+            return Ok(());
+        }
+
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        seat.stand_in = Some(tx);
+        seat.stood_in = true;
+        bot::spawn_bot(self.myself.clone(), rx, bot::new_strategy(BotKind::Heuristic));
+
+        let msg = Msg::BotTakeover(pid as u32);
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(())
+    }
+
+    async fn takeover_closed_seats(&mut self) {
+        let closed: Vec<usize> = self.seats.iter().enumerate().filter_map(
+            |(i, s)| (s.bot.is_none() && s.stand_in.is_none() && s.tx.is_closed()).then_some(i)
+        ).collect();
+
+        for pid in closed {
+            info!("Player {} lost game stream, bot takes over", pid);
+            self.bot_takeover(pid).await.unwrap_or_else(
+                |e| error!("Bot cannot take over player {}: {}", pid, e)
+            );
+        }
+    }
+
+    // dropping the channel stops the stand-in bot task
+    fn release_stand_ins(&mut self) {
+        self.seats.iter_mut().for_each(|s| s.stand_in = None);
+    }
+
+    fn is_bot_turn(&self, pid: usize) -> bool {
+        self.state == RoomState::Gaming
+            && self.game.get_next() == pid
+            && self.seats.get(pid).is_some_and(|s| s.bot.is_some() || s.stand_in.is_some())
+    }
+
+    // asked by bot task on every Start or Play it sees
+    fn bot_view(&self, pid: usize) -> RPCResult<Option<PlayerView>> {
+        if !self.is_bot_turn(pid) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.game.get_player_view(pid)?))
+    }
+
+    // things may have changed while the bot was thinking
+    async fn bot_play(&mut self, pid: usize, play: Play) -> RPCResult<()> {
+        if !self.is_bot_turn(pid) {
+            return Ok(());
+        }
+
+        self.play_and_broadcast(play.into()).await
+    }
+
+    pub async fn send_chat(&mut self, pid: usize, text: &str) -> RPCResult<()> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "Empty chat message!"
+            ));
+        }
+        if text.chars().count() > CHAT_MAX_CHARS {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                format!("Chat message longer than {} characters!", CHAT_MAX_CHARS)
+            ));
+        }
+        // would mess up the clients' screens
+        if text.chars().any(char::is_control) {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "Control characters in chat message!"
+            ));
+        }
+
+        let seat = self.seats.get_mut(pid).ok_or(
+            Status::new(
+                Code::NotFound,
+                format!("Room {} Player {} does not exist", &self.id, pid),
+            )
+        )?;
+        if !throttle(&mut seat.chats, CHAT_BURST, CHAT_WINDOW) {
+            return Err(Status::new(
+                Code::ResourceExhausted,
+                "Too many chat messages, please slow down!"
+            ));
+        }
+
+        let msg = Msg::Chat(ChatMsg {
+            player: pid as u32,
+            name: self.game.get_player_names().get(pid).cloned().unwrap_or_default(),
+            text: text.into(),
+        });
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(())
+    }
+
+    pub async fn send_reaction(&mut self, pid: usize, reaction: Reaction) -> RPCResult<()> {
+        if self.state != RoomState::Gaming {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                "Room is not gaming!"
+            ))
+        }
+
+        let seat = self.seats.get_mut(pid).ok_or(
+            Status::new(
+                Code::NotFound,
+                format!("Room {} Player {} does not exist", &self.id, pid),
+            )
+        )?;
+        if !throttle(&mut seat.reactions, REACTION_BURST, REACTION_WINDOW) {
+            return Err(Status::new(
+                Code::ResourceExhausted,
+                "Too many reactions, please slow down!"
+            ));
+        }
+
+        let msg = Msg::Reaction(ReactionMsg {
+            player: pid as u32,
+            reaction: reaction.into(),
+        });
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(())
+    }
+
     pub fn end_game(&mut self) -> RPCResult<GameEnding> {
         if self.state != RoomState::Gaming {
             return Err(Status::new(
@@ -392,11 +1857,32 @@ impl Room {
         }
 
         let ge = self.game.end_game()?;
+        if let Some(mi) = self.match_info.as_mut() {
+            mi.add_hand(&ge)?;
+            info!("Room {} match after {} hands: {:?}", self.id, mi.hands, mi.totals);
+        }
         self.state = RoomState::EndGame;
+        self.lobby_changed();
 
         Ok(ge)
     }
 
+    pub fn get_match_info(&self) -> RPCResult<MatchInfo> {
+        self.match_info.clone().ok_or(
+            Status::new(
+                Code::NotFound,
+                format!("Room {} is not playing a match!", &self.id)
+            )
+        )
+    }
+
+    // totals are by seat, so a match does not outlive its players
+    fn reset_match(&mut self) {
+        if let Some(mi) = self.match_info.as_mut() {
+            *mi = MatchInfo::new(mi.get_config(), self.game.get_seats());
+        }
+    }
+
     pub async fn exit_game(&mut self, pid: usize) -> RPCResult<()> {
         match self.state {
             RoomState::NotFull =>
@@ -408,17 +1894,47 @@ impl Room {
             _ => {
                 self.game.player_exit_game(pid)?;
                 self.state = RoomState::WaitReady;
+                self.turn = None;
+                self.release_stand_ins();
+                self.lobby_changed();
                 self.send_gamemsg(Msg::ExitGame(pid as u32)).await;
+                for bot in self.bots_get_ready() {
+                    self.send_gamemsg(Msg::WhoReady(bot as u32)).await;
+                }
                 Ok(())
             }
         }
     }
 
-    pub fn exit_room(&mut self, pid: usize) -> RPCResult<usize> {
-        let left = self.game.player_exit(pid)?;
+    // return how many human players are left
+    pub async fn exit_room(&mut self, pid: usize) -> RPCResult<usize> {
+        self.remove_player(pid)?;
+
+        let left_ones = self.human_num();
+        if left_ones != 0 {
+            let msg = Msg::ExitRoom(self.get_room_info()?);
+            info!("Sending GameMsg: {:?}", msg);
+            self.send_gamemsg(msg).await;
+        }
+
+        Ok(left_ones)
+    }
+
+    // a seat may not be there yet if its stream was never opened
+    fn remove_player(&mut self, pid: usize) -> RPCResult<()> {
+        self.game.player_exit(pid)?;
         self.state = RoomState::NotFull;
-        self.gamemsg_tx.remove(pid);
-        Ok(left)
+        if pid < self.seats.len() {
+            self.seats.remove(pid);
+        }
+        self.sessions.remove(pid);
+        self.turn = None;
+        self.release_stand_ins();
+        self.reset_match();
+        self.pass_host();
+        self.lobby_changed();
+
+        Ok(())
     }
 
     pub fn kill_unready(&mut self) -> RPCResult<usize> {
@@ -428,13 +1944,184 @@ impl Room {
 
         let left = self.game.kill_unready()?;
 
-        let mut new_gtx = Vec::new();
+        let mut new_seats = Vec::new();
+        let mut new_sessions = Vec::new();
         for i in ready_list {
-            new_gtx.push(self.gamemsg_tx[i].clone());
+            new_seats.push(self.seats[i].clone());
+            new_sessions.push(self.sessions[i].clone());
         }
-        self.gamemsg_tx = new_gtx;
+        self.seats = new_seats;
+        self.sessions = new_sessions;
+        self.reset_match();
+        self.pass_host();
 
         self.state = RoomState::NotFull;
+        self.lobby_changed();
         Ok(left)
     }
+
+    fn host_pid(&self) -> Option<usize> {
+        self.sessions.iter().position(
+            |s| !s.is_empty() && *s == self.host
+        )
+    }
+
+    // the next human in, or nobody if only bots are left
+    fn pass_host(&mut self) {
+        if self.host_pid().is_some() {
+            return;
+        }
+        self.host = self.sessions.iter().find(
+            |s| !s.is_empty()
+        ).cloned().unwrap_or_default();
+    }
+
+    // only the host, and only before a game
+    fn check_host(&self, pid: usize) -> RPCResult<()> {
+        if self.state != RoomState::NotFull && self.state != RoomState::WaitReady {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                "Room is in a game!"
+            ))
+        }
+        if self.host_pid() != Some(pid) {
+            return Err(Status::new(
+                Code::PermissionDenied,
+                format!("Player {} is not the host of room {}!", pid, &self.id)
+            ))
+        }
+
+        Ok(())
+    }
+
+    fn check_target(&self, pid: usize, target: usize) -> RPCResult<()> {
+        if target >= self.game.get_player_num() {
+            return Err(Status::new(
+                Code::NotFound,
+                format!("Room {} Player {} does not exist", &self.id, target),
+            ))
+        }
+        if target == pid {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "The host cannot pick themselves!"
+            ))
+        }
+
+        Ok(())
+    }
+
+    async fn send_host_notice(&mut self, change: host_notice::Change) -> RPCResult<()> {
+        let msg = self.host_notice(change)?;
+        info!("Sending GameMsg: {:?}", msg);
+        self.send_gamemsg(msg).await;
+
+        Ok(())
+    }
+
+    fn host_notice(&self, change: host_notice::Change) -> RPCResult<Msg> {
+        Ok(Msg::HostNotice(HostNotice {
+            change: Some(change),
+            room: Some(self.get_room_info()?),
+        }))
+    }
+
+    pub async fn kick_player(&mut self, pid: usize, target: usize) -> RPCResult<()> {
+        self.check_host(pid)?;
+        self.check_target(pid, target)?;
+
+        let name = self.game.get_player_name(target);
+        info!("Room {} host {} kicks {} {}", self.id, pid, target, name);
+        if target >= self.seats.len() {
+            self.remove_player(target)?;
+            return self.send_host_notice(host_notice::Change::Kicked(name)).await
+        }
+
+        // the target gets Kicked and the others the notice, all under one seq
+        let seq = self.next_seq();
+        // dropping the seat below ends its stream right after this
+        self.send_gamemsg_to(Msg::Kicked(self.game.get_player_name(pid)), seq, target).await;
+        self.remove_player(target)?;
+        let msg = self.host_notice(host_notice::Change::Kicked(name))?;
+        info!("Sending GameMsg: {:?}", msg);
+        for i in 0..self.seats.len() {
+            self.send_gamemsg_to(msg.clone(), seq, i).await;
+        }
+        self.send_gamemsg_spectators(msg, seq).await;
+
+        Ok(())
+    }
+
+    pub async fn lock_room(&mut self, pid: usize, locked: bool) -> RPCResult<()> {
+        self.check_host(pid)?;
+
+        info!("Room {} locked: {}", self.id, locked);
+        self.locked = locked;
+        self.lobby_changed();
+        self.send_host_notice(host_notice::Change::Locked(locked)).await
+    }
+
+    pub async fn transfer_host(&mut self, pid: usize, target: usize) -> RPCResult<()> {
+        self.check_host(pid)?;
+        self.check_target(pid, target)?;
+        if self.sessions[target].is_empty() {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                format!("Room {} Player {} is a bot!", &self.id, target)
+            ))
+        }
+
+        let name = self.game.get_player_name(target);
+        info!("Room {} host passes from {} to {} {}", self.id, pid, target, name);
+        self.host = self.sessions[target].clone();
+        self.send_host_notice(host_notice::Change::NewHost(name)).await
+    }
+
+    // everything is checked before anything changes, and everyone gets ready again
+    pub async fn change_settings(&mut self, pid: usize, settings: RoomSettings) -> RPCResult<()> {
+        self.check_host(pid)?;
+
+        // 0 for the usual table
+        let seats = if settings.seats == 0 { DEFAULT_SEATS } else { settings.seats as usize };
+        dealer::check_seats(seats)?;
+        if self.game.get_player_num() > seats {
+            return Err(Status::new(
+                Code::FailedPrecondition,
+                format!("{} players cannot fit in {} seats!", self.game.get_player_num(), seats)
+            ))
+        }
+        let rules = settings.rules.unwrap_or_default();
+        rules.check()?;
+        if let Some(mc) = &settings.match_config {
+            mc.check()?;
+        }
+        if let Some(tt) = &settings.turn_timer {
+            tt.check()?;
+        }
+        let dealer = Self::new_dealer(&self.deal_config, seats, self.hands)?;
+
+        info!("Room {} settings changed to {:?}", self.id, settings);
+        self.game.set_rules(rules)?;
+        self.game.set_seats(seats)?;
+        self.game.reset_ready();
+        self.dealer = dealer;
+        self.match_info = settings.match_config.map(|mc| MatchInfo::new(mc, seats));
+        // a zero clock is no clock
+        self.turn_timer = settings.turn_timer.filter(|tt| tt.is_on());
+        self.state = RoomState::NotFull;
+        let bots = self.check_full();
+        self.lobby_changed();
+
+        let settings = RoomSettings {
+            rules: Some(rules),
+            seats: seats as u32,
+            match_config: settings.match_config,
+            turn_timer: self.turn_timer,
+        };
+        self.send_host_notice(host_notice::Change::Settings(settings)).await?;
+        for bot in bots {
+            self.send_gamemsg(Msg::WhoReady(bot as u32)).await;
+        }
+        Ok(())
+    }
 }
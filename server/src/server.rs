@@ -1,4 +1,5 @@
 use tokio_stream::{wrappers::ReceiverStream, Stream};
+use tokio::sync::{mpsc, broadcast::error::RecvError};
 use crate::room::RoomManager;
 use tonic::{Code, Request, Response, Status};
 use log::*;
@@ -11,18 +12,35 @@ pub struct Heart7D {
 }
 
 impl Heart7D {
+    pub fn new(rm: RoomManager) -> Self {
+        Self { rm }
+    }
+
     pub fn spawn_watch_dog(&self) {
         self.rm.spawn_watch_dog();
     }
 }
 
+fn get_session<T>(request: &Request<T>) -> Option<&str> {
+    request.metadata().get(SESSION_KEY).and_then(
+        |v| v.to_str().ok()
+    )
+}
+
+// empty if not given
+fn get_password<T>(request: &Request<T>) -> String {
+    request.metadata().get_bin(PASSWORD_KEY).and_then(
+        |v| v.to_bytes().ok()
+    ).map(|b| String::from_utf8_lossy(&b).into_owned()).unwrap_or_default()
+}
+
 #[tonic::async_trait]
 impl Heart7 for Heart7D {
     async fn hello(
         &self,
         request: Request<EmptyRequest>,
     ) -> Result<Response<CommonReply>, Status> {
-        info!("Got Hello request: {:?}", request);
+        info!("Got Hello request: {:?}", request.get_ref());
 
         Ok(Response::new(CommonReply{
             success: true,
@@ -33,15 +51,21 @@ impl Heart7 for Heart7D {
     async fn new_room(
         &self,
         request: Request<NewRoomReq>,
-    ) -> Result<Response<CommonReply>, Status> {
+    ) -> Result<Response<NewRoomReply>, Status> {
 
-        info!("Got NewRoom request: {:?}", request);
+        let req = request.get_ref();
+        // the password never goes to the log
+        info!("Got NewRoom request: {:?}", NewRoomReq { password: String::new(), ..req.clone() });
 
-        let _ = self.rm.new_room(&request.get_ref().roomid).await?;
+        let (roomid, _) = self.rm.new_room(
+            &req.roomid, req.deal.clone(), req.rules, req.seats as usize, req.match_config,
+            req.turn_timer, &req.password
+        ).await?;
 
-        let reply = CommonReply {
+        let reply = NewRoomReply {
             success: true,
             msg: "Ok".into(),
+            roomid,
         };
 
         info!("NewRoom response: {:?}", reply);
@@ -52,12 +76,12 @@ impl Heart7 for Heart7D {
         &self,
         request: Request<JoinRoomReq>,
     ) -> Result<Response<PlayerId>, Status> {
-        info!("Got JoinRoom request: {:?}", request);
+        let JoinRoomReq { player, roomid, password } = request.get_ref();
+        info!("Got JoinRoom request: player {:?}, roomid {}", player, roomid);
 
-        let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
-        let mut room = aroom.write().await;
+        let room = self.rm.get_room(roomid).await?;
 
-        let player = request.get_ref().player.as_ref().ok_or(
+        let player = player.as_ref().ok_or(
             Status::new(
                 Code::InvalidArgument,
                 "Empty PlayerInfo!"
@@ -71,10 +95,10 @@ impl Heart7 for Heart7D {
             ))
         }
 
-        let pid = room.add_player(&player)?;
+        let (pid, session) = room.join_room(player.clone(), password).await?;
 
         info!("JoinRoom response: PlayerId {}", pid);
-        Ok(Response::new(PlayerId{ your_id: pid as u32 }))
+        Ok(Response::new(PlayerId{ your_id: pid as u32, session }))
     }
 
     type GameStreamStream = Pin<Box<dyn Stream<Item = Result<GameMsg, Status>> + Send>>;
@@ -84,12 +108,11 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<Self::GameStreamStream>, Status> {
 
-        info!("Got GameStream request: {:?}", request);
+        info!("Got GameStream request: {:?}", request.get_ref());
 
         let RoomReq {roomid, playerid: pid} = &request.get_ref();
-        let aroom = self.rm.get_room(roomid).await?;
-        let mut room = aroom.write().await;
-        let rx = room.get_game_stream_rx(*pid as usize)?;
+        let room = self.rm.get_room(roomid).await?;
+        let rx = room.game_stream(*pid as usize, get_session(&request)).await?;
 
         Ok(Response::new(
             Box::pin(ReceiverStream::new(rx)) as Self::GameStreamStream
@@ -101,23 +124,11 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<CommonReply>, Status> {
 
-        info!("Got StreamReady request: {:?}", request);
+        info!("Got StreamReady request: {:?}", request.get_ref());
 
         let RoomReq {roomid, playerid: pid} = &request.get_ref();
-        let aroom = self.rm.get_room(roomid).await?;
-        let mut room = aroom.write().await;
-
-        room.stream_ready(*pid as usize)?;
-
-        {
-            let ar = aroom.clone();
-            tokio::spawn(async move {
-                let room = ar.read().await;
-                let msg = Msg::RoomInfo(room.get_room_info().unwrap());
-                info!("Sending GameMsg: {:?}", msg);
-                room.send_gamemsg(msg).await;
-            });
-        }
+        let room = self.rm.get_room(roomid).await?;
+        room.stream_ready(*pid as usize, get_session(&request)).await?;
 
         let reply = CommonReply {
             success: true,
@@ -133,43 +144,241 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<RoomInfo>, Status> {
 
-        info!("Got RoomStatus request: {:?}", request);
+        info!("Got RoomStatus request: {:?}", request.get_ref());
 
-        let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
-        let room_info = aroom.write().await.get_room_info()?;
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let room_info = room.room_status().await?;
 
         info!("RoomStatus response: {:?}", room_info);
         Ok(Response::new(room_info))
     }
 
+    async fn match_status(
+        &self,
+        request: Request<RoomReq>,
+    ) -> Result<Response<MatchInfo>, Status> {
+
+        info!("Got MatchStatus request: {:?}", request.get_ref());
+
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let match_info = room.match_status().await?;
+
+        info!("MatchStatus response: {:?}", match_info);
+        Ok(Response::new(match_info))
+    }
+
+    async fn get_profile(
+        &self,
+        request: Request<PlayerInfo>,
+    ) -> Result<Response<Profile>, Status> {
+
+        info!("Got GetProfile request: {:?}", request.get_ref());
+
+        let profile = self.rm.profiles().get(&request.get_ref().name).await;
+
+        info!("GetProfile response: {:?}", profile);
+        Ok(Response::new(profile))
+    }
+
+    async fn leaderboard(
+        &self,
+        request: Request<LeaderboardReq>,
+    ) -> Result<Response<ProfileList>, Status> {
+
+        info!("Got Leaderboard request: {:?}", request.get_ref());
+
+        let reply = self.rm.profiles().leaderboard(request.get_ref().limit as usize).await;
+
+        info!("Leaderboard response: {} profiles", reply.profiles.len());
+        Ok(Response::new(reply))
+    }
+
+    async fn send_chat(
+        &self,
+        request: Request<ChatReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got SendChat request: {:?}", request.get_ref());
+
+        let roomreq = request.get_ref().roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.send_chat(
+            roomreq.playerid as usize, request.get_ref().text.clone(), get_session(&request)
+        ).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("SendChat response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
+    async fn send_reaction(
+        &self,
+        request: Request<ReactionReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got SendReaction request: {:?}", request.get_ref());
+
+        let ReactionReq { roomreq, reaction } = request.get_ref();
+        let roomreq = roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+        let reaction = Reaction::try_from(*reaction).map_err(
+            |_| Status::new(
+                Code::InvalidArgument,
+                "Invalid Reaction!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.send_reaction(roomreq.playerid as usize, reaction, get_session(&request)).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("SendReaction response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
+    async fn kick_player(
+        &self,
+        request: Request<SeatReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got KickPlayer request: {:?}", request.get_ref());
+
+        let SeatReq { roomreq, target } = request.get_ref();
+        let roomreq = roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.kick_player(roomreq.playerid as usize, *target as usize, get_session(&request)).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("KickPlayer response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
+    async fn lock_room(
+        &self,
+        request: Request<LockReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got LockRoom request: {:?}", request.get_ref());
+
+        let LockReq { roomreq, locked } = request.get_ref();
+        let roomreq = roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.lock_room(roomreq.playerid as usize, *locked, get_session(&request)).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("LockRoom response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
+    async fn transfer_host(
+        &self,
+        request: Request<SeatReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got TransferHost request: {:?}", request.get_ref());
+
+        let SeatReq { roomreq, target } = request.get_ref();
+        let roomreq = roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.transfer_host(roomreq.playerid as usize, *target as usize, get_session(&request)).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("TransferHost response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
+    async fn change_settings(
+        &self,
+        request: Request<SettingsReq>,
+    ) -> Result<Response<CommonReply>, Status> {
+
+        info!("Got ChangeSettings request: {:?}", request.get_ref());
+
+        let SettingsReq { roomreq, settings } = request.get_ref();
+        let roomreq = roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+        let settings = settings.ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomSettings!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        room.change_settings(roomreq.playerid as usize, settings, get_session(&request)).await?;
+
+        let reply = CommonReply {
+            success: true,
+            msg: "Ok".into(),
+        };
+
+        info!("ChangeSettings response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
+
     async fn game_ready(
         &self,
         request: Request<RoomReq>,
     ) -> Result<Response<GameReadyReply>, Status> {
 
-        info!("Got GameReady request: {:?}", request);
-
-        let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
-        let mut room = aroom.write().await;
-
-        let left = room.player_ready(request.get_ref().playerid as usize)?;
-
-        if left != 0 {
-            let ar = aroom.clone();
-            tokio::spawn(async move {
-                let room = ar.read().await;
-                let msg = Msg::WhoReady(request.get_ref().playerid);
-                info!("Sending GameMsg: {:?}", msg);
-                room.send_gamemsg(msg).await;
-            });
-        } else {
-            // start game, skip the fourth WhoReady
-            let ar = aroom.clone();
-            tokio::spawn(async move {
-                let mut room = ar.write().await;
-                room.start_game().await;
-            });
-        }
+        info!("Got GameReady request: {:?}", request.get_ref());
+
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let left = room.game_ready(
+            request.get_ref().playerid as usize, get_session(&request)
+        ).await?;
 
         let reply = GameReadyReply{ left };
 
@@ -182,15 +391,30 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<GameInfo>, Status> {
 
-        info!("Got GameStatus request: {:?}", request);
+        info!("Got GameStatus request: {:?}", request.get_ref());
+
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let reply = room.game_status(
+            request.get_ref().playerid as usize, get_session(&request)
+        ).await?;
+
+        info!("GameStatus response: {:?}", reply);
+        Ok(Response::new(reply))
+    }
 
-        let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
+    async fn snapshot(
+        &self,
+        request: Request<RoomReq>,
+    ) -> Result<Response<GameSnapshot>, Status> {
 
-        let room = aroom.read().await;
+        info!("Got Snapshot request: {:?}", request.get_ref());
 
-        let reply = room.get_game_info(request.get_ref().playerid)?;
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let reply = room.snapshot(
+            request.get_ref().playerid as usize, get_session(&request), get_password(&request)
+        ).await?;
 
-        info!("GameStatus response: {:?}", reply);
+        info!("Snapshot response: {:?}", reply);
         Ok(Response::new(reply))
     }
 
@@ -199,7 +423,7 @@ impl Heart7 for Heart7D {
         request: Request<PlayReq>,
     ) -> Result<Response<CommonReply>, Status> {
 
-        info!("Got PlayCard request: {:?}", request);
+        info!("Got PlayCard request: {:?}", request.get_ref());
 
         let roomreq = &request.get_ref().roomreq.as_ref().ok_or(
             Status::new(
@@ -215,41 +439,12 @@ impl Heart7 for Heart7D {
             )
         )?;
 
-        let aroom = self.rm.get_room(&roomreq.roomid).await?;
-        let mut room = aroom.write().await;
-
-        let mut pi = PlayInfo {
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        let pi = PlayInfo {
             player: roomreq.playerid,
             playone: Some(playone.clone()),
         };
-        let who = roomreq.playerid as usize;
-        let endgame = room.play_card(pi.clone().into())?;
-
-        if playone.is_discard {
-            let msg = Msg::Play(pi);
-            info!("Sending GameMsg: {:?}", msg);
-            room.send_gamemsg(msg).await;
-        } else {
-            let msg = Msg::Play(pi.clone());
-            info!("Sending GameMsg to {} only: {:?}", who, msg);
-            room.send_gamemsg_to(msg, who).await;
-
-            pi.playone.as_mut().unwrap().card = Some(DUMMY_CARD.clone().into());
-            let msg = Msg::Play(pi);
-            info!("Sending GameMsg except {}: {:?}", who, msg);
-            room.send_gamemsg_except(msg, who).await;
-        }
-
-        if endgame {
-            let res = room.end_game()?;
-            let ar = aroom.clone();
-            tokio::spawn(async move {
-                let room = ar.read().await;
-                let msg = Msg::Endgame(res);
-                info!("Sending GameMsg: {:?}", msg);
-                room.send_gamemsg(msg).await;
-            });
-        }
+        room.play_card(pi, get_session(&request)).await?;
 
         let reply = CommonReply {
             success: true,
@@ -265,12 +460,10 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<CommonReply>, Status> {
 
-        info!("Got ExitGame request: {:?}", request);
-
-        let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
-        let mut room = aroom.write().await;
+        info!("Got ExitGame request: {:?}", request.get_ref());
 
-        room.exit_game(request.get_ref().playerid as usize).await?;
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        room.exit_game(request.get_ref().playerid as usize, get_session(&request)).await?;
 
         let reply = CommonReply {
             success: true,
@@ -286,23 +479,12 @@ impl Heart7 for Heart7D {
         request: Request<RoomReq>,
     ) -> Result<Response<CommonReply>, Status> {
 
-        info!("Got ExitRoom request: {:?}", request);
-
-        let left_ones = {
-            let aroom = self.rm.get_room(&request.get_ref().roomid).await?;
-            let mut room = aroom.write().await;
-            let left_ones = room.exit_room(request.get_ref().playerid as usize)?;
-            if left_ones != 0 {
-                let ar = aroom.clone();
-                tokio::spawn(async move {
-                    let room = ar.read().await;
-                    let msg = Msg::ExitRoom(room.get_room_info().unwrap());
-                    info!("Sending GameMsg: {:?}", msg);
-                    room.send_gamemsg(msg).await;
-                });
-            }
-            left_ones
-        };
+        info!("Got ExitRoom request: {:?}", request.get_ref());
+
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let left_ones = room.exit_room(
+            request.get_ref().playerid as usize, get_session(&request)
+        ).await?;
 
         if left_ones == 0 {
             self.rm.del_room(&request.get_ref().roomid).await?;
@@ -316,5 +498,125 @@ impl Heart7 for Heart7D {
         info!("ExitRoom response: {:?}", reply);
         Ok(Response::new(reply))
     }
-}
 
+    async fn add_bot(
+        &self,
+        request: Request<AddBotReq>,
+    ) -> Result<Response<PlayerId>, Status> {
+        info!("Got AddBot request: {:?}", request.get_ref());
+
+        let AddBotReq { roomid, kind } = request.get_ref();
+        let kind = BotKind::try_from(*kind).map_err(
+            |_| Status::new(
+                Code::InvalidArgument,
+                "Invalid BotKind!"
+            )
+        )?;
+
+        let room = self.rm.get_room(roomid).await?;
+        let pid = room.add_bot(kind, get_session(&request)).await?;
+
+        info!("AddBot response: PlayerId {}", pid);
+        Ok(Response::new(PlayerId{ your_id: pid as u32, session: String::new() }))
+    }
+
+    async fn fetch_since(
+        &self,
+        request: Request<FetchReq>,
+    ) -> Result<Response<GameMsgList>, Status> {
+
+        info!("Got FetchSince request: {:?}", request.get_ref());
+
+        let roomreq = request.get_ref().roomreq.as_ref().ok_or(
+            Status::new(
+                Code::InvalidArgument,
+                "Empty RoomReq!"
+            )
+        )?;
+
+        let room = self.rm.get_room(&roomreq.roomid).await?;
+        let reply = room.fetch_since(
+            roomreq.playerid as usize, get_session(&request), get_password(&request),
+            request.get_ref().seq
+        ).await?;
+
+        info!("FetchSince response: {} msgs", reply.msgs.len());
+        Ok(Response::new(reply))
+    }
+
+    type ResumeSessionStream = Pin<Box<dyn Stream<Item = Result<GameMsg, Status>> + Send>>;
+
+    async fn resume_session(
+        &self,
+        request: Request<ResumeReq>,
+    ) -> Result<Response<Self::ResumeSessionStream>, Status> {
+
+        info!("Got ResumeSession request for room {}", request.get_ref().roomid);
+
+        let ResumeReq {roomid, session} = &request.get_ref();
+        let room = self.rm.get_room(roomid).await?;
+        let rx = room.resume_session(session.clone()).await?;
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as Self::ResumeSessionStream
+        ))
+    }
+
+    async fn list_rooms(
+        &self,
+        request: Request<EmptyRequest>,
+    ) -> Result<Response<RoomList>, Status> {
+        info!("Got ListRooms request: {:?}", request.get_ref());
+
+        let reply = self.rm.list_rooms().await;
+
+        info!("ListRooms response: {} rooms", reply.rooms.len());
+        Ok(Response::new(reply))
+    }
+
+    type LobbyStreamStream = Pin<Box<dyn Stream<Item = Result<RoomList, Status>> + Send>>;
+
+    async fn lobby_stream(
+        &self,
+        request: Request<EmptyRequest>,
+    ) -> Result<Response<Self::LobbyStreamStream>, Status> {
+        info!("Got LobbyStream request: {:?}", request.get_ref());
+
+        let rm = self.rm.clone();
+        let mut changed = rm.subscribe_lobby();
+        let (tx, rx) = mpsc::channel(DEFAULT_CHANNEL_SIZE);
+        tokio::spawn(async move {
+            loop {
+                if tx.send(Ok(rm.list_rooms().await)).await.is_err() {
+                    break;
+                }
+                // lagging behind is fine, we always send the whole list
+                match changed.recv().await {
+                    Ok(()) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            info!("Lobby stream closed");
+        });
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as Self::LobbyStreamStream
+        ))
+    }
+
+    type SpectateStream = Pin<Box<dyn Stream<Item = Result<GameMsg, Status>> + Send>>;
+
+    async fn spectate(
+        &self,
+        request: Request<RoomReq>,
+    ) -> Result<Response<Self::SpectateStream>, Status> {
+        info!("Got Spectate request: {:?}", request.get_ref());
+
+        let room = self.rm.get_room(&request.get_ref().roomid).await?;
+        let rx = room.spectate(get_session(&request), get_password(&request)).await?;
+
+        Ok(Response::new(
+            Box::pin(ReceiverStream::new(rx)) as Self::SpectateStream
+        ))
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+use prost::Message;
+use std::io;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use tokio::fs;
+
+// where rooms are kept, so that they survive a restart
+#[tonic::async_trait]
+pub trait RoomStore: Send + Sync + std::fmt::Debug {
+    async fn save(&self, rec: &RoomRecord) -> io::Result<()>;
+    async fn remove(&self, roomid: &str) -> io::Result<()>;
+    async fn load_all(&self) -> io::Result<Vec<RoomRecord>>;
+}
+
+// keeps nothing, rooms go away with the server
+#[derive(Debug, Default)]
+pub struct NoStore;
+
+#[tonic::async_trait]
+impl RoomStore for NoStore {
+    async fn save(&self, _rec: &RoomRecord) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn remove(&self, _roomid: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    async fn load_all(&self) -> io::Result<Vec<RoomRecord>> {
+        Ok(vec![])
+    }
+}
+
+// one protobuf file per room
+#[derive(Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+const ROOM_FILE_EXT: &str = "room";
+
+// room IDs are whatever players typed, so hex them for a safe file name
+pub fn hex_name(roomid: &str) -> String {
+    roomid.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+impl FileStore {
+    pub async fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir).await?;
+        Ok(Self { dir })
+    }
+
+    fn path_of(&self, roomid: &str) -> PathBuf {
+        self.dir.join(hex_name(roomid)).with_extension(ROOM_FILE_EXT)
+    }
+}
+
+#[tonic::async_trait]
+impl RoomStore for FileStore {
+    async fn save(&self, rec: &RoomRecord) -> io::Result<()> {
+        let roomid = rec.info.as_ref().map(|i| i.roomid.as_str()).unwrap_or_default();
+        let path = self.path_of(roomid);
+        // never leave a half written room behind
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, rec.encode_to_vec()).await?;
+        fs::rename(&tmp, &path).await
+    }
+
+    async fn remove(&self, roomid: &str) -> io::Result<()> {
+        match fs::remove_file(self.path_of(roomid)).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            r => r,
+        }
+    }
+
+    async fn load_all(&self) -> io::Result<Vec<RoomRecord>> {
+        let mut recs = Vec::new();
+        let mut dir = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let path = entry.path();
+            if path.extension() != Some(OsStr::new(ROOM_FILE_EXT)) {
+                continue;
+            }
+            match RoomRecord::decode(fs::read(&path).await?.as_slice()) {
+                Ok(rec) => recs.push(rec),
+                Err(e) => error!("Cannot decode room file {}: {}", path.display(), e),
+            }
+        }
+        Ok(recs)
+    }
+}